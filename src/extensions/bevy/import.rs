@@ -0,0 +1,370 @@
+use super::{BevyMesh3d, BevyVertexPayload3d};
+use crate::{
+    halfedge::{HalfEdgeImplMeshType, HalfEdgeMeshImpl},
+    math::{HasNormal, HasPosition, HasUV},
+    mesh::{
+        drop_unused_vertices, weld, DefaultEdgePayload, DefaultFacePayload, IndexedFaceError,
+        MeshBuilder, MeshType3D,
+    },
+};
+use bevy::{
+    math::{Vec2, Vec3},
+    render::mesh::{Mesh, PrimitiveTopology, VertexAttributeValues},
+};
+use std::collections::{HashMap, HashSet, VecDeque};
+
+/// Errors that can occur when converting a bevy mesh into a half-edge mesh.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BevyImportError {
+    /// Only `TriangleList` meshes can be converted.
+    UnsupportedTopology(PrimitiveTopology),
+
+    /// The mesh has no `Float32x3` position attribute.
+    MissingPositions,
+
+    /// An attribute has an unexpected format or length.
+    InvalidAttribute(String),
+
+    /// An index refers to a vertex that doesn't exist.
+    InvalidIndex {
+        /// The invalid index.
+        index: usize,
+        /// The number of vertices.
+        len: usize,
+    },
+
+    /// The triangles don't describe an oriented manifold surface.
+    Topology(IndexedFaceError),
+}
+
+impl std::fmt::Display for BevyImportError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BevyImportError::UnsupportedTopology(t) => {
+                write!(f, "Unsupported primitive topology {:?}", t)
+            }
+            BevyImportError::MissingPositions => write!(f, "The mesh has no positions"),
+            BevyImportError::InvalidAttribute(message) => write!(f, "{}", message),
+            BevyImportError::InvalidIndex { index, len } => {
+                write!(f, "Index {} is out of bounds for {} vertices", index, len)
+            }
+            BevyImportError::Topology(e) => write!(f, "Invalid topology: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for BevyImportError {}
+
+impl From<IndexedFaceError> for BevyImportError {
+    fn from(e: IndexedFaceError) -> Self {
+        BevyImportError::Topology(e)
+    }
+}
+
+/// Reads an optional attribute and checks that it has one value per vertex.
+fn attribute<'a, V>(
+    mesh: &'a Mesh,
+    id: impl Into<bevy::render::mesh::MeshVertexAttributeId>,
+    name: &str,
+    len: usize,
+    get: impl Fn(&'a VertexAttributeValues) -> Option<&'a [V]>,
+) -> Result<Option<&'a [V]>, BevyImportError> {
+    let Some(values) = mesh.attribute(id) else {
+        return Ok(None);
+    };
+    let values = get(values).ok_or_else(|| {
+        BevyImportError::InvalidAttribute(format!("Unsupported format of the {}", name))
+    })?;
+    if values.len() != len {
+        return Err(BevyImportError::InvalidAttribute(format!(
+            "Expected {} {} but got {}",
+            len,
+            name,
+            values.len()
+        )));
+    }
+    Ok(Some(values))
+}
+
+fn triangle_normal(positions: &[[f64; 3]], t: &[usize; 3]) -> [f64; 3] {
+    let [a, b, c] = t.map(|i| positions[i]);
+    let u = [b[0] - a[0], b[1] - a[1], b[2] - a[2]];
+    let v = [c[0] - a[0], c[1] - a[1], c[2] - a[2]];
+    let n = [
+        u[1] * v[2] - u[2] * v[1],
+        u[2] * v[0] - u[0] * v[2],
+        u[0] * v[1] - u[1] * v[0],
+    ];
+    let len = (n[0] * n[0] + n[1] * n[1] + n[2] * n[2]).sqrt();
+    if len == 0.0 {
+        n
+    } else {
+        n.map(|x| x / len)
+    }
+}
+
+/// Returns the boundary loop of the triangles if it is a single simple loop.
+fn boundary_loop(triangles: &[[usize; 3]]) -> Option<Vec<usize>> {
+    let edges: HashSet<(usize, usize)> = triangles
+        .iter()
+        .flat_map(|t| (0..3).map(move |i| (t[i], t[(i + 1) % 3])))
+        .collect();
+    let mut next = HashMap::new();
+    for &(a, b) in edges.iter() {
+        if !edges.contains(&(b, a)) && next.insert(a, b).is_some() {
+            // the boundary touches itself
+            return None;
+        }
+    }
+    let start = *next.keys().min()?;
+    let mut polygon = vec![start];
+    let mut current = next[&start];
+    while current != start {
+        polygon.push(current);
+        current = *next.get(&current)?;
+        if polygon.len() > next.len() {
+            return None;
+        }
+    }
+    // a single loop must use all boundary edges, otherwise the region has holes
+    (polygon.len() == next.len()).then_some(polygon)
+}
+
+/// Merges connected triangles whose normals deviate less than `angle` from the normal
+/// of the first triangle of their region into polygons.
+/// Regions that aren't bounded by a single simple loop are kept as triangles.
+fn merge_coplanar_triangles(
+    positions: &[[f64; 3]],
+    triangles: &[[usize; 3]],
+    angle: f64,
+) -> Vec<Vec<usize>> {
+    let normals: Vec<[f64; 3]> = triangles
+        .iter()
+        .map(|t| triangle_normal(positions, t))
+        .collect();
+    let mut by_edge = HashMap::new();
+    for (i, t) in triangles.iter().enumerate() {
+        for k in 0..3 {
+            by_edge.insert((t[k], t[(k + 1) % 3]), i);
+        }
+    }
+
+    let min_cos = angle.cos();
+    let mut visited = vec![false; triangles.len()];
+    let mut polygons = Vec::new();
+    for seed in 0..triangles.len() {
+        if visited[seed] {
+            continue;
+        }
+        visited[seed] = true;
+        let n0 = normals[seed];
+        let mut region = vec![seed];
+        let mut queue = VecDeque::from([seed]);
+        while let Some(i) = queue.pop_front() {
+            let t = triangles[i];
+            for k in 0..3 {
+                let Some(&j) = by_edge.get(&(t[(k + 1) % 3], t[k])) else {
+                    continue;
+                };
+                let n = normals[j];
+                if !visited[j] && n[0] * n0[0] + n[1] * n0[1] + n[2] * n0[2] >= min_cos {
+                    visited[j] = true;
+                    region.push(j);
+                    queue.push_back(j);
+                }
+            }
+        }
+
+        let region: Vec<[usize; 3]> = region.iter().map(|&i| triangles[i]).collect();
+        match boundary_loop(&region) {
+            Some(polygon) if region.len() > 1 => polygons.push(polygon),
+            _ => polygons.extend(region.iter().map(|t| t.to_vec())),
+        }
+    }
+    polygons
+}
+
+impl<T: HalfEdgeImplMeshType<VP = BevyVertexPayload3d> + MeshType3D<Vec = Vec3, S = f32>>
+    HalfEdgeMeshImpl<T>
+{
+    /// Convert a bevy `TriangleList` mesh into a half-edge mesh.
+    /// Vertices at exactly the same position are welded; see `from_bevy_ex`.
+    pub fn from_bevy(mesh: &Mesh) -> Result<Self, BevyImportError>
+    where
+        T::EP: DefaultEdgePayload,
+        T::FP: DefaultFacePayload,
+    {
+        Self::from_bevy_ex(mesh, 0.0, None)
+    }
+
+    /// Convert a bevy `TriangleList` mesh into a half-edge mesh.
+    ///
+    /// Vertices closer than `tolerance` are welded into a single vertex that keeps
+    /// the normal and uv coordinates of the first welded vertex.
+    /// Triangles that collapse when welding are skipped.
+    /// If `merge_coplanar` is given, adjacent triangles whose normals deviate less than
+    /// this angle (in radians) are merged into polygon faces.
+    pub fn from_bevy_ex(
+        mesh: &Mesh,
+        tolerance: f32,
+        merge_coplanar: Option<f32>,
+    ) -> Result<Self, BevyImportError>
+    where
+        T::EP: DefaultEdgePayload,
+        T::FP: DefaultFacePayload,
+    {
+        if mesh.primitive_topology() != PrimitiveTopology::TriangleList {
+            return Err(BevyImportError::UnsupportedTopology(
+                mesh.primitive_topology(),
+            ));
+        }
+        let positions = mesh
+            .attribute(Mesh::ATTRIBUTE_POSITION)
+            .and_then(|values| values.as_float3())
+            .ok_or(BevyImportError::MissingPositions)?;
+        let len = positions.len();
+        let normals = attribute(mesh, Mesh::ATTRIBUTE_NORMAL, "normals", len, |v| {
+            v.as_float3()
+        })?;
+        let uvs = attribute(
+            mesh,
+            Mesh::ATTRIBUTE_UV_0,
+            "uv coordinates",
+            len,
+            |v| match v {
+                VertexAttributeValues::Float32x2(values) => Some(values.as_slice()),
+                _ => None,
+            },
+        )?;
+
+        let indices: Vec<usize> = match mesh.indices() {
+            Some(indices) => indices.iter().collect(),
+            None => (0..len).collect(),
+        };
+        if let Some(&index) = indices.iter().find(|&&i| i >= len) {
+            return Err(BevyImportError::InvalidIndex { index, len });
+        }
+
+        let (welded, ids) = weld(
+            positions.iter().map(|p| p.map(|x| x as f64)),
+            tolerance as f64,
+        );
+        // the first vertex welded into each position provides the payload
+        let mut first = vec![usize::MAX; welded.len()];
+        for (i, &id) in ids.iter().enumerate().rev() {
+            first[id] = i;
+        }
+        let vps = first.iter().map(|&i| {
+            let mut vp = BevyVertexPayload3d::from_pos(Vec3::from_array(positions[i]));
+            if let Some(normals) = normals {
+                vp.set_normal(Vec3::from_array(normals[i]));
+            }
+            if let Some(uvs) = uvs {
+                vp.set_uv(Vec2::from_array(uvs[i]));
+            }
+            vp
+        });
+
+        let triangles: Vec<[usize; 3]> = indices
+            .chunks_exact(3)
+            .map(|t| [ids[t[0]], ids[t[1]], ids[t[2]]])
+            .filter(|t| t[0] != t[1] && t[1] != t[2] && t[2] != t[0])
+            .collect();
+        let polygons = match merge_coplanar {
+            Some(angle) => merge_coplanar_triangles(&welded, &triangles, angle as f64),
+            None => triangles.iter().map(|t| t.to_vec()).collect(),
+        };
+
+        // drop vertices that aren't used by any face, e.g., the inner vertices of merged regions
+        let mut faces: Vec<_> = polygons
+            .into_iter()
+            .map(|f| (f, T::FP::default()))
+            .collect();
+        let vps = drop_unused_vertices(&vps.collect::<Vec<_>>(), &mut faces);
+
+        let mut result = Self::default();
+        result.insert_indexed_faces(vps, faces)?;
+        Ok(result)
+    }
+}
+
+impl TryFrom<&Mesh> for BevyMesh3d {
+    type Error = BevyImportError;
+
+    fn try_from(mesh: &Mesh) -> Result<Self, Self::Error> {
+        Self::from_bevy(mesh)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{extensions::bevy::*, prelude::*};
+    use bevy::{
+        math::primitives::{Cuboid, Plane3d},
+        render::{
+            mesh::{Meshable, PrimitiveTopology},
+            render_asset::RenderAssetUsages,
+        },
+    };
+
+    #[test]
+    fn test_bevy_import_cuboid() {
+        // bevy duplicates the corners for each side of the cuboid
+        let cuboid = bevy::render::mesh::Mesh::from(Cuboid::new(1.0, 2.0, 3.0));
+        let mesh = BevyMesh3d::try_from(&cuboid).unwrap();
+        assert!(mesh.check().is_ok());
+        assert!(!mesh.is_open());
+        assert_eq!(mesh.num_vertices(), 8);
+        assert_eq!(mesh.num_faces(), 12);
+
+        let merged = BevyMesh3d::from_bevy_ex(&cuboid, 0.0, Some(0.01)).unwrap();
+        assert!(merged.check().is_ok());
+        assert_eq!(merged.num_vertices(), 8);
+        assert_eq!(merged.num_faces(), 6);
+        assert!(merged.faces().all(|f| f.num_vertices(&merged) == 4));
+    }
+
+    #[test]
+    fn test_bevy_import_subdivided_plane() {
+        // merging the 4x4 grid leaves the inner vertices without faces
+        let plane = bevy::render::mesh::Mesh::from(Plane3d::default().mesh().subdivisions(3));
+        let mesh = BevyMesh3d::from_bevy_ex(&plane, 0.0, Some(0.01)).unwrap();
+        assert!(mesh.check().is_ok());
+        assert_eq!(mesh.num_faces(), 1);
+        assert_eq!(mesh.num_vertices(), 16);
+        assert!(mesh.vertices().all(|v| v.edge(&mesh).is_some()));
+    }
+
+    #[test]
+    fn test_bevy_round_trip() {
+        let prism = BevyMesh3d::regular_prism(1.0, 1.0, 7);
+        let exported = prism.to_bevy(RenderAssetUsages::all());
+        let mesh = BevyMesh3d::from_bevy_ex(&exported, 1e-5, Some(1e-3)).unwrap();
+        assert!(mesh.check().is_ok());
+        assert_eq!(mesh.num_vertices(), 14);
+        assert_eq!(mesh.num_faces(), 9);
+        assert!(mesh
+            .is_isomorphic_by_pos::<_, 3, _, BevyMeshType3d32>(&prism, 1e-5)
+            .eq());
+    }
+
+    #[test]
+    fn test_bevy_import_errors() {
+        let lines =
+            bevy::render::mesh::Mesh::new(PrimitiveTopology::LineList, RenderAssetUsages::all());
+        assert!(matches!(
+            BevyMesh3d::try_from(&lines),
+            Err(BevyImportError::UnsupportedTopology(
+                PrimitiveTopology::LineList
+            ))
+        ));
+        let empty = bevy::render::mesh::Mesh::new(
+            PrimitiveTopology::TriangleList,
+            RenderAssetUsages::all(),
+        );
+        assert!(matches!(
+            BevyMesh3d::try_from(&empty),
+            Err(BevyImportError::MissingPositions)
+        ));
+    }
+}
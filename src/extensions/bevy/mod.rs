@@ -1,11 +1,13 @@
 //! This module contains the bevy-specific implementations
 
+mod import;
 mod math;
 mod mesh2d;
 mod mesh3d;
 mod vertex_payload_2d;
 mod vertex_payload_3d;
 
+pub use import::*;
 pub use math::*;
 pub use mesh2d::*;
 pub use mesh3d::*;
@@ -7,6 +7,7 @@ use crate::{
 
 /// Vertex Payload for Bevy with 2d position, and uv.
 #[derive(Clone, PartialEq, Default, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct BevyVertexPayload2d {
     /// The position of the vertex.
     position: Vec2,
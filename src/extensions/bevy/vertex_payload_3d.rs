@@ -7,6 +7,7 @@ use crate::{
 
 /// Vertex Payload for Bevy with 3d position, normal, and uv.
 #[derive(Clone, PartialEq, Default, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct BevyVertexPayload3d {
     /// The position of the vertex.
     position: Vec3,
@@ -0,0 +1,478 @@
+use crate::{
+    math::{HasNormal, HasPosition, HasUV, IndexType, Scalar, Vector},
+    mesh::{MeshBasics, MeshType3D, ModellingError, Triangulateable, VertexBasics},
+    tesselate::{TesselationMeta, TriangulationAlgorithm},
+};
+use std::fmt::Write;
+
+const FLOAT: u32 = 5126;
+const UNSIGNED_SHORT: u32 = 5123;
+const UNSIGNED_INT: u32 = 5125;
+const ARRAY_BUFFER: u32 = 34962;
+const ELEMENT_ARRAY_BUFFER: u32 = 34963;
+
+/// The vertex buffers of a triangulated mesh.
+#[derive(Debug, Clone)]
+struct GltfMesh {
+    name: String,
+    positions: Vec<[f32; 3]>,
+    normals: Vec<[f32; 3]>,
+    uvs: Vec<[f32; 2]>,
+    indices: Vec<u32>,
+    /// Whether the indices are written as u16 instead of u32.
+    short_indices: bool,
+}
+
+/// A glTF 2.0 document with one node per mesh.
+///
+/// The document is written either as a self-contained `.gltf` file with an embedded buffer
+/// or as a binary `.glb` file.
+#[derive(Debug, Clone, Default)]
+pub struct GltfDocument {
+    meshes: Vec<GltfMesh>,
+}
+
+fn escape_json(s: &str) -> String {
+    let mut res = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '"' => res.push_str("\\\""),
+            '\\' => res.push_str("\\\\"),
+            c if (c as u32) < 0x20 => write!(res, "\\u{:04x}", c as u32).unwrap(),
+            c => res.push(c),
+        }
+    }
+    res
+}
+
+fn base64(data: &[u8]) -> String {
+    const ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut res = String::with_capacity(data.len().div_ceil(3) * 4);
+    for chunk in data.chunks(3) {
+        let b = [
+            chunk[0],
+            chunk.get(1).copied().unwrap_or(0),
+            chunk.get(2).copied().unwrap_or(0),
+        ];
+        let n = ((b[0] as u32) << 16) | ((b[1] as u32) << 8) | b[2] as u32;
+        for i in 0..4 {
+            if i <= chunk.len() {
+                res.push(ALPHABET[((n >> (18 - 6 * i)) & 63) as usize] as char);
+            } else {
+                res.push('=');
+            }
+        }
+    }
+    res
+}
+
+impl GltfDocument {
+    /// Creates a new empty document.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of meshes in the document.
+    pub fn len(&self) -> usize {
+        self.meshes.len()
+    }
+
+    /// Returns whether the document contains no meshes.
+    pub fn is_empty(&self) -> bool {
+        self.meshes.is_empty()
+    }
+
+    /// Triangulates the mesh and adds it to the document as a separate node.
+    /// Uses the normals stored in the vertex payloads.
+    ///
+    /// Panics if a vertex position is not finite.
+    pub fn add_mesh<T: MeshType3D>(&mut self, name: &str, mesh: &T::Mesh) -> &mut Self
+    where
+        T::Mesh: Triangulateable<T>,
+        T::VP: HasNormal<3, T::Vec, S = T::S> + HasUV<T::Vec2, S = T::S>,
+    {
+        self.add_mesh_ex::<T>(name, mesh, TriangulationAlgorithm::default(), false)
+    }
+
+    /// Like `add_mesh`, but fails instead of panicking if a vertex position is not finite.
+    pub fn try_add_mesh<T: MeshType3D>(
+        &mut self,
+        name: &str,
+        mesh: &T::Mesh,
+    ) -> Result<&mut Self, ModellingError>
+    where
+        T::Mesh: Triangulateable<T>,
+        T::VP: HasNormal<3, T::Vec, S = T::S> + HasUV<T::Vec2, S = T::S>,
+    {
+        self.try_add_mesh_ex::<T>(name, mesh, TriangulationAlgorithm::default(), false)
+    }
+
+    /// Like `add_mesh`, but triangulates using the given algorithm.
+    /// If `flat_normals` is set, the vertices are duplicated per face and get the face normal.
+    ///
+    /// Meshes without triangles are skipped since glTF doesn't allow empty accessors.
+    pub fn add_mesh_ex<T: MeshType3D>(
+        &mut self,
+        name: &str,
+        mesh: &T::Mesh,
+        algorithm: TriangulationAlgorithm,
+        flat_normals: bool,
+    ) -> &mut Self
+    where
+        T::Mesh: Triangulateable<T>,
+        T::VP: HasNormal<3, T::Vec, S = T::S> + HasUV<T::Vec2, S = T::S>,
+    {
+        self.try_add_mesh_ex::<T>(name, mesh, algorithm, flat_normals)
+            .unwrap_or_else(|e| panic!("Failed to add the mesh to the glTF document: {}", e))
+    }
+
+    /// Like `add_mesh_ex`, but fails instead of panicking if a vertex position is not finite.
+    /// The bounds of the positions must be written to the document and json has no
+    /// representation for NaN or infinity.
+    pub fn try_add_mesh_ex<T: MeshType3D>(
+        &mut self,
+        name: &str,
+        mesh: &T::Mesh,
+        algorithm: TriangulationAlgorithm,
+        flat_normals: bool,
+    ) -> Result<&mut Self, ModellingError>
+    where
+        T::Mesh: Triangulateable<T>,
+        T::VP: HasNormal<3, T::Vec, S = T::S> + HasUV<T::Vec2, S = T::S>,
+    {
+        let f = |s: T::S| s.to_f64() as f32;
+        if let Some(v) = mesh.vertices().find(|v| {
+            let p = v.pos();
+            ![p.x(), p.y(), p.z()].iter().all(|&x| f(x).is_finite())
+        }) {
+            return Err(ModellingError::NonFinite(format!(
+                "The position of vertex {} of the mesh '{}' doesn't fit into a f32",
+                v.id(),
+                name
+            )));
+        }
+
+        let mut meta = TesselationMeta::default();
+        let (indices, vertices) = if flat_normals {
+            mesh.triangulate_and_generate_flat_normals_post(algorithm, &mut meta)
+        } else {
+            mesh.triangulate(algorithm, &mut meta)
+        };
+        if indices.is_empty() {
+            return Ok(self);
+        }
+
+        self.meshes.push(GltfMesh {
+            name: name.to_string(),
+            positions: vertices
+                .iter()
+                .map(|vp| [f(vp.pos().x()), f(vp.pos().y()), f(vp.pos().z())])
+                .collect(),
+            normals: vertices
+                .iter()
+                .map(|vp| [f(vp.normal().x()), f(vp.normal().y()), f(vp.normal().z())])
+                .collect(),
+            uvs: vertices
+                .iter()
+                .map(|vp| [f(vp.uv().x()), f(vp.uv().y())])
+                .collect(),
+            indices: indices
+                .iter()
+                .map(|i| u32::try_from(i.index()).expect("glTF indices must fit into u32"))
+                .collect(),
+            short_indices: std::mem::size_of::<T::V>() <= 2,
+        });
+        Ok(self)
+    }
+
+    /// Writes the binary buffer and the json description of the document.
+    /// `uri` is the uri of the buffer or `None` for GLB files.
+    fn write(&self, uri: Option<&str>, buffer: &mut Vec<u8>) -> String {
+        let mut views = Vec::new();
+        let mut accessors = Vec::new();
+        let mut meshes = Vec::new();
+
+        let mut push_view = |buffer: &mut Vec<u8>, data: Vec<u8>, target: u32| {
+            views.push(format!(
+                "{{\"buffer\":0,\"byteOffset\":{},\"byteLength\":{},\"target\":{}}}",
+                buffer.len(),
+                data.len(),
+                target
+            ));
+            buffer.extend_from_slice(&data);
+            // keep all views aligned to 4 bytes
+            buffer.resize(buffer.len().next_multiple_of(4), 0);
+            views.len() - 1
+        };
+
+        for mesh in self.meshes.iter() {
+            let mut min = [f32::INFINITY; 3];
+            let mut max = [f32::NEG_INFINITY; 3];
+            for p in mesh.positions.iter() {
+                for i in 0..3 {
+                    min[i] = min[i].min(p[i]);
+                    max[i] = max[i].max(p[i]);
+                }
+            }
+
+            let floats = |v: &mut dyn Iterator<Item = f32>| -> Vec<u8> {
+                v.flat_map(|x| x.to_le_bytes()).collect()
+            };
+            let position_view = push_view(
+                buffer,
+                floats(&mut mesh.positions.iter().flatten().copied()),
+                ARRAY_BUFFER,
+            );
+            let normal_view = push_view(
+                buffer,
+                floats(&mut mesh.normals.iter().flatten().copied()),
+                ARRAY_BUFFER,
+            );
+            let uv_view = push_view(
+                buffer,
+                floats(&mut mesh.uvs.iter().flatten().copied()),
+                ARRAY_BUFFER,
+            );
+            let (index_data, index_type) = if mesh.short_indices {
+                (
+                    mesh.indices
+                        .iter()
+                        .flat_map(|&i| (i as u16).to_le_bytes())
+                        .collect(),
+                    UNSIGNED_SHORT,
+                )
+            } else {
+                (
+                    mesh.indices.iter().flat_map(|i| i.to_le_bytes()).collect(),
+                    UNSIGNED_INT,
+                )
+            };
+            let index_view = push_view(buffer, index_data, ELEMENT_ARRAY_BUFFER);
+
+            let n = mesh.positions.len();
+            accessors.push(format!(
+                "{{\"bufferView\":{},\"componentType\":{},\"count\":{},\"type\":\"VEC3\",\"min\":[{},{},{}],\"max\":[{},{},{}]}}",
+                position_view, FLOAT, n, min[0], min[1], min[2], max[0], max[1], max[2]
+            ));
+            accessors.push(format!(
+                "{{\"bufferView\":{},\"componentType\":{},\"count\":{},\"type\":\"VEC3\"}}",
+                normal_view, FLOAT, n
+            ));
+            accessors.push(format!(
+                "{{\"bufferView\":{},\"componentType\":{},\"count\":{},\"type\":\"VEC2\"}}",
+                uv_view, FLOAT, n
+            ));
+            accessors.push(format!(
+                "{{\"bufferView\":{},\"componentType\":{},\"count\":{},\"type\":\"SCALAR\"}}",
+                index_view,
+                index_type,
+                mesh.indices.len()
+            ));
+            let a = accessors.len() - 4;
+            meshes.push(format!(
+                "{{\"name\":\"{}\",\"primitives\":[{{\"attributes\":{{\"POSITION\":{},\"NORMAL\":{},\"TEXCOORD_0\":{}}},\"indices\":{},\"mode\":4}}]}}",
+                escape_json(&mesh.name),
+                a,
+                a + 1,
+                a + 2,
+                a + 3
+            ));
+        }
+
+        let nodes: Vec<String> = self
+            .meshes
+            .iter()
+            .enumerate()
+            .map(|(i, mesh)| {
+                format!(
+                    "{{\"name\":\"{}\",\"mesh\":{}}}",
+                    escape_json(&mesh.name),
+                    i
+                )
+            })
+            .collect();
+        let buffer_uri = match uri {
+            Some(uri) => format!(",\"uri\":\"{}\"", uri),
+            None => String::new(),
+        };
+        // buffers must not be empty
+        let buffers = if buffer.is_empty() {
+            String::new()
+        } else {
+            format!("{{\"byteLength\":{}{}}}", buffer.len(), buffer_uri)
+        };
+
+        format!(
+            "{{\"asset\":{{\"version\":\"2.0\",\"generator\":\"procedural_modelling\"}},\"scene\":0,\"scenes\":[{{\"nodes\":[{}]}}],\"nodes\":[{}],\"meshes\":[{}],\"accessors\":[{}],\"bufferViews\":[{}],\"buffers\":[{}]}}",
+            (0..nodes.len()).map(|i| i.to_string()).collect::<Vec<_>>().join(","),
+            nodes.join(","),
+            meshes.join(","),
+            accessors.join(","),
+            views.join(","),
+            buffers
+        )
+    }
+
+    /// Writes the document as a self-contained `.gltf` file with the buffer embedded as base64 data uri.
+    pub fn to_gltf(&self) -> String {
+        let mut buffer = Vec::new();
+        self.write(None, &mut buffer);
+        let uri = format!("data:application/octet-stream;base64,{}", base64(&buffer));
+        let mut buffer = Vec::new();
+        self.write(Some(&uri), &mut buffer)
+    }
+
+    /// Writes the document as a binary `.glb` file.
+    /// The binary chunk is omitted if the document has no meshes.
+    pub fn to_glb(&self) -> Vec<u8> {
+        let mut buffer = Vec::new();
+        let mut json = self.write(None, &mut buffer).into_bytes();
+        json.resize(json.len().next_multiple_of(4), b' ');
+
+        let bin_len = if buffer.is_empty() {
+            0
+        } else {
+            8 + buffer.len()
+        };
+        let mut glb = Vec::with_capacity(20 + json.len() + bin_len);
+        glb.extend_from_slice(b"glTF");
+        glb.extend_from_slice(&2u32.to_le_bytes());
+        glb.extend_from_slice(&((20 + json.len() + bin_len) as u32).to_le_bytes());
+        glb.extend_from_slice(&(json.len() as u32).to_le_bytes());
+        glb.extend_from_slice(b"JSON");
+        glb.extend_from_slice(&json);
+        if !buffer.is_empty() {
+            glb.extend_from_slice(&(buffer.len() as u32).to_le_bytes());
+            glb.extend_from_slice(b"BIN\0");
+            glb.extend_from_slice(&buffer);
+        }
+        glb
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "nalgebra")]
+mod tests {
+    use super::base64;
+    use crate::{extensions::gltf::*, extensions::nalgebra::*, prelude::*};
+
+    fn u32_at(data: &[u8], offset: usize) -> u32 {
+        u32::from_le_bytes([
+            data[offset],
+            data[offset + 1],
+            data[offset + 2],
+            data[offset + 3],
+        ])
+    }
+
+    #[test]
+    fn test_base64() {
+        assert_eq!(base64(b""), "");
+        assert_eq!(base64(b"f"), "Zg==");
+        assert_eq!(base64(b"fo"), "Zm8=");
+        assert_eq!(base64(b"foo"), "Zm9v");
+        assert_eq!(base64(b"foobar"), "Zm9vYmFy");
+    }
+
+    #[test]
+    fn test_glb() {
+        let mut cube = Mesh3d64::cube(1.0);
+        cube.generate_smooth_normals();
+        let prism = Mesh3d64::regular_prism(1.0, 1.0, 6);
+
+        let mut doc = GltfDocument::new();
+        doc.add_mesh::<MeshType3d64PNU>("cube", &cube)
+            .add_mesh_ex::<MeshType3d64PNU>("prism", &prism, TriangulationAlgorithm::Fan, true);
+        assert_eq!(doc.len(), 2);
+        let glb = doc.to_glb();
+
+        assert_eq!(&glb[0..4], b"glTF");
+        assert_eq!(u32_at(&glb, 8) as usize, glb.len());
+        let json_len = u32_at(&glb, 12) as usize;
+        assert_eq!(json_len % 4, 0);
+        let bin_len = u32_at(&glb, 20 + json_len) as usize;
+        assert_eq!(&glb[24 + json_len..28 + json_len], b"BIN\0");
+        assert_eq!(28 + json_len + bin_len, glb.len());
+
+        let json: serde_json::Value = serde_json::from_slice(&glb[20..20 + json_len]).unwrap();
+        assert_eq!(
+            json["nodes"],
+            serde_json::json!([{"name": "cube", "mesh": 0}, {"name": "prism", "mesh": 1}])
+        );
+        assert_eq!(json["buffers"][0]["byteLength"], bin_len);
+        assert!(json["buffers"][0].get("uri").is_none());
+
+        // usize indices are written as u32
+        let accessors = json["accessors"].as_array().unwrap();
+        assert_eq!(accessors.len(), 8);
+        assert_eq!(accessors[3]["componentType"], 5125);
+        assert_eq!(accessors[7]["componentType"], 5125);
+        assert_eq!(accessors[0]["min"], serde_json::json!([-0.5, -0.5, -0.5]));
+        assert_eq!(accessors[0]["max"], serde_json::json!([0.5, 0.5, 0.5]));
+
+        // the cube has 8 vertices, 12 triangles, and is the first view
+        let views = json["bufferViews"].as_array().unwrap();
+        assert_eq!(views[0]["byteOffset"], 0);
+        assert_eq!(views[0]["byteLength"], 8 * 12);
+        assert_eq!(accessors[3]["count"], 36);
+        let bin = &glb[28 + json_len..];
+        let index_offset = views[3]["byteOffset"].as_u64().unwrap() as usize;
+        for i in 0..36 {
+            assert!(u32_at(bin, index_offset + 4 * i) < 8);
+        }
+    }
+
+    #[test]
+    fn test_gltf() {
+        let mut doc = GltfDocument::new();
+        doc.add_mesh::<MeshType3d64PNU>("tetra\"hedron", &Mesh3d64::regular_tetrahedron(1.0));
+        let json: serde_json::Value = serde_json::from_str(&doc.to_gltf()).unwrap();
+        assert_eq!(json["meshes"][0]["name"], "tetra\"hedron");
+        assert!(json["buffers"][0]["uri"]
+            .as_str()
+            .unwrap()
+            .starts_with("data:application/octet-stream;base64,"));
+        // 4 vertices with position, normal, and uv and 12 indices
+        assert_eq!(json["buffers"][0]["byteLength"], 4 * 32 + 12 * 4);
+    }
+
+    #[test]
+    fn test_gltf_empty_mesh() {
+        let mut doc = GltfDocument::new();
+        doc.add_mesh::<MeshType3d64PNU>("empty", &Mesh3d64::new())
+            .add_mesh::<MeshType3d64PNU>("cube", &Mesh3d64::cube(1.0));
+        assert_eq!(doc.len(), 1);
+        let json: serde_json::Value = serde_json::from_str(&doc.to_gltf()).unwrap();
+        assert_eq!(json["meshes"].as_array().unwrap().len(), 1);
+        assert!(json["bufferViews"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .all(|v| v["byteLength"].as_u64().unwrap() > 0));
+
+        // an empty document is still valid json
+        let json: serde_json::Value = serde_json::from_str(&GltfDocument::new().to_gltf()).unwrap();
+        assert_eq!(json["meshes"], serde_json::json!([]));
+        assert_eq!(json["buffers"], serde_json::json!([]));
+
+        // and has no binary chunk
+        let glb = GltfDocument::new().to_glb();
+        let json_len = u32_at(&glb, 12) as usize;
+        assert_eq!(u32_at(&glb, 8) as usize, glb.len());
+        assert_eq!(glb.len(), 20 + json_len);
+    }
+
+    #[test]
+    fn test_gltf_non_finite() {
+        let mut mesh = Mesh3d64::cube(1.0);
+        mesh.vertex_mut(0)
+            .payload_mut()
+            .set_pos(Vec3::new(f64::NAN, 0.0, 0.0));
+        let mut doc = GltfDocument::new();
+        assert!(matches!(
+            doc.try_add_mesh::<MeshType3d64PNU>("cube", &mesh),
+            Err(ModellingError::NonFinite(_))
+        ));
+        assert!(doc.is_empty());
+    }
+}
@@ -0,0 +1,48 @@
+//! This module contains the glTF 2.0 and GLB export
+
+use crate::{
+    math::{HasNormal, HasUV},
+    mesh::{MeshType3D, ModellingError, Triangulateable},
+};
+
+mod document;
+
+pub use document::*;
+
+/// Backend trait for exporting a single mesh as glTF 2.0.
+/// Use `GltfDocument` directly to write multiple meshes into one file.
+pub trait BackendGLTF<T: MeshType3D<Mesh = Self>>: Triangulateable<T>
+where
+    T::VP: HasNormal<3, T::Vec, S = T::S> + HasUV<T::Vec2, S = T::S>,
+{
+    /// Export the triangulated mesh as a self-contained `.gltf` file.
+    fn to_gltf(&self, name: &str) -> String {
+        GltfDocument::to_gltf(GltfDocument::new().add_mesh::<T>(name, self))
+    }
+
+    /// Export the triangulated mesh as a binary `.glb` file.
+    fn to_glb(&self, name: &str) -> Vec<u8> {
+        GltfDocument::to_glb(GltfDocument::new().add_mesh::<T>(name, self))
+    }
+
+    /// Like `to_gltf`, but fails instead of panicking if a vertex position is not finite.
+    fn try_to_gltf(&self, name: &str) -> Result<String, ModellingError> {
+        Ok(GltfDocument::to_gltf(
+            GltfDocument::new().try_add_mesh::<T>(name, self)?,
+        ))
+    }
+
+    /// Like `to_glb`, but fails instead of panicking if a vertex position is not finite.
+    fn try_to_glb(&self, name: &str) -> Result<Vec<u8>, ModellingError> {
+        Ok(GltfDocument::to_glb(
+            GltfDocument::new().try_add_mesh::<T>(name, self)?,
+        ))
+    }
+}
+
+impl<T: MeshType3D> BackendGLTF<T> for T::Mesh
+where
+    T::Mesh: Triangulateable<T>,
+    T::VP: HasNormal<3, T::Vec, S = T::S> + HasUV<T::Vec2, S = T::S>,
+{
+}
@@ -11,3 +11,24 @@ pub mod svg;
 
 #[cfg(feature = "nalgebra")]
 pub mod nalgebra;
+
+#[cfg(feature = "obj")]
+pub mod obj;
+
+#[cfg(feature = "stl")]
+pub mod stl;
+
+#[cfg(feature = "ply")]
+pub mod ply;
+
+#[cfg(feature = "gltf")]
+pub mod gltf;
+
+#[cfg(feature = "off")]
+pub mod off;
+
+#[cfg(feature = "vtk")]
+pub mod vtk;
+
+#[cfg(any(feature = "obj", feature = "ply", feature = "off"))]
+mod polygons;
@@ -6,6 +6,7 @@ use crate::{
 
 /// d-dimensional Vertex Payload with position, normal, and uv coordinates.
 #[derive(Clone, PartialEq, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct VertexPayloadPNU<S: Scalar, const D: usize> {
     /// The position of the vertex.
     position: VecN<S, D>,
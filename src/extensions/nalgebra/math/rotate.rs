@@ -98,14 +98,24 @@ impl<S: Scalar, const D: usize> NdRotate<S, D> {
                 rot: None,
             }
         } else if D == 3 {
+            let from = from.fixed_rows::<3>(0).into_owned();
+            let to = to.fixed_rows::<3>(0).into_owned();
             Self {
                 rot2: None,
                 rot3: Some(
-                    nalgebra::Rotation3::rotation_between(
-                        &from.fixed_rows::<3>(0).into_owned(),
-                        &to.fixed_rows::<3>(0).into_owned(),
-                    )
-                    .expect("Failed to create rotation"),
+                    nalgebra::Rotation3::rotation_between(&from, &to).unwrap_or_else(|| {
+                        // the vectors are opposite, so rotate by pi around any perpendicular axis
+                        let axis = from.cross(&VecN::<S, 3>::x());
+                        let axis = if axis.norm_squared() > S::EPS {
+                            axis
+                        } else {
+                            from.cross(&VecN::<S, 3>::y())
+                        };
+                        nalgebra::Rotation3::from_axis_angle(
+                            &nalgebra::Unit::new_normalize(axis),
+                            S::PI,
+                        )
+                    }),
                 ),
                 rot: None,
             }
@@ -150,4 +160,25 @@ mod tests {
         let expected = SMatrix::<f64, 3, 3>::new(1.0, 0.0, 0.0, 0.0, 0.0, -1.0, 0.0, 1.0, 0.0);
         assert!((rot.to_matrix() - expected).abs().max() < 1e-10);
     }
+
+    #[test]
+    fn test_rotation_arc_antiparallel() {
+        // `rotation_between` has no unique solution for opposite vectors
+        for from in [
+            VecN::<f64, 3>::new(0.0, 0.0, 1.0),
+            VecN::<f64, 3>::new(1.0, 0.0, 0.0),
+            VecN::<f64, 3>::new(1.0, 2.0, -3.0).normalize(),
+        ] {
+            let rot = NdRotate::<f64, 3>::from_rotation_arc(from, -from);
+            let m = rot.to_matrix();
+            assert!((m * from + from).abs().max() < 1e-10);
+            assert!(
+                (m.transpose() * m - SMatrix::<f64, 3, 3>::identity())
+                    .abs()
+                    .max()
+                    < 1e-10
+            );
+            assert!((m.determinant() - 1.0).abs() < 1e-10);
+        }
+    }
 }
@@ -3,8 +3,8 @@ use crate::{
         HalfEdgeFaceImpl, HalfEdgeImpl, HalfEdgeImplMeshType, HalfEdgeMeshImpl, HalfEdgeVertexImpl,
     },
     mesh::{
-        EmptyEdgePayload, EmptyFacePayload, EmptyMeshPayload, EuclideanMeshType, MeshType,
-        MeshType3D, MeshTypeHalfEdge,
+        CornerEdgePayload, CreaseEdgePayload, EmptyEdgePayload, EmptyFacePayload, EmptyMeshPayload,
+        EuclideanMeshType, MeshType, MeshType3D, MeshTypeHalfEdge, SmoothingGroupFacePayload,
     },
 };
 
@@ -70,3 +70,79 @@ pub type Mesh3d64Edge = HalfEdgeImpl<MeshTypeNd64PNU<3>>;
 
 /// 64-bit 3d variant of the half-edge face
 pub type Mesh3d64Face = HalfEdgeFaceImpl<MeshTypeNd64PNU<3>>;
+
+/// A mesh type for nalgebra with
+/// - 3d vertices,
+/// - usize indices,
+/// - no face payload,
+/// - corner edge payload with optional uv coordinates, normals, and colors per face corner,
+/// - f64 vertex positions, normals, and uv coordinates
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct MeshType3d64PNUCorner;
+
+impl MeshType for MeshType3d64PNUCorner {
+    type E = usize;
+    type V = usize;
+    type F = usize;
+    type EP = CornerEdgePayload<3, Self>;
+    type VP = VertexPayloadPNU<f64, 3>;
+    type FP = EmptyFacePayload<Self>;
+    type MP = EmptyMeshPayload<Self>;
+    type Mesh = Mesh3d64Corner;
+    type Face = HalfEdgeFaceImpl<Self>;
+    type Edge = HalfEdgeImpl<Self>;
+    type Vertex = HalfEdgeVertexImpl<Self>;
+}
+impl EuclideanMeshType<3> for MeshType3d64PNUCorner {
+    type S = f64;
+    type Vec = VecN<f64, 3>;
+    type Vec2 = VecN<f64, 2>;
+    type Trans = NdAffine<f64, 3>;
+    type Rot = NdRotate<f64, 3>;
+    type Poly = Polygon2d<f64>;
+}
+impl HalfEdgeImplMeshType for MeshType3d64PNUCorner {}
+impl MeshTypeHalfEdge for MeshType3d64PNUCorner {}
+impl MeshType3D for MeshType3d64PNUCorner {}
+
+/// A 3d mesh with f64 positions, normals, and uv coordinates
+/// that can override the uv coordinates, normals, and colors per face corner.
+pub type Mesh3d64Corner = HalfEdgeMeshImpl<MeshType3d64PNUCorner>;
+
+/// A mesh type for nalgebra with
+/// - 3d vertices,
+/// - usize indices,
+/// - smoothing groups as face payload,
+/// - crease weights as edge payload,
+/// - f64 vertex positions, normals, and uv coordinates
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct MeshType3d64PNUCrease;
+
+impl MeshType for MeshType3d64PNUCrease {
+    type E = usize;
+    type V = usize;
+    type F = usize;
+    type EP = CreaseEdgePayload<Self>;
+    type VP = VertexPayloadPNU<f64, 3>;
+    type FP = SmoothingGroupFacePayload<Self>;
+    type MP = EmptyMeshPayload<Self>;
+    type Mesh = Mesh3d64Crease;
+    type Face = HalfEdgeFaceImpl<Self>;
+    type Edge = HalfEdgeImpl<Self>;
+    type Vertex = HalfEdgeVertexImpl<Self>;
+}
+impl EuclideanMeshType<3> for MeshType3d64PNUCrease {
+    type S = f64;
+    type Vec = VecN<f64, 3>;
+    type Vec2 = VecN<f64, 2>;
+    type Trans = NdAffine<f64, 3>;
+    type Rot = NdRotate<f64, 3>;
+    type Poly = Polygon2d<f64>;
+}
+impl HalfEdgeImplMeshType for MeshType3d64PNUCrease {}
+impl MeshTypeHalfEdge for MeshType3d64PNUCrease {}
+impl MeshType3D for MeshType3d64PNUCrease {}
+
+/// A 3d mesh with f64 positions, normals, and uv coordinates,
+/// crease weights on the edges, and smoothing groups on the faces.
+pub type Mesh3d64Crease = HalfEdgeMeshImpl<MeshType3d64PNUCrease>;
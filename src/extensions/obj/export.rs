@@ -0,0 +1,131 @@
+use crate::{
+    extensions::polygons::polygons,
+    math::{HasNormal, HasPosition, HasUV, IndexType, Vector},
+    mesh::{MeshBasics, MeshType3D, Triangulateable, VertexBasics},
+    tesselate::{TesselationMeta, TriangulationAlgorithm},
+};
+use std::{collections::HashMap, fmt::Write};
+
+fn write_vertex<T: MeshType3D>(obj: &mut String, vp: &T::VP)
+where
+    T::VP: HasNormal<3, T::Vec, S = T::S> + HasUV<T::Vec2, S = T::S>,
+{
+    let p = vp.pos();
+    let n = vp.normal();
+    let uv = vp.uv();
+    writeln!(obj, "v {} {} {}", p.x(), p.y(), p.z()).unwrap();
+    writeln!(obj, "vn {} {} {}", n.x(), n.y(), n.z()).unwrap();
+    writeln!(obj, "vt {} {}", uv.x(), uv.y()).unwrap();
+}
+
+fn write_face(obj: &mut String, ids: impl Iterator<Item = usize>) {
+    obj.push('f');
+    for i in ids {
+        // OBJ indices are 1-based
+        write!(obj, " {0}/{0}/{0}", i + 1).unwrap();
+    }
+    obj.push('\n');
+}
+
+/// Writes the mesh as an OBJ string. Each vertex gets its own position, normal, and uv coordinate.
+///
+/// If no triangulation algorithm is given, the faces are written as n-gons.
+/// Faces with holes are always triangulated.
+pub(crate) fn export_obj<T: MeshType3D>(
+    mesh: &T::Mesh,
+    triangulation: Option<TriangulationAlgorithm>,
+) -> String
+where
+    T::Mesh: Triangulateable<T>,
+    T::VP: HasNormal<3, T::Vec, S = T::S> + HasUV<T::Vec2, S = T::S>,
+{
+    let mut obj = String::new();
+
+    if let Some(algorithm) = triangulation {
+        let (indices, vertices) = mesh.triangulate(algorithm, &mut TesselationMeta::default());
+        for vp in vertices.iter() {
+            write_vertex::<T>(&mut obj, vp);
+        }
+        for tri in indices.chunks_exact(3) {
+            write_face(&mut obj, tri.iter().map(|i| i.index()));
+        }
+    } else {
+        // vertex ids might be sparse
+        let mut id_map = HashMap::new();
+        for (i, v) in mesh.vertices().enumerate() {
+            id_map.insert(v.id(), i);
+            write_vertex::<T>(&mut obj, v.payload());
+        }
+        for (_, ids) in polygons::<T>(mesh) {
+            write_face(&mut obj, ids.iter().map(|v| id_map[v]));
+        }
+    }
+
+    obj
+}
+
+#[cfg(test)]
+#[cfg(feature = "nalgebra")]
+mod tests {
+    use crate::{
+        extensions::nalgebra::*,
+        extensions::obj::*,
+        prelude::*,
+        test_util::{area, square_with_hole},
+    };
+
+    #[test]
+    fn test_obj_export_ngons() {
+        let mesh = Mesh3d64::regular_prism(1.0, 1.0, 6);
+        let obj = mesh.to_obj();
+        assert_eq!(obj.lines().filter(|l| l.starts_with("v ")).count(), 12);
+        assert_eq!(obj.lines().filter(|l| l.starts_with("vn ")).count(), 12);
+        assert_eq!(obj.lines().filter(|l| l.starts_with("f ")).count(), 8);
+
+        let reimported = Mesh3d64::from_obj(&obj).unwrap();
+        assert!(reimported.check().is_ok());
+        assert!(reimported
+            .is_isomorphic_by_pos::<_, 3, _, MeshType3d64PNU>(&mesh, 1e-6)
+            .eq());
+    }
+
+    #[test]
+    fn test_obj_export_hole() {
+        // the n-gon export triangulates the face with a hole but still shares its vertices
+        let (mesh, _, _) = square_with_hole();
+        let obj = mesh.to_obj();
+        assert_eq!(obj.lines().filter(|l| l.starts_with("v ")).count(), 8);
+        let faces: Vec<&str> = obj.lines().filter(|l| l.starts_with("f ")).collect();
+        assert_eq!(faces.len(), 8);
+        assert!(faces.iter().all(|l| l.split_whitespace().count() == 4));
+
+        let reimported = Mesh3d64::from_obj(&obj).unwrap();
+        assert!(reimported.check().is_ok());
+        assert_eq!(reimported.num_vertices(), 8);
+        assert!(area(&reimported).is_about(12.0, 1e-8));
+    }
+
+    #[test]
+    fn test_obj_export_triangulated() {
+        let mut mesh = Mesh3d64::regular_prism(1.0, 1.0, 6);
+        mesh.generate_smooth_normals();
+        let obj = mesh.to_obj_ex(Some(TriangulationAlgorithm::Fan));
+        // 2 hexagons with 4 triangles each and 6 quads with 2 triangles each
+        assert_eq!(obj.lines().filter(|l| l.starts_with("f ")).count(), 20);
+
+        let reimported = Mesh3d64::from_obj(&obj).unwrap();
+        assert!(reimported.check().is_ok());
+        assert!(!reimported.is_open());
+        assert_eq!(reimported.num_faces(), 20);
+        for v in reimported.vertices() {
+            let original = mesh
+                .vertices()
+                .find(|w| w.pos().is_about(&v.pos(), 1e-6))
+                .unwrap();
+            assert!(original
+                .payload()
+                .normal()
+                .is_about(v.payload().normal(), 1e-6));
+        }
+    }
+}
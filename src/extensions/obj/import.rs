@@ -0,0 +1,360 @@
+use crate::{
+    math::{Scalar, Vector, Vector2D},
+    mesh::{DefaultEdgePayload, DefaultFacePayload, IndexedFaceError, MeshBuilder, MeshType3D},
+};
+
+/// A group (`g`) or object (`o`) of an OBJ file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ObjGroup<F> {
+    /// The name of the group. Faces before the first group are in the group "default".
+    pub name: String,
+
+    /// The ids of the faces in the group.
+    pub faces: Vec<F>,
+}
+
+/// Errors that can occur when importing an OBJ file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ObjError {
+    /// A line of the file couldn't be parsed.
+    Parse {
+        /// The line number (starting at 1).
+        line: usize,
+        /// A description of the problem.
+        message: String,
+    },
+
+    /// The faces don't describe an oriented manifold surface.
+    Topology(IndexedFaceError),
+}
+
+impl std::fmt::Display for ObjError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ObjError::Parse { line, message } => write!(f, "Line {}: {}", line, message),
+            ObjError::Topology(e) => write!(f, "Invalid topology: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for ObjError {}
+
+impl From<IndexedFaceError> for ObjError {
+    fn from(e: IndexedFaceError) -> Self {
+        ObjError::Topology(e)
+    }
+}
+
+/// The parsed contents of an OBJ file with 0-based indices.
+#[derive(Debug, Clone, Default)]
+struct ObjData {
+    positions: Vec<[f64; 3]>,
+    normals: Vec<[f64; 3]>,
+    uvs: Vec<[f64; 2]>,
+    /// The corners (position, uv, normal) of each face and the index of its group.
+    faces: Vec<(Vec<(usize, Option<usize>, Option<usize>)>, usize)>,
+    groups: Vec<String>,
+}
+
+/// Parses the first `N` values. The last `optional` values may be missing and
+/// up to `extra` trailing values are ignored.
+fn parse_floats<const N: usize>(
+    line: usize,
+    parts: &[&str],
+    optional: usize,
+    extra: usize,
+) -> Result<[f64; N], ObjError> {
+    if parts.len() + optional < N || parts.len() > N + extra {
+        return Err(ObjError::Parse {
+            line,
+            message: format!("Expected {} coordinates but got {}", N, parts.len()),
+        });
+    }
+    let mut res = [0.0; N];
+    for (i, p) in parts.iter().take(N).enumerate() {
+        res[i] = p.parse::<f64>().map_err(|e| ObjError::Parse {
+            line,
+            message: format!("Invalid number '{}': {}", p, e),
+        })?;
+    }
+    Ok(res)
+}
+
+/// Resolves a 1-based or negative (relative) OBJ index.
+fn resolve_index(line: usize, s: &str, len: usize) -> Result<usize, ObjError> {
+    let i = s.parse::<isize>().map_err(|e| ObjError::Parse {
+        line,
+        message: format!("Invalid index '{}': {}", s, e),
+    })?;
+    let resolved = if i < 0 { len as isize + i } else { i - 1 };
+    if resolved < 0 || resolved as usize >= len {
+        return Err(ObjError::Parse {
+            line,
+            message: format!("Index {} is out of range", i),
+        });
+    }
+    Ok(resolved as usize)
+}
+
+fn parse_obj(obj: &str) -> Result<ObjData, ObjError> {
+    let mut data = ObjData::default();
+    data.groups.push("default".to_string());
+    let mut group = 0;
+
+    for (i, raw) in obj.lines().enumerate() {
+        let line = i + 1;
+        let content = raw.split('#').next().unwrap_or("").trim();
+        let mut parts = content.split_whitespace();
+        let Some(keyword) = parts.next() else {
+            continue;
+        };
+        let parts: Vec<&str> = parts.collect();
+        match keyword {
+            // the optional w coordinate or the vertex color extension `r g b` is ignored
+            "v" => {
+                if !matches!(parts.len(), 3 | 4 | 6) {
+                    return Err(ObjError::Parse {
+                        line,
+                        message: format!(
+                            "Expected 3, 4 (with w) or 6 (with rgb) values but got {}",
+                            parts.len()
+                        ),
+                    });
+                }
+                data.positions.push(parse_floats::<3>(line, &parts, 0, 3)?);
+            }
+            "vn" => data.normals.push(parse_floats::<3>(line, &parts, 0, 0)?),
+            // the optional w coordinate is ignored
+            "vt" => data.uvs.push(parse_floats::<2>(line, &parts, 1, 1)?),
+            "f" => {
+                let mut corners = Vec::with_capacity(parts.len());
+                for p in parts.iter() {
+                    let mut refs = p.split('/');
+                    let v = resolve_index(line, refs.next().unwrap_or(""), data.positions.len())?;
+                    let vt = match refs.next() {
+                        Some(s) if !s.is_empty() => Some(resolve_index(line, s, data.uvs.len())?),
+                        _ => None,
+                    };
+                    let vn = match refs.next() {
+                        Some(s) if !s.is_empty() => {
+                            Some(resolve_index(line, s, data.normals.len())?)
+                        }
+                        _ => None,
+                    };
+                    corners.push((v, vt, vn));
+                }
+                data.faces.push((corners, group));
+            }
+            "g" | "o" => {
+                let name = if parts.is_empty() {
+                    "default".to_string()
+                } else {
+                    parts.join(" ")
+                };
+                group = data
+                    .groups
+                    .iter()
+                    .position(|g| *g == name)
+                    .unwrap_or_else(|| {
+                        data.groups.push(name);
+                        data.groups.len() - 1
+                    });
+            }
+            // Free-form geometry, lines, materials and smoothing groups are not supported
+            _ => {}
+        }
+    }
+
+    Ok(data)
+}
+
+/// Parses the OBJ string and inserts its faces into the mesh.
+///
+/// Since normals and uv coordinates are stored per vertex, a vertex takes
+/// the normal and uv coordinates of the first face corner referencing it.
+pub(crate) fn import_obj<T: MeshType3D>(
+    mesh: &mut T::Mesh,
+    obj: &str,
+    vp: impl Fn(T::Vec, Option<T::Vec>, Option<T::Vec2>) -> T::VP,
+) -> Result<Vec<ObjGroup<T::F>>, ObjError>
+where
+    T::EP: DefaultEdgePayload,
+    T::FP: DefaultFacePayload,
+{
+    let data = parse_obj(obj)?;
+
+    let mut normals = vec![None; data.positions.len()];
+    let mut uvs = vec![None; data.positions.len()];
+    for (corners, _) in data.faces.iter() {
+        for &(v, vt, vn) in corners.iter() {
+            if uvs[v].is_none() {
+                uvs[v] = vt;
+            }
+            if normals[v].is_none() {
+                normals[v] = vn;
+            }
+        }
+    }
+
+    let s = |x: f64| T::S::from_f64(x);
+    let vps = data.positions.iter().enumerate().map(|(i, p)| {
+        vp(
+            T::Vec::from_xyz(s(p[0]), s(p[1]), s(p[2])),
+            normals[i].map(|n| {
+                let n = data.normals[n];
+                T::Vec::from_xyz(s(n[0]), s(n[1]), s(n[2]))
+            }),
+            uvs[i].map(|uv| {
+                let uv = data.uvs[uv];
+                T::Vec2::new(s(uv[0]), s(uv[1]))
+            }),
+        )
+    });
+
+    let (_, fs) = mesh.insert_indexed_faces(
+        vps,
+        data.faces
+            .iter()
+            .map(|(corners, _)| (corners.iter().map(|c| c.0).collect(), Default::default())),
+    )?;
+
+    let mut groups: Vec<ObjGroup<T::F>> = data
+        .groups
+        .into_iter()
+        .map(|name| ObjGroup {
+            name,
+            faces: Vec::new(),
+        })
+        .collect();
+    for ((_, group), f) in data.faces.iter().zip(fs) {
+        groups[*group].faces.push(f);
+    }
+    groups.retain(|g| !g.faces.is_empty());
+
+    Ok(groups)
+}
+
+#[cfg(test)]
+#[cfg(feature = "nalgebra")]
+mod tests {
+    use crate::{extensions::nalgebra::*, extensions::obj::*, prelude::*};
+
+    const CUBE: &str = "
+# a unit cube with one group per side
+v 0 0 0
+v 1 0 0
+v 1 1 0
+v 0 1 0
+v 0 0 1
+v 1 0 1
+v 1 1 1
+v 0 1 1
+vt 0 0
+vt 1 0
+vt 1 1
+vt 0 1
+vn 0 0 -1
+vn 0 0 1
+g bottom
+f 1/1/1 4/4/1 3/3/1 2/2/1
+g top
+f 5/1/2 6/2/2 7/3/2 8/4/2
+g sides
+f 1 2 6 5
+f 2 3 7 6
+f 3 4 8 7
+f -8 -4 -1 -5
+";
+
+    #[test]
+    fn test_obj_cube() {
+        let mut mesh = Mesh3d64::new();
+        let groups = mesh.import_obj(CUBE).unwrap();
+        assert!(mesh.check().is_ok());
+        assert!(!mesh.is_open());
+        assert_eq!(mesh.num_vertices(), 8);
+        assert_eq!(mesh.num_edges(), 24);
+        assert_eq!(mesh.num_faces(), 6);
+        assert_eq!(
+            groups
+                .iter()
+                .map(|g| (g.name.as_str(), g.faces.len()))
+                .collect::<Vec<_>>(),
+            vec![("bottom", 1), ("top", 1), ("sides", 4)]
+        );
+        assert_eq!(
+            mesh.vertex(0).payload().normal(),
+            &Vec3::new(0.0, 0.0, -1.0)
+        );
+        assert_eq!(mesh.vertex(6).payload().uv(), &Vec2::new(1.0, 1.0));
+        for f in mesh.faces() {
+            assert_eq!(f.num_vertices(&mesh), 4);
+            assert!(
+                f.normal(&mesh)
+                    .dot(&(f.centroid::<3>(&mesh) - Vec3::splat(0.5)))
+                    > 0.0
+            );
+        }
+    }
+
+    #[test]
+    fn test_obj_open() {
+        let mesh =
+            Mesh3d64::from_obj("v 0 0 0\nv 1 0 0\nv 1 1 0\nv 0 1 0\nf 1 2 3\nf 1 3 4\n").unwrap();
+        assert!(mesh.check().is_ok());
+        assert!(mesh.is_open());
+        assert_eq!(mesh.num_edges(), 10);
+        assert_eq!(mesh.num_faces(), 2);
+    }
+
+    #[test]
+    fn test_obj_vertex_colors() {
+        let mesh = Mesh3d64::from_obj(
+            "v 0 0 0 1 0 0\nv 1 0 0 0 1 0\nv 0 1 0 0 0 1\nv 0 0 1 1.0\nf 1 2 3\n",
+        )
+        .unwrap();
+        assert!(mesh.check().is_ok());
+        assert_eq!(mesh.num_vertices(), 4);
+        assert_eq!(mesh.vertex(1).pos(), Vec3::new(1.0, 0.0, 0.0));
+
+        assert!(matches!(
+            Mesh3d64::from_obj("v 0 0 0 1 0 0 1 2\n"),
+            Err(ObjError::Parse { line: 1, .. })
+        ));
+    }
+
+    #[test]
+    fn test_obj_errors() {
+        // three faces sharing the edge 1-2
+        let fin = "v 0 0 0\nv 1 0 0\nv 0 1 0\nv 0 -1 0\nv 0 0 1\nf 1 2 3\nf 2 1 4\nf 1 2 5\n";
+        let mut mesh = Mesh3d64::new();
+        assert_eq!(
+            mesh.import_obj(fin),
+            Err(ObjError::Topology(IndexedFaceError::NonManifoldEdge {
+                face: 2,
+                from: 0,
+                to: 1
+            }))
+        );
+        assert_eq!(mesh.num_vertices(), 0);
+
+        assert!(matches!(
+            Mesh3d64::from_obj("v 0 0 0\nf 1 2 3\n"),
+            Err(ObjError::Parse { line: 2, .. })
+        ));
+        assert!(matches!(
+            Mesh3d64::from_obj("v 0 0\n"),
+            Err(ObjError::Parse { line: 1, .. })
+        ));
+        assert!(matches!(
+            Mesh3d64::from_obj("v 0 0 0\nv 0 0 0 1 0\n"),
+            Err(ObjError::Parse { line: 2, .. })
+        ));
+        assert_eq!(
+            Mesh3d64::from_obj("v 0 0 0\nv 1 0 0\nf 1 2\n").map(|_| ()),
+            Err(ObjError::Topology(IndexedFaceError::DegenerateFace {
+                face: 0
+            }))
+        );
+    }
+}
@@ -0,0 +1,85 @@
+//! This module contains the Wavefront OBJ import and export
+
+use crate::{
+    math::{HasNormal, HasPosition, HasUV},
+    mesh::{DefaultEdgePayload, DefaultFacePayload, MeshBuilder, MeshType3D, Triangulateable},
+    tesselate::TriangulationAlgorithm,
+};
+
+mod export;
+mod import;
+
+use export::*;
+pub use import::*;
+
+/// Backend trait for Wavefront OBJ import/export.
+pub trait BackendOBJ<T: MeshType3D<Mesh = Self>>: MeshBuilder<T> {
+    /// Import an OBJ string into the mesh.
+    /// Normals and uv coordinates are written to the vertex payloads if present.
+    /// Returns the groups of the file with the ids of their faces.
+    fn import_obj(&mut self, obj: &str) -> Result<Vec<ObjGroup<T::F>>, ObjError>
+    where
+        T::EP: DefaultEdgePayload,
+        T::FP: DefaultFacePayload,
+        T::VP: HasNormal<3, T::Vec, S = T::S> + HasUV<T::Vec2, S = T::S>,
+    {
+        import_obj::<T>(self, obj, |pos, normal, uv| {
+            let mut vp = T::VP::from_pos(pos);
+            if let Some(normal) = normal {
+                vp.set_normal(normal);
+            }
+            if let Some(uv) = uv {
+                vp.set_uv(uv);
+            }
+            vp
+        })
+    }
+
+    /// Like `import_obj`, but constructs the vertex payloads using the given function.
+    /// It receives the position and, if present, the normal and uv coordinates of the vertex.
+    fn import_obj_with<F: Fn(T::Vec, Option<T::Vec>, Option<T::Vec2>) -> T::VP>(
+        &mut self,
+        obj: &str,
+        vp: F,
+    ) -> Result<Vec<ObjGroup<T::F>>, ObjError>
+    where
+        T::EP: DefaultEdgePayload,
+        T::FP: DefaultFacePayload,
+    {
+        import_obj::<T>(self, obj, vp)
+    }
+
+    /// Create a new mesh from an OBJ string.
+    fn from_obj(obj: &str) -> Result<Self, ObjError>
+    where
+        T::EP: DefaultEdgePayload,
+        T::FP: DefaultFacePayload,
+        T::VP: HasNormal<3, T::Vec, S = T::S> + HasUV<T::Vec2, S = T::S>,
+    {
+        let mut mesh = Self::default();
+        mesh.import_obj(obj)?;
+        Ok(mesh)
+    }
+
+    /// Export the mesh as an OBJ string.
+    /// Faces are written as n-gons and every vertex has a position, normal, and uv coordinate.
+    fn to_obj(&self) -> String
+    where
+        Self: Triangulateable<T>,
+        T::VP: HasNormal<3, T::Vec, S = T::S> + HasUV<T::Vec2, S = T::S>,
+    {
+        export_obj::<T>(self, None)
+    }
+
+    /// Like `to_obj`, but if a triangulation algorithm is given,
+    /// the mesh is triangulated using that algorithm before writing it.
+    fn to_obj_ex(&self, triangulation: Option<TriangulationAlgorithm>) -> String
+    where
+        Self: Triangulateable<T>,
+        T::VP: HasNormal<3, T::Vec, S = T::S> + HasUV<T::Vec2, S = T::S>,
+    {
+        export_obj::<T>(self, triangulation)
+    }
+}
+
+impl<T: MeshType3D> BackendOBJ<T> for T::Mesh {}
@@ -0,0 +1,136 @@
+use super::OffColor;
+use crate::{
+    extensions::polygons::polygons,
+    math::Vector,
+    mesh::{FaceBasics, MeshBasics, MeshType3D, VertexBasics},
+};
+use std::{collections::HashMap, fmt::Write};
+
+/// Writes the color as floats. Integer components would be read as values in [0, 255].
+fn write_color(off: &mut String, color: OffColor) {
+    write!(
+        off,
+        " {:?} {:?} {:?} {:?}",
+        color[0], color[1], color[2], color[3]
+    )
+    .unwrap();
+}
+
+/// Writes the mesh as an OFF string with n-gon faces. Faces with holes are triangulated.
+/// If `vertex_color` is given, the file is a COFF file with a color for every vertex.
+/// If `face_color` is given, every face is followed by its color.
+pub(crate) fn export_off<T: MeshType3D>(
+    mesh: &T::Mesh,
+    vertex_color: Option<&dyn Fn(&T::VP) -> OffColor>,
+    face_color: Option<&dyn Fn(&T::FP) -> OffColor>,
+) -> String {
+    let polygons = polygons::<T>(mesh);
+    let mut off = String::new();
+    if vertex_color.is_some() {
+        off.push('C');
+    }
+    writeln!(
+        off,
+        "OFF\n{} {} {}",
+        mesh.num_vertices(),
+        polygons.len(),
+        mesh.num_edges() / 2
+    )
+    .unwrap();
+
+    // vertex ids might be sparse
+    let mut id_map = HashMap::new();
+    for (i, v) in mesh.vertices().enumerate() {
+        id_map.insert(v.id(), i);
+        let p = v.pos();
+        write!(off, "{} {} {}", p.x(), p.y(), p.z()).unwrap();
+        if let Some(color) = vertex_color {
+            write_color(&mut off, color(v.payload()));
+        }
+        off.push('\n');
+    }
+
+    for (f, ids) in polygons {
+        write!(off, "{}", ids.len()).unwrap();
+        for v in ids {
+            write!(off, " {}", id_map[&v]).unwrap();
+        }
+        if let Some(color) = face_color {
+            write_color(&mut off, color(mesh.face(f).payload()));
+        }
+        off.push('\n');
+    }
+
+    off
+}
+
+#[cfg(test)]
+#[cfg(feature = "nalgebra")]
+mod tests {
+    use crate::{
+        extensions::nalgebra::*,
+        extensions::off::*,
+        prelude::*,
+        test_util::{area, square_with_hole},
+    };
+    use std::cell::RefCell;
+
+    #[test]
+    fn test_coff_round_trip() {
+        let mesh = Mesh3d64::regular_prism(1.0, 1.0, 5);
+        let red = |vp: &VertexPayloadPNU<f64, 3>| [vp.pos().y() as f32, 0.0, 0.0, 1.0];
+        let off = mesh.to_off_with(Some(&red), Some(&|_| [0.0, 0.0, 1.0, 1.0]));
+        assert!(off.starts_with("COFF\n10 7 15\n"));
+
+        let colors = RefCell::new(Vec::new());
+        let mut reimported = Mesh3d64::new();
+        reimported
+            .import_off_with(
+                &off,
+                |pos, _, _, color| {
+                    colors.borrow_mut().push(color);
+                    VertexPayloadPNU::from_pos(pos)
+                },
+                |color| {
+                    assert_eq!(color, Some([0.0, 0.0, 1.0, 1.0]));
+                    Default::default()
+                },
+            )
+            .unwrap();
+        assert!(reimported
+            .is_isomorphic_by_pos::<_, 3, _, MeshType3d64PNU>(&mesh, 1e-6)
+            .eq());
+        for (v, color) in reimported.vertices().zip(colors.into_inner()) {
+            assert_eq!(color, Some(red(v.payload())));
+        }
+
+        let plain = Mesh3d64::from_off(&mesh.to_off()).unwrap();
+        assert!(plain
+            .is_isomorphic_by_pos::<_, 3, _, MeshType3d64PNU>(&mesh, 1e-6)
+            .eq());
+    }
+
+    #[test]
+    fn test_off_export_hole() {
+        // the face with a hole is triangulated and each triangle keeps the face color
+        let (mesh, _, _) = square_with_hole();
+        let off = mesh.to_off_with(None, Some(&|_| [0.0, 0.0, 1.0, 1.0]));
+        assert!(off.starts_with("OFF\n8 8 "));
+        let mut reimported = Mesh3d64::new();
+        reimported
+            .import_off_with(
+                &off,
+                |pos, _, _, _| VertexPayloadPNU::from_pos(pos),
+                |color| {
+                    assert_eq!(color, Some([0.0, 0.0, 1.0, 1.0]));
+                    Default::default()
+                },
+            )
+            .unwrap();
+        assert!(reimported.check().is_ok());
+        assert_eq!(reimported.num_vertices(), 8);
+        assert_eq!(reimported.num_faces(), 8);
+        assert!(reimported.faces().all(|f| f.num_vertices(&reimported) == 3));
+        assert!(area(&reimported).is_about(12.0, 1e-8));
+    }
+}
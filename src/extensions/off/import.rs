@@ -0,0 +1,303 @@
+use crate::{
+    math::{Scalar, Vector, Vector2D},
+    mesh::{DefaultEdgePayload, IndexedFaceError, MeshBuilder, MeshType3D},
+};
+
+/// An RGBA color of a vertex or face in an OFF file with components in the range [0, 1].
+pub type OffColor = [f32; 4];
+
+/// Errors that can occur when importing an OFF file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OffError {
+    /// A line of the file couldn't be parsed.
+    Parse {
+        /// The line number (starting at 1).
+        line: usize,
+        /// A description of the problem.
+        message: String,
+    },
+
+    /// The faces don't describe an oriented manifold surface.
+    Topology(IndexedFaceError),
+}
+
+impl std::fmt::Display for OffError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OffError::Parse { line, message } => write!(f, "Line {}: {}", line, message),
+            OffError::Topology(e) => write!(f, "Invalid topology: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for OffError {}
+
+impl From<IndexedFaceError> for OffError {
+    fn from(e: IndexedFaceError) -> Self {
+        OffError::Topology(e)
+    }
+}
+
+fn parse_error(line: usize, message: String) -> OffError {
+    OffError::Parse { line, message }
+}
+
+fn parse_floats(line: usize, parts: &[&str]) -> Result<Vec<f64>, OffError> {
+    parts
+        .iter()
+        .map(|p| {
+            p.parse::<f64>()
+                .map_err(|e| parse_error(line, format!("Invalid number '{}': {}", p, e)))
+        })
+        .collect()
+}
+
+/// Parses a color with 3 or 4 components. Integer colors are in the range [0, 255].
+/// A single value is an index into a color map, which isn't supported and is ignored.
+fn parse_color(line: usize, parts: &[&str]) -> Result<Option<OffColor>, OffError> {
+    if parts.len() <= 1 {
+        return Ok(None);
+    }
+    if parts.len() == 2 || parts.len() > 4 {
+        return Err(parse_error(
+            line,
+            format!("Expected 3 or 4 color components but got {}", parts.len()),
+        ));
+    }
+    let values = parse_floats(line, parts)?;
+    let scale = if parts.iter().all(|p| !p.contains(['.', 'e', 'E'])) {
+        255.0
+    } else {
+        1.0
+    };
+    let mut color = [1.0; 4];
+    for (c, v) in color.iter_mut().zip(values) {
+        *c = (v / scale) as f32;
+    }
+    Ok(Some(color))
+}
+
+/// Parses the OFF string and inserts its faces into the mesh.
+///
+/// Supports the `ST`, `C`, and `N` header prefixes. `vp` receives the position and,
+/// if present, the normal, uv coordinates, and color of each vertex.
+/// `fp` receives the color of each face if present.
+pub(crate) fn import_off<T: MeshType3D>(
+    mesh: &mut T::Mesh,
+    off: &str,
+    vp: impl Fn(T::Vec, Option<T::Vec>, Option<T::Vec2>, Option<OffColor>) -> T::VP,
+    fp: impl Fn(Option<OffColor>) -> T::FP,
+) -> Result<Vec<T::F>, OffError>
+where
+    T::EP: DefaultEdgePayload,
+{
+    let mut lines = off
+        .lines()
+        .enumerate()
+        .map(|(i, raw)| (i + 1, raw.split('#').next().unwrap_or("").trim()))
+        .filter(|(_, content)| !content.is_empty());
+
+    let (line, header) = lines
+        .next()
+        .ok_or_else(|| parse_error(1, "Missing header".to_string()))?;
+    let mut header_parts = header.split_whitespace();
+    let keyword = header_parts.next().unwrap_or("");
+    let Some(prefix) = keyword.strip_suffix("OFF") else {
+        return Err(parse_error(line, format!("Invalid header '{}'", keyword)));
+    };
+    let has_uv = prefix.contains("ST");
+    let has_color = prefix.contains('C');
+    let has_normal = prefix.contains('N');
+    if prefix.contains('4') || prefix.contains('n') {
+        return Err(parse_error(
+            line,
+            format!(
+                "Unsupported header '{}'; only 3D files are supported",
+                keyword
+            ),
+        ));
+    }
+
+    // the counts might be on the same line as the keyword
+    let mut counts: Vec<&str> = header_parts.collect();
+    let mut counts_line = line;
+    if counts.is_empty() {
+        let (l, c) = lines
+            .next()
+            .ok_or_else(|| parse_error(line, "Missing counts".to_string()))?;
+        counts = c.split_whitespace().collect();
+        counts_line = l;
+    }
+    let counts = counts
+        .iter()
+        .map(|p| {
+            p.parse::<usize>()
+                .map_err(|e| parse_error(counts_line, format!("Invalid count '{}': {}", p, e)))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    if counts.len() < 2 {
+        return Err(parse_error(
+            counts_line,
+            "Expected the number of vertices and faces".to_string(),
+        ));
+    }
+    let (nv, nf) = (counts[0], counts[1]);
+
+    // the counts are untrusted, so don't reserve more than a reasonable amount up front
+    let s = |x: f64| T::S::from_f64(x);
+    let mut vps = Vec::with_capacity(nv.min(1 << 16));
+    for _ in 0..nv {
+        let (line, content) = lines
+            .next()
+            .ok_or_else(|| parse_error(counts_line, format!("Expected {} vertices", nv)))?;
+        let parts: Vec<&str> = content.split_whitespace().collect();
+        let required = 3 + if has_normal { 3 } else { 0 } + if has_uv { 2 } else { 0 };
+        if parts.len() < required || (!has_color && parts.len() > required) {
+            return Err(parse_error(
+                line,
+                format!("Expected {} values but got {}", required, parts.len()),
+            ));
+        }
+        let values = parse_floats(line, &parts[0..3])?;
+        let pos = T::Vec::from_xyz(s(values[0]), s(values[1]), s(values[2]));
+        let mut rest = &parts[3..];
+        let normal = if has_normal {
+            let n = parse_floats(line, &rest[0..3])?;
+            rest = &rest[3..];
+            Some(T::Vec::from_xyz(s(n[0]), s(n[1]), s(n[2])))
+        } else {
+            None
+        };
+        let uv = if has_uv {
+            let uv = parse_floats(line, &rest[rest.len() - 2..])?;
+            rest = &rest[..rest.len() - 2];
+            Some(T::Vec2::new(s(uv[0]), s(uv[1])))
+        } else {
+            None
+        };
+        let color = if has_color {
+            parse_color(line, rest)?
+        } else {
+            None
+        };
+        vps.push(vp(pos, normal, uv, color));
+    }
+
+    let mut faces = Vec::with_capacity(nf.min(1 << 16));
+    for _ in 0..nf {
+        let (line, content) = lines
+            .next()
+            .ok_or_else(|| parse_error(counts_line, format!("Expected {} faces", nf)))?;
+        let parts: Vec<&str> = content.split_whitespace().collect();
+        let n = parts[0].parse::<usize>().map_err(|e| {
+            parse_error(line, format!("Invalid vertex count '{}': {}", parts[0], e))
+        })?;
+        if n >= parts.len() {
+            return Err(parse_error(
+                line,
+                format!("Expected {} vertex indices but got {}", n, parts.len() - 1),
+            ));
+        }
+        let indices = parts[1..=n]
+            .iter()
+            .map(|p| {
+                p.parse::<usize>()
+                    .map_err(|e| parse_error(line, format!("Invalid index '{}': {}", p, e)))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let color = parse_color(line, &parts[n + 1..])?;
+        faces.push((indices, fp(color)));
+    }
+
+    let (_, fs) = mesh.insert_indexed_faces(vps, faces)?;
+    Ok(fs)
+}
+
+#[cfg(test)]
+#[cfg(feature = "nalgebra")]
+mod tests {
+    use crate::{extensions::nalgebra::*, extensions::off::*, prelude::*};
+    use std::cell::RefCell;
+
+    const CUBE: &str = "OFF
+# a cube with colored faces
+8 6 12
+0 0 0
+1 0 0
+1 1 0
+0 1 0
+0 0 1
+1 0 1
+1 1 1
+0 1 1
+4 0 3 2 1 255 0 0
+4 4 5 6 7 0 255 0
+4 0 1 5 4 0 0 255
+4 1 2 6 5 0.5 0.5 0.5 0.5
+4 2 3 7 6
+4 3 0 4 7 3
+";
+
+    #[test]
+    fn test_off_face_colors() {
+        let colors = RefCell::new(Vec::new());
+        let mut mesh = Mesh3d64::new();
+        mesh.import_off_with(
+            CUBE,
+            |pos, _, _, _| VertexPayloadPNU::from_pos(pos),
+            |color| {
+                colors.borrow_mut().push(color);
+                Default::default()
+            },
+        )
+        .unwrap();
+        assert!(mesh.check().is_ok());
+        assert!(!mesh.is_open());
+        assert_eq!(mesh.num_faces(), 6);
+        assert_eq!(
+            colors.into_inner(),
+            vec![
+                Some([1.0, 0.0, 0.0, 1.0]),
+                Some([0.0, 1.0, 0.0, 1.0]),
+                Some([0.0, 0.0, 1.0, 1.0]),
+                Some([0.5, 0.5, 0.5, 0.5]),
+                None,
+                // color map indices are ignored
+                None,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_off_errors() {
+        assert!(matches!(
+            Mesh3d64::from_off("PLY\n"),
+            Err(OffError::Parse { line: 1, .. })
+        ));
+        assert!(matches!(
+            Mesh3d64::from_off("OFF\n3 1 0\n0 0 0\n1 0 0\n0 1 0\n3 0 1\n"),
+            Err(OffError::Parse { line: 6, .. })
+        ));
+        assert!(matches!(
+            Mesh3d64::from_off("OFF 3 1 0\n0 0 0\n1 0 0\n"),
+            Err(OffError::Parse { line: 1, .. })
+        ));
+        // a bogus vertex count of a face fails instead of overflowing
+        assert!(matches!(
+            Mesh3d64::from_off("OFF\n3 1 0\n0 0 0\n1 0 0\n0 1 0\n18446744073709551615 0 1 2\n"),
+            Err(OffError::Parse { line: 6, .. })
+        ));
+        // the counts have to be non-negative integers
+        for count in ["3.7", "-1", "1e30", "nan"] {
+            assert!(matches!(
+                Mesh3d64::from_off(&format!("OFF\n{} 0 0\n", count)),
+                Err(OffError::Parse { line: 2, .. })
+            ));
+        }
+        // bogus counts fail when the data runs out instead of allocating
+        assert!(matches!(
+            Mesh3d64::from_off("OFF\n1000000000000 1000000000000 0\n0 0 0\n"),
+            Err(OffError::Parse { line: 2, .. })
+        ));
+    }
+}
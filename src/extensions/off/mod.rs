@@ -0,0 +1,85 @@
+//! This module contains the OFF and COFF import and export
+
+use crate::{
+    math::{HasNormal, HasPosition, HasUV},
+    mesh::{DefaultEdgePayload, DefaultFacePayload, MeshBuilder, MeshType3D},
+};
+
+mod export;
+mod import;
+
+use export::*;
+pub use import::*;
+
+/// Backend trait for OFF and COFF import/export.
+pub trait BackendOFF<T: MeshType3D<Mesh = Self>>: MeshBuilder<T> {
+    /// Import an OFF string into the mesh.
+    /// Normals and uv coordinates are written to the vertex payloads if present.
+    /// Colors are ignored. Returns the ids of the inserted faces.
+    fn import_off(&mut self, off: &str) -> Result<Vec<T::F>, OffError>
+    where
+        T::EP: DefaultEdgePayload,
+        T::FP: DefaultFacePayload,
+        T::VP: HasNormal<3, T::Vec, S = T::S> + HasUV<T::Vec2, S = T::S>,
+    {
+        import_off::<T>(
+            self,
+            off,
+            |pos, normal, uv, _| {
+                let mut vp = T::VP::from_pos(pos);
+                if let Some(normal) = normal {
+                    vp.set_normal(normal);
+                }
+                if let Some(uv) = uv {
+                    vp.set_uv(uv);
+                }
+                vp
+            },
+            |_| T::FP::default(),
+        )
+    }
+
+    /// Like `import_off`, but constructs the payloads using the given functions.
+    /// `vp` receives the position and, if present, the normal, uv coordinates, and color of the vertex.
+    /// `fp` receives the color of the face if present.
+    fn import_off_with(
+        &mut self,
+        off: &str,
+        vp: impl Fn(T::Vec, Option<T::Vec>, Option<T::Vec2>, Option<OffColor>) -> T::VP,
+        fp: impl Fn(Option<OffColor>) -> T::FP,
+    ) -> Result<Vec<T::F>, OffError>
+    where
+        T::EP: DefaultEdgePayload,
+    {
+        import_off::<T>(self, off, vp, fp)
+    }
+
+    /// Create a new mesh from an OFF string.
+    fn from_off(off: &str) -> Result<Self, OffError>
+    where
+        T::EP: DefaultEdgePayload,
+        T::FP: DefaultFacePayload,
+        T::VP: HasNormal<3, T::Vec, S = T::S> + HasUV<T::Vec2, S = T::S>,
+    {
+        let mut mesh = Self::default();
+        mesh.import_off(off)?;
+        Ok(mesh)
+    }
+
+    /// Export the mesh as an OFF string with n-gon faces and without colors.
+    fn to_off(&self) -> String {
+        export_off::<T>(self, None, None)
+    }
+
+    /// Like `to_off`, but writes the colors returned by the given functions.
+    /// If `vertex_color` is given, the mesh is written as a COFF file.
+    fn to_off_with(
+        &self,
+        vertex_color: Option<&dyn Fn(&T::VP) -> OffColor>,
+        face_color: Option<&dyn Fn(&T::FP) -> OffColor>,
+    ) -> String {
+        export_off::<T>(self, vertex_color, face_color)
+    }
+}
+
+impl<T: MeshType3D> BackendOFF<T> for T::Mesh {}
@@ -0,0 +1,342 @@
+use super::{PlyFormat, PlyProperty, PlyScalarType};
+use crate::{
+    extensions::polygons::polygons,
+    math::{Scalar, Vector},
+    mesh::{FaceBasics, MeshBasics, MeshType3D, VertexBasics},
+};
+use std::{collections::HashMap, fmt::Write};
+
+/// The floating point type matching the precision of the scalar.
+pub(crate) fn float_type<S: Scalar>() -> PlyScalarType {
+    if std::mem::size_of::<S>() <= 4 {
+        PlyScalarType::Float
+    } else {
+        PlyScalarType::Double
+    }
+}
+
+/// Writes the header lines of the properties.
+/// All elements must have the same properties as the first one.
+fn write_properties(header: &mut String, properties: &[Vec<PlyProperty>]) {
+    let Some(first) = properties.first() else {
+        return;
+    };
+    for p in first.iter() {
+        writeln!(header, "property {} {}", p.ty.name(), p.name).unwrap();
+    }
+    debug_assert!(
+        properties.iter().all(|ps| ps.len() == first.len()
+            && ps
+                .iter()
+                .zip(first.iter())
+                .all(|(a, b)| a.name == b.name && a.ty == b.ty)),
+        "all elements must have the same properties"
+    );
+}
+
+fn write_value(out: &mut Vec<u8>, format: PlyFormat, ty: PlyScalarType, value: f64) {
+    match format {
+        PlyFormat::Ascii => {
+            // separate the values but don't start a line with a space
+            if out.last() != Some(&b'\n') {
+                out.push(b' ');
+            }
+            if ty.is_integer() {
+                out.extend_from_slice((value as i64).to_string().as_bytes());
+            } else {
+                out.extend_from_slice(value.to_string().as_bytes());
+            }
+        }
+        PlyFormat::BinaryLittleEndian => ty.write_le(value, out),
+    }
+}
+
+/// Writes the mesh as a PLY file with the positions, the properties returned by `vp`
+/// for each vertex, the face loops, and the properties returned by `fp` for each face.
+/// Faces with holes are triangulated and each triangle gets the properties of its face.
+pub(crate) fn export_ply<T: MeshType3D>(
+    mesh: &T::Mesh,
+    format: PlyFormat,
+    vp: impl Fn(&T::VP) -> Vec<PlyProperty>,
+    fp: impl Fn(&T::FP) -> Vec<PlyProperty>,
+) -> Vec<u8> {
+    let pos_ty = float_type::<T::S>();
+
+    // vertex ids might be sparse
+    let mut id_map = HashMap::new();
+    let mut vertex_properties = Vec::new();
+    for (i, v) in mesh.vertices().enumerate() {
+        id_map.insert(v.id(), i);
+        let p = v.pos();
+        let mut properties = vec![
+            PlyProperty::new("x", pos_ty, p.x().to_f64()),
+            PlyProperty::new("y", pos_ty, p.y().to_f64()),
+            PlyProperty::new("z", pos_ty, p.z().to_f64()),
+        ];
+        properties.extend(vp(v.payload()));
+        vertex_properties.push(properties);
+    }
+    let polygons = polygons::<T>(mesh);
+    let face_properties: Vec<_> = polygons
+        .iter()
+        .map(|(f, _)| fp(mesh.face(*f).payload()))
+        .collect();
+
+    // most readers expect `uchar`, so only use a wider count type if a face needs it
+    let max_len = polygons.iter().map(|(_, ids)| ids.len()).max().unwrap_or(0);
+    let count_ty = if max_len <= u8::MAX as usize {
+        PlyScalarType::UChar
+    } else if max_len <= u16::MAX as usize {
+        PlyScalarType::UShort
+    } else {
+        PlyScalarType::UInt
+    };
+
+    let mut header = String::new();
+    writeln!(header, "ply\nformat {} 1.0", format.name()).unwrap();
+    writeln!(header, "element vertex {}", vertex_properties.len()).unwrap();
+    write_properties(&mut header, &vertex_properties);
+    writeln!(header, "element face {}", face_properties.len()).unwrap();
+    writeln!(
+        header,
+        "property list {} int vertex_indices",
+        count_ty.name()
+    )
+    .unwrap();
+    write_properties(&mut header, &face_properties);
+    header.push_str("end_header\n");
+
+    let mut ply = header.into_bytes();
+    for properties in vertex_properties.iter() {
+        for p in properties.iter() {
+            write_value(&mut ply, format, p.ty, p.value);
+        }
+        if format == PlyFormat::Ascii {
+            ply.push(b'\n');
+        }
+    }
+    for ((_, ids), properties) in polygons.iter().zip(face_properties.iter()) {
+        write_value(&mut ply, format, count_ty, ids.len() as f64);
+        for v in ids {
+            write_value(&mut ply, format, PlyScalarType::Int, id_map[v] as f64);
+        }
+        for p in properties.iter() {
+            write_value(&mut ply, format, p.ty, p.value);
+        }
+        if format == PlyFormat::Ascii {
+            ply.push(b'\n');
+        }
+    }
+
+    ply
+}
+
+#[cfg(test)]
+#[cfg(feature = "nalgebra")]
+mod tests {
+    use crate::{
+        extensions::nalgebra::*,
+        extensions::ply::*,
+        prelude::*,
+        test_util::{area, square_with_hole},
+    };
+    use std::cell::RefCell;
+
+    #[test]
+    fn test_ply_round_trip() {
+        let mut mesh = Mesh3d64::regular_prism(1.0, 1.0, 6);
+        mesh.generate_smooth_normals();
+
+        for format in [PlyFormat::Ascii, PlyFormat::BinaryLittleEndian] {
+            let ply = mesh.to_ply(format);
+            let reimported = Mesh3d64::from_ply(&ply).unwrap();
+            assert!(reimported.check().is_ok());
+            assert!(reimported
+                .is_isomorphic_by_pos::<_, 3, _, MeshType3d64PNU>(&mesh, 1e-6)
+                .eq());
+            if format == PlyFormat::Ascii {
+                let ply = String::from_utf8(ply).unwrap();
+                assert!(ply
+                    .lines()
+                    .all(|line| !line.starts_with(' ') && !line.ends_with(' ')));
+            }
+            for (a, b) in mesh.vertices().zip(reimported.vertices()) {
+                assert!(a.payload().normal().is_about(b.payload().normal(), 1e-6));
+            }
+        }
+    }
+
+    #[test]
+    fn test_ply_export_hole() {
+        // each triangle of the face with a hole is a face element with the properties of the face
+        let (mesh, _, _) = square_with_hole();
+        let material = PlyProperty::new("material", PlyScalarType::UChar, 3.0);
+        for format in [PlyFormat::Ascii, PlyFormat::BinaryLittleEndian] {
+            let ply = mesh.to_ply_with(format, |_| Vec::new(), |_| vec![material.clone()]);
+            let header = String::from_utf8_lossy(&ply);
+            assert!(header.contains("element vertex 8\n"));
+            assert!(header.contains("element face 8\nproperty list uchar int vertex_indices\n"));
+
+            let materials = RefCell::new(Vec::new());
+            let mut reimported = Mesh3d64::new();
+            reimported
+                .import_ply_with(
+                    &ply,
+                    |pos, _, _, _| VertexPayloadPNU::from_pos(pos),
+                    |properties| {
+                        materials.borrow_mut().push(properties.to_vec());
+                        Default::default()
+                    },
+                )
+                .unwrap();
+            assert!(reimported.check().is_ok());
+            assert!(reimported.faces().all(|f| f.num_vertices(&reimported) == 3));
+            assert!(area(&reimported).is_about(12.0, 1e-8));
+            assert_eq!(materials.into_inner(), vec![vec![material.clone()]; 8]);
+        }
+    }
+
+    #[test]
+    fn test_ply_large_faces() {
+        // the vertex count of the face doesn't fit into an `uchar`
+        let mesh = Mesh3d64::regular_polygon(1.0, 300);
+        for format in [PlyFormat::Ascii, PlyFormat::BinaryLittleEndian] {
+            let ply = mesh.to_ply(format);
+            assert!(
+                String::from_utf8_lossy(&ply).contains("property list ushort int vertex_indices")
+            );
+            let reimported = Mesh3d64::from_ply(&ply).unwrap();
+            assert!(reimported.check().is_ok());
+            assert_eq!(reimported.num_faces(), 1);
+            assert_eq!(reimported.num_vertices(), 300);
+            assert!(reimported
+                .is_isomorphic_by_pos::<_, 3, _, MeshType3d64PNU>(&mesh, 1e-6)
+                .eq());
+        }
+    }
+
+    #[test]
+    fn test_ply_custom_properties() {
+        let mesh = Mesh3d64::regular_prism(1.0, 1.0, 4);
+        // derive a color channel from the height and use the same material for all faces
+        let red = |vp: &VertexPayloadPNU<f64, 3>| (vp.pos().y() * 255.0).round();
+        let ply = mesh.to_ply_with(
+            PlyFormat::BinaryLittleEndian,
+            |vp| vec![PlyProperty::new("red", PlyScalarType::UChar, red(vp))],
+            |_| vec![PlyProperty::new("material", PlyScalarType::UShort, 7.0)],
+        );
+
+        let reds = RefCell::new(Vec::new());
+        let materials = RefCell::new(Vec::new());
+        let mut reimported = Mesh3d64::new();
+        reimported
+            .import_ply_with(
+                &ply,
+                |pos, _, _, properties| {
+                    reds.borrow_mut().push(properties.to_vec());
+                    VertexPayloadPNU::from_pos(pos)
+                },
+                |properties| {
+                    materials.borrow_mut().push(properties.to_vec());
+                    Default::default()
+                },
+            )
+            .unwrap();
+
+        for (v, properties) in reimported.vertices().zip(reds.borrow().iter()) {
+            assert_eq!(
+                properties,
+                &vec![PlyProperty::new(
+                    "red",
+                    PlyScalarType::UChar,
+                    red(v.payload())
+                )]
+            );
+        }
+        assert_eq!(materials.borrow().len(), 6);
+        assert!(materials
+            .borrow()
+            .iter()
+            .all(|p| p == &vec![PlyProperty::new("material", PlyScalarType::UShort, 7.0)]));
+    }
+
+    #[test]
+    fn test_ply_errors() {
+        assert!(matches!(
+            Mesh3d64::from_ply(b"ply\nformat binary_big_endian 1.0\nend_header\n"),
+            Err(PlyError::Header { line: 2, .. })
+        ));
+        assert!(matches!(
+            Mesh3d64::from_ply(
+                b"ply\nformat ascii 1.0\nelement vertex 2\nproperty float x\nend_header\n1\n"
+            ),
+            Err(PlyError::Data { index: 0, .. })
+        ));
+        assert!(matches!(
+            Mesh3d64::from_ply(b"ply\nformat ascii 1.0\nelement vertex 1\nproperty float x\nproperty float y\nproperty float z\nend_header\n0 0"),
+            Err(PlyError::Data { index: 0, .. })
+        ));
+
+        // elements without properties can't announce an arbitrary number of instances
+        assert!(matches!(
+            Mesh3d64::from_ply(
+                b"ply\nformat ascii 1.0\nelement junk 18446744073709551615\nend_header\n"
+            ),
+            Err(PlyError::Data { index: 0, .. })
+        ));
+        assert!(Mesh3d64::from_ply(b"ply\nformat ascii 1.0\nelement junk 0\nend_header\n").is_ok());
+
+        // vertex indices have to be non-negative integers
+        for index in ["-1", "1.5", "nan"] {
+            let ply = format!("ply\nformat ascii 1.0\nelement vertex 3\nproperty float x\nproperty float y\nproperty float z\nelement face 1\nproperty list uchar float vertex_indices\nend_header\n0 0 0\n1 0 0\n0 1 0\n3 0 {} 2\n", index);
+            assert!(matches!(
+                Mesh3d64::from_ply(ply.as_bytes()),
+                Err(PlyError::Data { index: 0, .. })
+            ));
+        }
+
+        // integers have to fit into their declared type
+        let header = "ply\nformat ascii 1.0\nelement vertex 3\nproperty float x\nproperty float y\nproperty float z\nelement face 1\nproperty list uchar int vertex_indices\nend_header\n0 0 0\n1 0 0\n0 1 0\n";
+        for face in [
+            "3 0 1 2.5",
+            "300 0 1 2",
+            "3.5 0 1 2",
+            "-1 0 1 2",
+            "nan 0 1 2",
+        ] {
+            assert!(matches!(
+                Mesh3d64::from_ply(format!("{}{}\n", header, face).as_bytes()),
+                Err(PlyError::Data { index: 0, .. })
+            ));
+        }
+        assert!(Mesh3d64::from_ply(format!("{}3 0 1 2\n", header).as_bytes()).is_ok());
+        assert!(matches!(
+            Mesh3d64::from_ply(b"ply\nformat ascii 1.0\nelement face 0\nproperty list float int vertex_indices\nend_header\n"),
+            Err(PlyError::Header { line: 4, .. })
+        ));
+
+        // lists other than the vertex indices can't be passed to the payloads
+        assert!(matches!(
+            Mesh3d64::from_ply(
+                format!(
+                    "{}3 0 1 2 2 0.5 0.5\n",
+                    header.replace(
+                        "end_header",
+                        "property list uchar float texcoord\nend_header"
+                    )
+                )
+                .as_bytes()
+            ),
+            Err(PlyError::Data { index: 0, message, .. }) if message.contains("texcoord")
+        ));
+
+        // a bogus list count fails when the data runs out instead of allocating
+        let mut ply = b"ply\nformat binary_little_endian 1.0\nelement face 1\nproperty list uint int vertex_indices\nend_header\n".to_vec();
+        ply.extend_from_slice(&u32::MAX.to_le_bytes());
+        ply.extend_from_slice(&0i32.to_le_bytes());
+        assert!(matches!(
+            Mesh3d64::from_ply(&ply),
+            Err(PlyError::Data { index: 0, .. })
+        ));
+    }
+}
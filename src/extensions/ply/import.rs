@@ -0,0 +1,329 @@
+use super::{PlyFormat, PlyProperty, PlyScalarType};
+use crate::{
+    math::{Scalar, Vector, Vector2D},
+    mesh::{DefaultEdgePayload, IndexedFaceError, MeshBuilder, MeshType3D},
+};
+
+/// Errors that can occur when importing a PLY file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PlyError {
+    /// A line of the header couldn't be parsed.
+    Header {
+        /// The line number (starting at 1).
+        line: usize,
+        /// A description of the problem.
+        message: String,
+    },
+
+    /// The element data couldn't be read.
+    Data {
+        /// The name of the element.
+        element: String,
+        /// The index of the element instance.
+        index: usize,
+        /// A description of the problem.
+        message: String,
+    },
+
+    /// The faces don't describe an oriented manifold surface.
+    Topology(IndexedFaceError),
+}
+
+impl std::fmt::Display for PlyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PlyError::Header { line, message } => write!(f, "Header line {}: {}", line, message),
+            PlyError::Data {
+                element,
+                index,
+                message,
+            } => write!(f, "{} {}: {}", element, index, message),
+            PlyError::Topology(e) => write!(f, "Invalid topology: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for PlyError {}
+
+impl From<IndexedFaceError> for PlyError {
+    fn from(e: IndexedFaceError) -> Self {
+        PlyError::Topology(e)
+    }
+}
+
+#[derive(Debug, Clone)]
+enum PropertyKind {
+    Scalar(PlyScalarType),
+    List(PlyScalarType, PlyScalarType),
+}
+
+#[derive(Debug, Clone)]
+struct PropertyDef {
+    name: String,
+    kind: PropertyKind,
+}
+
+#[derive(Debug, Clone)]
+struct ElementDef {
+    name: String,
+    count: usize,
+    properties: Vec<PropertyDef>,
+}
+
+/// Parses the header and returns the format, the elements, and the offset of the data.
+fn parse_header(ply: &[u8]) -> Result<(PlyFormat, Vec<ElementDef>, usize), PlyError> {
+    let header_error = |line: usize, message: &str| PlyError::Header {
+        line,
+        message: message.to_string(),
+    };
+
+    let mut format = None;
+    let mut elements: Vec<ElementDef> = Vec::new();
+    let mut offset = 0;
+    let mut line = 0;
+    loop {
+        line += 1;
+        let Some(len) = ply[offset..].iter().position(|&b| b == b'\n') else {
+            return Err(header_error(line, "Missing end_header"));
+        };
+        let raw = String::from_utf8_lossy(&ply[offset..offset + len]).to_string();
+        offset += len + 1;
+        let parts: Vec<&str> = raw.split_whitespace().collect();
+
+        if line == 1 {
+            if parts != ["ply"] {
+                return Err(header_error(line, "Not a PLY file"));
+            }
+            continue;
+        }
+
+        match parts.as_slice() {
+            ["format", "ascii", _] => format = Some(PlyFormat::Ascii),
+            ["format", "binary_little_endian", _] => format = Some(PlyFormat::BinaryLittleEndian),
+            ["format", f, _] => {
+                return Err(header_error(line, &format!("Unsupported format '{}'", f)))
+            }
+            ["element", name, count] => elements.push(ElementDef {
+                name: name.to_string(),
+                count: count
+                    .parse()
+                    .map_err(|_| header_error(line, &format!("Invalid count '{}'", count)))?,
+                properties: Vec::new(),
+            }),
+            ["property", "list", count_ty, item_ty, name] => {
+                let ty = |s: &str| {
+                    PlyScalarType::from_name(s)
+                        .ok_or_else(|| header_error(line, &format!("Unknown type '{}'", s)))
+                };
+                let count_ty = ty(count_ty)?;
+                if !count_ty.is_integer() {
+                    return Err(header_error(
+                        line,
+                        "The list count must have an integer type",
+                    ));
+                }
+                let kind = PropertyKind::List(count_ty, ty(item_ty)?);
+                elements
+                    .last_mut()
+                    .ok_or_else(|| header_error(line, "Property outside of an element"))?
+                    .properties
+                    .push(PropertyDef {
+                        name: name.to_string(),
+                        kind,
+                    });
+            }
+            ["property", ty, name] => {
+                let ty = PlyScalarType::from_name(ty)
+                    .ok_or_else(|| header_error(line, &format!("Unknown type '{}'", ty)))?;
+                elements
+                    .last_mut()
+                    .ok_or_else(|| header_error(line, "Property outside of an element"))?
+                    .properties
+                    .push(PropertyDef {
+                        name: name.to_string(),
+                        kind: PropertyKind::Scalar(ty),
+                    });
+            }
+            ["end_header"] => break,
+            ["comment", ..] | ["obj_info", ..] | [] => {}
+            _ => {
+                return Err(header_error(
+                    line,
+                    &format!("Invalid line '{}'", raw.trim()),
+                ))
+            }
+        }
+    }
+
+    let format = format.ok_or_else(|| header_error(line, "Missing format"))?;
+    Ok((format, elements, offset))
+}
+
+/// Reads scalar values from the body of the file.
+enum PlyReader<'a> {
+    Ascii(std::str::SplitAsciiWhitespace<'a>),
+    Binary(&'a [u8]),
+}
+
+impl PlyReader<'_> {
+    fn read(&mut self, ty: PlyScalarType) -> Result<f64, String> {
+        match self {
+            PlyReader::Ascii(tokens) => {
+                let token = tokens.next().ok_or("Unexpected end of file")?;
+                let Some((min, max)) = ty.integer_range() else {
+                    return token
+                        .parse::<f64>()
+                        .map_err(|e| format!("Invalid number '{}': {}", token, e));
+                };
+                let value = token
+                    .parse::<i64>()
+                    .map_err(|e| format!("Invalid {} '{}': {}", ty.name(), token, e))?;
+                if value < min || value > max {
+                    return Err(format!("The {} '{}' is out of range", ty.name(), token));
+                }
+                Ok(value as f64)
+            }
+            PlyReader::Binary(data) => {
+                if data.len() < ty.size() {
+                    return Err("Unexpected end of file".to_string());
+                }
+                let (value, rest) = data.split_at(ty.size());
+                *data = rest;
+                Ok(ty.read_le(value))
+            }
+        }
+    }
+}
+
+/// The vertex or face properties that have a special meaning.
+const POSITION: [&str; 3] = ["x", "y", "z"];
+const NORMAL: [&str; 3] = ["nx", "ny", "nz"];
+const UV: [[&str; 2]; 3] = [["u", "v"], ["s", "t"], ["texture_u", "texture_v"]];
+const INDICES: [&str; 2] = ["vertex_indices", "vertex_index"];
+
+/// Removes the properties with the given names if all of them are present.
+fn take_all<const N: usize>(scalars: &mut Vec<PlyProperty>, names: [&str; N]) -> Option<[f64; N]> {
+    let positions = names.map(|name| scalars.iter().position(|p| p.name == name));
+    if positions.iter().any(|p| p.is_none()) {
+        return None;
+    }
+    let values = positions.map(|i| scalars[i.unwrap()].value);
+    scalars.retain(|p| !names.contains(&p.name.as_str()));
+    Some(values)
+}
+
+/// Parses the PLY file and inserts its faces into the mesh.
+///
+/// Positions, normals, and uv coordinates are passed to `vp` together with all other scalar vertex properties.
+/// The scalar face properties are passed to `fp`. Other elements are ignored.
+/// Vertex and face list properties other than the vertex indices are rejected.
+pub(crate) fn import_ply<T: MeshType3D>(
+    mesh: &mut T::Mesh,
+    ply: &[u8],
+    vp: impl Fn(T::Vec, Option<T::Vec>, Option<T::Vec2>, &[PlyProperty]) -> T::VP,
+    fp: impl Fn(&[PlyProperty]) -> T::FP,
+) -> Result<Vec<T::F>, PlyError>
+where
+    T::EP: DefaultEdgePayload,
+{
+    let (format, elements, offset) = parse_header(ply)?;
+    let mut reader = match format {
+        PlyFormat::Ascii => PlyReader::Ascii(
+            std::str::from_utf8(&ply[offset..])
+                .map_err(|e| PlyError::Data {
+                    element: elements.first().map(|e| e.name.clone()).unwrap_or_default(),
+                    index: 0,
+                    message: format!("Invalid UTF-8: {}", e),
+                })?
+                .split_ascii_whitespace(),
+        ),
+        PlyFormat::BinaryLittleEndian => PlyReader::Binary(&ply[offset..]),
+    };
+
+    let s = |x: f64| T::S::from_f64(x);
+    let mut vps = Vec::new();
+    let mut faces = Vec::new();
+
+    for element in elements.iter() {
+        // an element without properties doesn't consume any data, so its count could be arbitrary
+        if element.properties.is_empty() && element.count > 0 {
+            return Err(PlyError::Data {
+                element: element.name.clone(),
+                index: 0,
+                message: "Element has no properties".to_string(),
+            });
+        }
+
+        for index in 0..element.count {
+            let data_error = |message: String| PlyError::Data {
+                element: element.name.clone(),
+                index,
+                message,
+            };
+
+            let mut scalars = Vec::new();
+            let mut indices = None;
+            for property in element.properties.iter() {
+                match property.kind {
+                    PropertyKind::Scalar(ty) => scalars.push(PlyProperty {
+                        name: property.name.clone(),
+                        ty,
+                        value: reader.read(ty).map_err(data_error)?,
+                    }),
+                    PropertyKind::List(count_ty, item_ty) => {
+                        // the count type is an integer type, so the value is an integer
+                        let n = reader.read(count_ty).map_err(data_error)?;
+                        let n = usize::try_from(n as i64)
+                            .map_err(|_| data_error(format!("Invalid list length {}", n)))?;
+                        // the count is untrusted, so don't pre-allocate more than a typical face
+                        let mut list = Vec::with_capacity(n.min(64));
+                        for _ in 0..n {
+                            list.push(reader.read(item_ty).map_err(data_error)?);
+                        }
+                        if element.name == "face" && INDICES.contains(&property.name.as_str()) {
+                            indices = Some(list);
+                        } else if element.name == "vertex" || element.name == "face" {
+                            return Err(data_error(format!(
+                                "Unsupported list property '{}'",
+                                property.name
+                            )));
+                        }
+                    }
+                }
+            }
+
+            match element.name.as_str() {
+                "vertex" => {
+                    let pos = take_all(&mut scalars, POSITION)
+                        .ok_or_else(|| data_error("Missing x, y, or z".to_string()))?;
+                    let normal = take_all(&mut scalars, NORMAL);
+                    let uv = UV.iter().find_map(|names| take_all(&mut scalars, *names));
+                    vps.push(vp(
+                        T::Vec::from_xyz(s(pos[0]), s(pos[1]), s(pos[2])),
+                        normal.map(|n| T::Vec::from_xyz(s(n[0]), s(n[1]), s(n[2]))),
+                        uv.map(|uv| T::Vec2::new(s(uv[0]), s(uv[1]))),
+                        &scalars,
+                    ));
+                }
+                "face" => {
+                    let indices = indices
+                        .ok_or_else(|| data_error("Missing vertex_indices".to_string()))?
+                        .iter()
+                        .map(|&i| {
+                            if !i.is_finite() || i.fract() != 0.0 || i < 0.0 {
+                                Err(data_error(format!("Invalid vertex index {}", i)))
+                            } else {
+                                Ok(i as usize)
+                            }
+                        })
+                        .collect::<Result<Vec<_>, _>>()?;
+                    faces.push((indices, fp(&scalars)));
+                }
+                _ => {}
+            }
+        }
+    }
+
+    let (_, fs) = mesh.insert_indexed_faces(vps, faces)?;
+    Ok(fs)
+}
@@ -0,0 +1,111 @@
+//! This module contains the Stanford PLY import and export
+
+use crate::{
+    math::{HasNormal, HasPosition, HasUV, Scalar, Vector},
+    mesh::{DefaultEdgePayload, DefaultFacePayload, MeshBuilder, MeshType3D},
+};
+
+mod export;
+mod import;
+mod property;
+
+use export::*;
+pub use import::*;
+pub use property::*;
+
+/// Backend trait for Stanford PLY import/export.
+pub trait BackendPLY<T: MeshType3D<Mesh = Self>>: MeshBuilder<T> {
+    /// Import an ASCII or binary little-endian PLY file into the mesh.
+    /// Normals (`nx`, `ny`, `nz`) and uv coordinates (`u`, `v` or `s`, `t`)
+    /// are written to the vertex payloads if present. Other scalar properties are ignored.
+    /// Returns the ids of the inserted faces.
+    fn import_ply(&mut self, ply: &[u8]) -> Result<Vec<T::F>, PlyError>
+    where
+        T::EP: DefaultEdgePayload,
+        T::FP: DefaultFacePayload,
+        T::VP: HasNormal<3, T::Vec, S = T::S> + HasUV<T::Vec2, S = T::S>,
+    {
+        import_ply::<T>(
+            self,
+            ply,
+            |pos, normal, uv, _| {
+                let mut vp = T::VP::from_pos(pos);
+                if let Some(normal) = normal {
+                    vp.set_normal(normal);
+                }
+                if let Some(uv) = uv {
+                    vp.set_uv(uv);
+                }
+                vp
+            },
+            |_| T::FP::default(),
+        )
+    }
+
+    /// Like `import_ply`, but constructs the payloads using the given functions.
+    /// `vp` receives the position, the normal and uv coordinates if present,
+    /// and all remaining scalar properties of the vertex.
+    /// `fp` receives the scalar properties of the face.
+    /// List properties other than the vertex indices of a face aren't supported and return an error.
+    fn import_ply_with(
+        &mut self,
+        ply: &[u8],
+        vp: impl Fn(T::Vec, Option<T::Vec>, Option<T::Vec2>, &[PlyProperty]) -> T::VP,
+        fp: impl Fn(&[PlyProperty]) -> T::FP,
+    ) -> Result<Vec<T::F>, PlyError>
+    where
+        T::EP: DefaultEdgePayload,
+    {
+        import_ply::<T>(self, ply, vp, fp)
+    }
+
+    /// Create a new mesh from a PLY file.
+    fn from_ply(ply: &[u8]) -> Result<Self, PlyError>
+    where
+        T::EP: DefaultEdgePayload,
+        T::FP: DefaultFacePayload,
+        T::VP: HasNormal<3, T::Vec, S = T::S> + HasUV<T::Vec2, S = T::S>,
+    {
+        let mut mesh = Self::default();
+        mesh.import_ply(ply)?;
+        Ok(mesh)
+    }
+
+    /// Export the mesh as a PLY file with positions, normals, and uv coordinates.
+    /// Faces are written as n-gons.
+    fn to_ply(&self, format: PlyFormat) -> Vec<u8>
+    where
+        T::VP: HasNormal<3, T::Vec, S = T::S> + HasUV<T::Vec2, S = T::S>,
+    {
+        let ty = float_type::<T::S>();
+        export_ply::<T>(
+            self,
+            format,
+            |vp| {
+                let n = vp.normal();
+                let uv = vp.uv();
+                vec![
+                    PlyProperty::new("nx", ty, n.x().to_f64()),
+                    PlyProperty::new("ny", ty, n.y().to_f64()),
+                    PlyProperty::new("nz", ty, n.z().to_f64()),
+                    PlyProperty::new("u", ty, uv.x().to_f64()),
+                    PlyProperty::new("v", ty, uv.y().to_f64()),
+                ]
+            },
+            |_| Vec::new(),
+        )
+    }
+
+    /// Export the mesh as a PLY file with positions and the properties returned by the given functions.
+    /// Every vertex and every face must return the same list of property names and types.
+    fn to_ply_with(
+        &self,
+        format: PlyFormat,
+        vp: impl Fn(&T::VP) -> Vec<PlyProperty>,
+        fp: impl Fn(&T::FP) -> Vec<PlyProperty>,
+    ) -> Vec<u8> {
+        export_ply::<T>(self, format, vp, fp)
+    }
+}
+
+impl<T: MeshType3D> BackendPLY<T> for T::Mesh {}
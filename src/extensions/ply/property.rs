@@ -0,0 +1,155 @@
+/// The encoding of a PLY file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PlyFormat {
+    /// The human-readable ASCII encoding.
+    Ascii,
+
+    /// The binary little-endian encoding.
+    #[default]
+    BinaryLittleEndian,
+}
+
+impl PlyFormat {
+    /// The name of the format in the header.
+    pub fn name(&self) -> &'static str {
+        match self {
+            PlyFormat::Ascii => "ascii",
+            PlyFormat::BinaryLittleEndian => "binary_little_endian",
+        }
+    }
+}
+
+/// The scalar types of PLY properties.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlyScalarType {
+    /// 8-bit signed integer (`char` or `int8`)
+    Char,
+    /// 8-bit unsigned integer (`uchar` or `uint8`)
+    UChar,
+    /// 16-bit signed integer (`short` or `int16`)
+    Short,
+    /// 16-bit unsigned integer (`ushort` or `uint16`)
+    UShort,
+    /// 32-bit signed integer (`int` or `int32`)
+    Int,
+    /// 32-bit unsigned integer (`uint` or `uint32`)
+    UInt,
+    /// 32-bit floating point number (`float` or `float32`)
+    Float,
+    /// 64-bit floating point number (`double` or `float64`)
+    Double,
+}
+
+impl PlyScalarType {
+    /// Parses the name of the type as used in the header.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "char" | "int8" => Some(PlyScalarType::Char),
+            "uchar" | "uint8" => Some(PlyScalarType::UChar),
+            "short" | "int16" => Some(PlyScalarType::Short),
+            "ushort" | "uint16" => Some(PlyScalarType::UShort),
+            "int" | "int32" => Some(PlyScalarType::Int),
+            "uint" | "uint32" => Some(PlyScalarType::UInt),
+            "float" | "float32" => Some(PlyScalarType::Float),
+            "double" | "float64" => Some(PlyScalarType::Double),
+            _ => None,
+        }
+    }
+
+    /// The name of the type as written to the header.
+    pub fn name(&self) -> &'static str {
+        match self {
+            PlyScalarType::Char => "char",
+            PlyScalarType::UChar => "uchar",
+            PlyScalarType::Short => "short",
+            PlyScalarType::UShort => "ushort",
+            PlyScalarType::Int => "int",
+            PlyScalarType::UInt => "uint",
+            PlyScalarType::Float => "float",
+            PlyScalarType::Double => "double",
+        }
+    }
+
+    /// The size of the type in bytes.
+    pub fn size(&self) -> usize {
+        match self {
+            PlyScalarType::Char | PlyScalarType::UChar => 1,
+            PlyScalarType::Short | PlyScalarType::UShort => 2,
+            PlyScalarType::Int | PlyScalarType::UInt | PlyScalarType::Float => 4,
+            PlyScalarType::Double => 8,
+        }
+    }
+
+    /// Whether the type is an integer type.
+    pub fn is_integer(&self) -> bool {
+        !matches!(self, PlyScalarType::Float | PlyScalarType::Double)
+    }
+
+    /// The smallest and largest value of an integer type.
+    pub(crate) fn integer_range(&self) -> Option<(i64, i64)> {
+        match self {
+            PlyScalarType::Char => Some((i8::MIN as i64, i8::MAX as i64)),
+            PlyScalarType::UChar => Some((0, u8::MAX as i64)),
+            PlyScalarType::Short => Some((i16::MIN as i64, i16::MAX as i64)),
+            PlyScalarType::UShort => Some((0, u16::MAX as i64)),
+            PlyScalarType::Int => Some((i32::MIN as i64, i32::MAX as i64)),
+            PlyScalarType::UInt => Some((0, u32::MAX as i64)),
+            PlyScalarType::Float | PlyScalarType::Double => None,
+        }
+    }
+
+    /// Reads a little-endian value of this type. The slice must have the size of the type.
+    pub(crate) fn read_le(&self, b: &[u8]) -> f64 {
+        match self {
+            PlyScalarType::Char => b[0] as i8 as f64,
+            PlyScalarType::UChar => b[0] as f64,
+            PlyScalarType::Short => i16::from_le_bytes([b[0], b[1]]) as f64,
+            PlyScalarType::UShort => u16::from_le_bytes([b[0], b[1]]) as f64,
+            PlyScalarType::Int => i32::from_le_bytes([b[0], b[1], b[2], b[3]]) as f64,
+            PlyScalarType::UInt => u32::from_le_bytes([b[0], b[1], b[2], b[3]]) as f64,
+            PlyScalarType::Float => f32::from_le_bytes([b[0], b[1], b[2], b[3]]) as f64,
+            PlyScalarType::Double => {
+                f64::from_le_bytes([b[0], b[1], b[2], b[3], b[4], b[5], b[6], b[7]])
+            }
+        }
+    }
+
+    /// Appends the value as a little-endian value of this type.
+    pub(crate) fn write_le(&self, value: f64, out: &mut Vec<u8>) {
+        match self {
+            PlyScalarType::Char => out.extend_from_slice(&(value as i8).to_le_bytes()),
+            PlyScalarType::UChar => out.extend_from_slice(&(value as u8).to_le_bytes()),
+            PlyScalarType::Short => out.extend_from_slice(&(value as i16).to_le_bytes()),
+            PlyScalarType::UShort => out.extend_from_slice(&(value as u16).to_le_bytes()),
+            PlyScalarType::Int => out.extend_from_slice(&(value as i32).to_le_bytes()),
+            PlyScalarType::UInt => out.extend_from_slice(&(value as u32).to_le_bytes()),
+            PlyScalarType::Float => out.extend_from_slice(&(value as f32).to_le_bytes()),
+            PlyScalarType::Double => out.extend_from_slice(&value.to_le_bytes()),
+        }
+    }
+}
+
+/// A scalar property of a PLY element, e.g., the `red` channel of a vertex.
+/// All supported types are represented exactly by a `f64`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlyProperty {
+    /// The name of the property.
+    pub name: String,
+
+    /// The type of the property in the file.
+    pub ty: PlyScalarType,
+
+    /// The value of the property.
+    pub value: f64,
+}
+
+impl PlyProperty {
+    /// Creates a new property.
+    pub fn new(name: &str, ty: PlyScalarType, value: f64) -> Self {
+        Self {
+            name: name.to_string(),
+            ty,
+            value,
+        }
+    }
+}
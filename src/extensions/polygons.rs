@@ -0,0 +1,29 @@
+use crate::{
+    mesh::{FaceBasics, MeshBasics, MeshType3D, Triangulation},
+    tesselate::{triangulate_face, TesselationMeta, TriangulationAlgorithm},
+};
+
+/// Returns the vertex loops of the faces together with the id of their face.
+/// Faces with holes can't be written as a single polygon,
+/// so they are triangulated and each triangle is returned as a separate loop.
+pub(crate) fn polygons<T: MeshType3D>(mesh: &T::Mesh) -> Vec<(T::F, Vec<T::V>)> {
+    let mut meta = TesselationMeta::default();
+    let mut polygons = Vec::with_capacity(mesh.num_faces());
+    for f in mesh.faces() {
+        if !f.has_holes() {
+            polygons.push((f.id(), f.vertex_ids(mesh).collect()));
+            continue;
+        }
+        let mut indices = Vec::new();
+        let mut tri = Triangulation::new(&mut indices);
+        triangulate_face::<T>(
+            f,
+            mesh,
+            &mut tri,
+            TriangulationAlgorithm::Delaunay,
+            &mut meta,
+        );
+        polygons.extend(indices.chunks_exact(3).map(|t| (f.id(), t.to_vec())));
+    }
+    polygons
+}
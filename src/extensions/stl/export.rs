@@ -0,0 +1,110 @@
+use crate::{
+    math::{HasPosition, Scalar, Vector},
+    mesh::{Face3d, MeshBasics, MeshType3D, Triangulation, VertexBasics},
+    tesselate::{triangulate_face, TesselationMeta, TriangulationAlgorithm},
+};
+use std::fmt::Write;
+
+/// The encoding of an STL file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum StlFormat {
+    /// The compact binary encoding.
+    #[default]
+    Binary,
+
+    /// The human-readable ASCII encoding.
+    Ascii,
+}
+
+/// Triangulates the faces and returns the facet normal and the corners of each triangle.
+/// All triangles of a face share the face's normal.
+fn facets<T: MeshType3D>(
+    mesh: &T::Mesh,
+    algorithm: TriangulationAlgorithm,
+) -> Vec<(T::Vec, [T::Vec; 3])> {
+    let mut meta = TesselationMeta::default();
+    let mut facets = Vec::new();
+    for f in mesh.faces() {
+        let mut indices = Vec::new();
+        let mut tri = Triangulation::new(&mut indices);
+        triangulate_face::<T>(f, mesh, &mut tri, algorithm, &mut meta);
+        let normal = Face3d::normal(f, mesh).normalize();
+        for t in indices.chunks_exact(3) {
+            facets.push((
+                normal,
+                [0, 1, 2].map(|i| *mesh.vertex(t[i]).payload().pos()),
+            ));
+        }
+    }
+    facets
+}
+
+/// Writes the mesh as an STL file after triangulating it with the given algorithm.
+pub(crate) fn export_stl<T: MeshType3D>(
+    mesh: &T::Mesh,
+    format: StlFormat,
+    algorithm: TriangulationAlgorithm,
+) -> Vec<u8> {
+    let facets = facets::<T>(mesh, algorithm);
+    match format {
+        StlFormat::Binary => {
+            let mut stl = Vec::with_capacity(84 + 50 * facets.len());
+            stl.extend_from_slice(&[0; 80]);
+            stl.extend_from_slice(&(facets.len() as u32).to_le_bytes());
+            for (normal, corners) in facets.iter() {
+                for v in std::iter::once(normal).chain(corners.iter()) {
+                    for c in [v.x(), v.y(), v.z()] {
+                        stl.extend_from_slice(&(c.to_f64() as f32).to_le_bytes());
+                    }
+                }
+                // attribute byte count
+                stl.extend_from_slice(&[0; 2]);
+            }
+            stl
+        }
+        StlFormat::Ascii => {
+            let mut stl = String::from("solid mesh\n");
+            for (n, corners) in facets.iter() {
+                writeln!(stl, "facet normal {} {} {}", n.x(), n.y(), n.z()).unwrap();
+                stl.push_str("  outer loop\n");
+                for p in corners.iter() {
+                    writeln!(stl, "    vertex {} {} {}", p.x(), p.y(), p.z()).unwrap();
+                }
+                stl.push_str("  endloop\nendfacet\n");
+            }
+            stl.push_str("endsolid mesh\n");
+            stl.into_bytes()
+        }
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "nalgebra")]
+mod tests {
+    use crate::{extensions::nalgebra::*, extensions::stl::*, prelude::*};
+
+    #[test]
+    fn test_stl_round_trip() {
+        let mesh = Mesh3d64::regular_prism(1.0, 1.0, 6);
+        let center = mesh.centroid();
+        for format in [StlFormat::Binary, StlFormat::Ascii] {
+            let stl = mesh.to_stl(format);
+            if format == StlFormat::Binary {
+                assert_eq!(stl.len(), 84 + 50 * 20);
+            }
+
+            let reimported = Mesh3d64::from_stl(&stl, 1e-5).unwrap();
+            assert!(reimported.check().is_ok());
+            assert!(!reimported.is_open());
+            assert_eq!(reimported.num_vertices(), 12);
+            assert_eq!(reimported.num_faces(), 20);
+            for f in reimported.faces() {
+                assert!(
+                    f.normal(&reimported)
+                        .dot(&(f.centroid::<3>(&reimported) - center))
+                        > 0.0
+                );
+            }
+        }
+    }
+}
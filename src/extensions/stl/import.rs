@@ -0,0 +1,273 @@
+use crate::{
+    math::{HasPosition, Scalar, Vector},
+    mesh::{
+        weld, DefaultEdgePayload, DefaultFacePayload, IndexedFaceError, MeshBuilder, MeshType3D,
+    },
+};
+
+/// Errors that can occur when importing an STL file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StlError {
+    /// A line of an ASCII file couldn't be parsed.
+    Parse {
+        /// The line number (starting at 1), or the triangle number for binary files.
+        line: usize,
+        /// A description of the problem.
+        message: String,
+    },
+
+    /// A binary file is shorter than its triangle count requires.
+    Truncated {
+        /// The number of bytes required by the header.
+        expected: usize,
+        /// The actual number of bytes.
+        actual: usize,
+    },
+
+    /// The welded triangles don't describe an oriented manifold surface.
+    Topology(IndexedFaceError),
+}
+
+impl std::fmt::Display for StlError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StlError::Parse { line, message } => write!(f, "Line {}: {}", line, message),
+            StlError::Truncated { expected, actual } => write!(
+                f,
+                "Truncated binary STL: expected {} bytes but got {}",
+                expected, actual
+            ),
+            StlError::Topology(e) => write!(f, "Invalid topology: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for StlError {}
+
+impl From<IndexedFaceError> for StlError {
+    fn from(e: IndexedFaceError) -> Self {
+        StlError::Topology(e)
+    }
+}
+
+/// Returns whether the file is a binary STL file.
+/// Some binary files start with "solid" as well, so the size is checked first.
+fn is_binary(stl: &[u8]) -> bool {
+    if stl.len() >= 84 {
+        let n = u32::from_le_bytes([stl[80], stl[81], stl[82], stl[83]]) as usize;
+        if 84 + 50 * n == stl.len() {
+            return true;
+        }
+    }
+    !stl.trim_ascii_start().starts_with(b"solid")
+}
+
+fn parse_binary(stl: &[u8]) -> Result<Vec<[[f64; 3]; 3]>, StlError> {
+    if stl.len() < 84 {
+        return Err(StlError::Truncated {
+            expected: 84,
+            actual: stl.len(),
+        });
+    }
+    let n = u32::from_le_bytes([stl[80], stl[81], stl[82], stl[83]]) as usize;
+    if stl.len() < 84 + 50 * n {
+        return Err(StlError::Truncated {
+            expected: 84 + 50 * n,
+            actual: stl.len(),
+        });
+    }
+
+    let f = |offset: usize| {
+        f32::from_le_bytes([
+            stl[offset],
+            stl[offset + 1],
+            stl[offset + 2],
+            stl[offset + 3],
+        ]) as f64
+    };
+    (0..n)
+        .map(|i| {
+            // skip the facet normal
+            let start = 84 + 50 * i + 12;
+            let triangle: [[f64; 3]; 3] =
+                std::array::from_fn(|j| std::array::from_fn(|k| f(start + 12 * j + 4 * k)));
+            if triangle.iter().flatten().all(|x| x.is_finite()) {
+                Ok(triangle)
+            } else {
+                Err(StlError::Parse {
+                    line: i + 1,
+                    message: "Non-finite vertex coordinate".to_string(),
+                })
+            }
+        })
+        .collect()
+}
+
+fn parse_ascii(stl: &[u8]) -> Result<Vec<[[f64; 3]; 3]>, StlError> {
+    let stl = String::from_utf8_lossy(stl);
+    let mut triangles = Vec::new();
+    let mut corners: Vec<[f64; 3]> = Vec::with_capacity(3);
+
+    for (i, raw) in stl.lines().enumerate() {
+        let line = i + 1;
+        let mut parts = raw.split_whitespace();
+        match parts.next() {
+            Some("vertex") => {
+                let mut p = [0.0; 3];
+                for c in p.iter_mut() {
+                    let s = parts.next().ok_or_else(|| StlError::Parse {
+                        line,
+                        message: "Expected 3 coordinates".to_string(),
+                    })?;
+                    *c = s.parse::<f64>().map_err(|e| StlError::Parse {
+                        line,
+                        message: format!("Invalid number '{}': {}", s, e),
+                    })?;
+                    if !c.is_finite() {
+                        return Err(StlError::Parse {
+                            line,
+                            message: format!("Non-finite coordinate '{}'", s),
+                        });
+                    }
+                }
+                corners.push(p);
+            }
+            Some("endloop") => {
+                if corners.len() != 3 {
+                    return Err(StlError::Parse {
+                        line,
+                        message: format!("Expected 3 vertices but got {}", corners.len()),
+                    });
+                }
+                triangles.push([corners[0], corners[1], corners[2]]);
+                corners.clear();
+            }
+            // The facet normals are recomputed from the geometry
+            _ => {}
+        }
+    }
+
+    Ok(triangles)
+}
+
+/// Parses the binary or ASCII STL file and inserts its triangles into the mesh.
+///
+/// Positions closer than `tolerance` are welded into a single vertex.
+/// Triangles that collapse when welding are skipped.
+pub(crate) fn import_stl<T: MeshType3D>(
+    mesh: &mut T::Mesh,
+    stl: &[u8],
+    tolerance: T::S,
+) -> Result<Vec<T::F>, StlError>
+where
+    T::EP: DefaultEdgePayload,
+    T::FP: DefaultFacePayload,
+{
+    let triangles = if is_binary(stl) {
+        parse_binary(stl)?
+    } else {
+        parse_ascii(stl)?
+    };
+
+    let (positions, indices) = weld(triangles.iter().flatten().copied(), tolerance.to_f64());
+
+    let s = |x: f64| T::S::from_f64(x);
+    let (_, fs) = mesh.insert_indexed_faces(
+        positions
+            .iter()
+            .map(|p| T::VP::from_pos(T::Vec::from_xyz(s(p[0]), s(p[1]), s(p[2])))),
+        indices
+            .chunks_exact(3)
+            .filter(|t| t[0] != t[1] && t[1] != t[2] && t[2] != t[0])
+            .map(|t| (t.to_vec(), Default::default())),
+    )?;
+
+    Ok(fs)
+}
+
+#[cfg(test)]
+#[cfg(feature = "nalgebra")]
+mod tests {
+    use crate::{extensions::nalgebra::*, extensions::stl::*, prelude::*};
+
+    const TETRAHEDRON: &str = "solid tetrahedron
+facet normal 0 0 -1
+  outer loop
+    vertex 0 0 0
+    vertex 0 1 0
+    vertex 1 0 0
+  endloop
+endfacet
+facet normal 0 -1 0
+  outer loop
+    vertex 0 0 0
+    vertex 1 0 0
+    vertex 0 0 1
+  endloop
+endfacet
+facet normal -1 0 0
+  outer loop
+    vertex 0 0 0
+    vertex 0 0 1
+    vertex 0 1 0.0000001
+  endloop
+endfacet
+facet normal 1 1 1
+  outer loop
+    vertex 1 0 0
+    vertex 0 1 0
+    vertex 0 0 1.0000001
+  endloop
+endfacet
+endsolid tetrahedron
+";
+
+    #[test]
+    fn test_stl_ascii_weld() {
+        let mesh = Mesh3d64::from_stl(TETRAHEDRON.as_bytes(), 1e-5).unwrap();
+        assert!(mesh.check().is_ok());
+        assert!(!mesh.is_open());
+        assert_eq!(mesh.num_vertices(), 4);
+        assert_eq!(mesh.num_faces(), 4);
+
+        // without welding, the slightly perturbed positions are separate vertices
+        assert_eq!(
+            Mesh3d64::from_stl(TETRAHEDRON.as_bytes(), 0.0)
+                .unwrap()
+                .num_vertices(),
+            6
+        );
+    }
+
+    #[test]
+    fn test_stl_errors() {
+        assert!(matches!(
+            Mesh3d64::from_stl(b"solid x\nouter loop\nvertex 0 0 0\nendloop\n", 0.0),
+            Err(StlError::Parse { line: 4, .. })
+        ));
+        assert!(matches!(
+            Mesh3d64::from_stl(b"solid x\nvertex 0 a 0\n", 0.0),
+            Err(StlError::Parse { line: 2, .. })
+        ));
+        assert!(matches!(
+            Mesh3d64::from_stl(b"solid x\nvertex 0 inf 0\n", 0.0),
+            Err(StlError::Parse { line: 2, .. })
+        ));
+        let mut infinite = vec![0u8; 134];
+        infinite[80] = 1;
+        infinite[96..100].copy_from_slice(&f32::INFINITY.to_le_bytes());
+        assert!(matches!(
+            Mesh3d64::from_stl(&infinite, 0.0),
+            Err(StlError::Parse { line: 1, .. })
+        ));
+        let mut truncated = vec![0u8; 84];
+        truncated[80] = 2;
+        assert_eq!(
+            Mesh3d64::from_stl(&truncated, 0.0).map(|_| ()),
+            Err(StlError::Truncated {
+                expected: 184,
+                actual: 84
+            })
+        );
+    }
+}
@@ -0,0 +1,51 @@
+//! This module contains the binary and ASCII STL import and export
+
+use crate::{
+    mesh::{DefaultEdgePayload, DefaultFacePayload, MeshBuilder, MeshType3D},
+    tesselate::TriangulationAlgorithm,
+};
+
+mod export;
+mod import;
+
+pub use export::*;
+pub use import::*;
+
+/// Backend trait for STL import/export.
+pub trait BackendSTL<T: MeshType3D<Mesh = Self>>: MeshBuilder<T> {
+    /// Import a binary or ASCII STL file into the mesh.
+    /// Positions closer than `tolerance` are welded into shared vertices
+    /// so the triangles form a connected surface.
+    /// Returns the ids of the inserted faces.
+    fn import_stl(&mut self, stl: &[u8], tolerance: T::S) -> Result<Vec<T::F>, StlError>
+    where
+        T::EP: DefaultEdgePayload,
+        T::FP: DefaultFacePayload,
+    {
+        import_stl::<T>(self, stl, tolerance)
+    }
+
+    /// Create a new mesh from a binary or ASCII STL file.
+    fn from_stl(stl: &[u8], tolerance: T::S) -> Result<Self, StlError>
+    where
+        T::EP: DefaultEdgePayload,
+        T::FP: DefaultFacePayload,
+    {
+        let mut mesh = Self::default();
+        mesh.import_stl(stl, tolerance)?;
+        Ok(mesh)
+    }
+
+    /// Export the triangulated mesh as an STL file.
+    /// The facet normals are the normals of the original faces.
+    fn to_stl(&self, format: StlFormat) -> Vec<u8> {
+        export_stl::<T>(self, format, TriangulationAlgorithm::default())
+    }
+
+    /// Like `to_stl`, but triangulates the faces using the given algorithm.
+    fn to_stl_ex(&self, format: StlFormat, algorithm: TriangulationAlgorithm) -> Vec<u8> {
+        export_stl::<T>(self, format, algorithm)
+    }
+}
+
+impl<T: MeshType3D> BackendSTL<T> for T::Mesh {}
@@ -0,0 +1,195 @@
+use crate::{
+    math::{Scalar, Vector},
+    mesh::{
+        CurvedEdge, CurvedEdgeType, EdgeBasics, EuclideanMeshType, FaceBasics, HalfEdge,
+        MeshBasics, MeshTypeHalfEdge, VertexBasics,
+    },
+};
+use itertools::Itertools;
+use std::fmt::Write;
+
+/// Returns the curve of the half-edge in its own direction.
+/// Builders might store the curve only on one of the two twins.
+fn directed_curve<T: EuclideanMeshType<2> + MeshTypeHalfEdge>(
+    edge: &T::Edge,
+    mesh: &T::Mesh,
+) -> CurvedEdgeType<2, T>
+where
+    T::Edge: CurvedEdge<2, T>,
+{
+    match edge.curve_type() {
+        CurvedEdgeType::Linear => match edge.twin(mesh).curve_type() {
+            CurvedEdgeType::CubicBezier(c1, c2) => CurvedEdgeType::CubicBezier(c2, c1),
+            curve => curve,
+        },
+        curve => curve,
+    }
+}
+
+/// Writes the closed loop of half-edges as a closed subpath.
+fn subpath<T: EuclideanMeshType<2> + MeshTypeHalfEdge>(
+    mesh: &T::Mesh,
+    edges: impl Iterator<Item = T::Edge>,
+) -> String
+where
+    T::Edge: CurvedEdge<2, T>,
+{
+    // `import_svg` creates the face on the opposite side of the path, so the path
+    // runs along the twins in reverse order to preserve the orientation of the face.
+    let mut edges: Vec<T::Edge> = edges.map(|e| e.twin(mesh)).collect();
+    edges.reverse();
+    // start at the oldest vertex to keep the order of the vertices when importing
+    if let Some(start) = edges.iter().position_min_by_key(|e| e.origin_id()) {
+        edges.rotate_left(start);
+    }
+
+    let mut d = String::new();
+    if let Some(first) = edges.first() {
+        let p = first.origin(mesh).pos();
+        write!(d, "M{} {}", p.x(), p.y()).unwrap();
+    }
+    for e in edges.iter() {
+        let p = e.target(mesh).pos();
+        match directed_curve::<T>(e, mesh) {
+            CurvedEdgeType::Linear => write!(d, " L{} {}", p.x(), p.y()),
+            CurvedEdgeType::QuadraticBezier(c) => {
+                write!(d, " Q{} {} {} {}", c.x(), c.y(), p.x(), p.y())
+            }
+            CurvedEdgeType::CubicBezier(c1, c2) => write!(
+                d,
+                " C{} {} {} {} {} {}",
+                c1.x(),
+                c1.y(),
+                c2.x(),
+                c2.y(),
+                p.x(),
+                p.y()
+            ),
+        }
+        .unwrap();
+    }
+    d.push_str(" Z");
+    d
+}
+
+/// Writes each face of the mesh as a closed `<path>` of an SVG document with one subpath per hole.
+/// Curved edges are written as quadratic (`Q`) or cubic (`C`) bezier commands.
+pub(crate) fn export_svg<T: EuclideanMeshType<2> + MeshTypeHalfEdge>(mesh: &T::Mesh) -> String
+where
+    T::Edge: CurvedEdge<2, T>,
+{
+    // the bounding box of the vertices and control points
+    let mut min = (T::S::INFINITY, T::S::INFINITY);
+    let mut max = (T::S::NEG_INFINITY, T::S::NEG_INFINITY);
+    let mut extend = |p: T::Vec| {
+        min = (min.0.min(p.x()), min.1.min(p.y()));
+        max = (max.0.max(p.x()), max.1.max(p.y()));
+    };
+    mesh.vertices().for_each(|v| extend(v.pos()));
+    for e in mesh.edges() {
+        match e.curve_type() {
+            CurvedEdgeType::Linear => {}
+            CurvedEdgeType::QuadraticBezier(c) => extend(c),
+            CurvedEdgeType::CubicBezier(c1, c2) => {
+                extend(c1);
+                extend(c2);
+            }
+        }
+    }
+    if mesh.num_vertices() == 0 {
+        min = (T::S::ZERO, T::S::ZERO);
+        max = min;
+    }
+
+    let mut svg = String::new();
+    writeln!(
+        svg,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"{} {} {} {}\">",
+        min.0,
+        min.1,
+        max.0 - min.0,
+        max.1 - min.1
+    )
+    .unwrap();
+
+    for f in mesh.faces() {
+        // each hole is an additional subpath that runs against the outer boundary
+        let mut d = subpath::<T>(mesh, f.edges(mesh));
+        for hole in 0..f.num_holes() {
+            d.push(' ');
+            d.push_str(&subpath::<T>(mesh, f.hole_edges(mesh, hole)));
+        }
+        writeln!(svg, "<path d=\"{}\"/>", d).unwrap();
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
+
+#[cfg(test)]
+#[cfg(feature = "nalgebra")]
+mod tests {
+    use crate::{
+        extensions::nalgebra::*, extensions::svg::*, prelude::*, test_util::square_with_hole_in,
+    };
+
+    #[test]
+    fn test_svg_round_trip() {
+        let svg = "<path d='M0 0 L10 0 Q15 5 10 10 C7 12 3 8 0 10 Z'/>";
+        let mesh: Mesh2d64Curved = BackendSVG::<MeshType2d64PNUCurved>::from_svg(svg);
+        assert_eq!(mesh.num_faces(), 1);
+        assert!(mesh.has_curved_edges());
+
+        let exported = BackendSVG::<MeshType2d64PNUCurved>::to_svg(&mesh);
+        assert!(exported.contains(" Q15 5 10 10"));
+        assert!(exported.contains(" C7 12 3 8 0 10"));
+
+        let reimported: Mesh2d64Curved = BackendSVG::<MeshType2d64PNUCurved>::from_svg(&exported);
+        assert!(reimported.check().is_ok());
+        assert!(reimported
+            .is_trivially_isomorphic_pos_curved(&mesh, 1e-6)
+            .eq());
+    }
+
+    #[test]
+    fn test_svg_export_hole() {
+        let (mesh, _, _) = square_with_hole_in::<2, MeshType2d64PNUCurved>();
+        let svg = BackendSVG::<MeshType2d64PNUCurved>::to_svg(&mesh);
+        let paths: Vec<&str> = svg.lines().filter(|l| l.starts_with("<path")).collect();
+        assert_eq!(paths.len(), 1);
+
+        // the outer boundary and the hole are closed subpaths of the same path
+        // and wind in opposite directions, so the hole stays empty when filled
+        let d = paths[0]
+            .trim_start_matches("<path d=\"")
+            .trim_end_matches("\"/>");
+        let areas: Vec<f64> = d
+            .split_terminator('Z')
+            .map(|subpath| {
+                let coords: Vec<f64> = subpath
+                    .split(|c: char| c.is_whitespace() || c == 'M' || c == 'L')
+                    .filter(|s| !s.is_empty())
+                    .map(|s| s.parse().unwrap())
+                    .collect();
+                let ps: Vec<(f64, f64)> = coords.chunks(2).map(|c| (c[0], c[1])).collect();
+                (0..ps.len() - 1)
+                    .map(|i| ps[i].0 * ps[i + 1].1 - ps[i + 1].0 * ps[i].1)
+                    .sum::<f64>()
+                    / 2.0
+            })
+            .collect();
+        assert_eq!(areas.len(), 2);
+        assert!((areas[0] - 16.0).abs() < 1e-12);
+        assert!((areas[1] + 4.0).abs() < 1e-12);
+
+        // the import turns the inner subpath back into a hole
+        let reimported: Mesh2d64Curved = BackendSVG::<MeshType2d64PNUCurved>::from_svg(&svg);
+        assert!(reimported.check().is_ok());
+        assert_eq!(reimported.num_faces(), 1);
+        let face = reimported.faces().next().unwrap();
+        assert_eq!(face.num_holes(), 1);
+        assert!(face
+            .hole_vertices(&reimported, 0)
+            .all(|v| v.pos().x().abs() == 1.0 && v.pos().y().abs() == 1.0));
+    }
+}
@@ -4,6 +4,7 @@ use crate::mesh::{
     CurvedEdge, DefaultEdgePayload, DefaultFacePayload, EuclideanMeshType, MeshTypeHalfEdge,
 };
 
+mod export;
 mod svg;
 
 /// Backend trait for SVG import/export.
@@ -33,6 +34,15 @@ where
         mesh.import_svg(svg);
         mesh
     }
+
+    /// Export the mesh as an SVG string with one closed path per face.
+    /// Curved edges are written as bezier commands instead of being flattened.
+    fn to_svg(&self) -> String
+    where
+        T: MeshTypeHalfEdge,
+    {
+        export::export_svg::<T>(self)
+    }
 }
 
 impl<T: EuclideanMeshType<2>> BackendSVG<T> for T::Mesh
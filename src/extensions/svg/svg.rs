@@ -1,8 +1,8 @@
 use crate::{
     math::Vector,
     mesh::{
-        CurvedEdge, DefaultEdgePayload, DefaultFacePayload, EuclideanMeshType, MeshTypeHalfEdge,
-        PathBuilder,
+        nest_holes, CurvedEdge, DefaultEdgePayload, DefaultFacePayload, EuclideanMeshType,
+        MeshTypeHalfEdge, PathBuilder,
     },
 };
 
@@ -47,15 +47,36 @@ where
     // let po = path.paint_order();
     // let transform = path.abs_transform();
 
+    // every subpath becomes a face; the ones nested in another subpath become its holes
+    let mut faces = Vec::new();
+    let mut segments = path.data().segments().peekable();
+    while segments.peek().is_some() {
+        faces.push(import_subpath::<T>(mesh, &mut segments));
+    }
+    nest_holes::<2, T>(mesh, &faces);
+}
+
+/// Imports the segments up to the next `MoveTo` as a closed face.
+fn import_subpath<T: EuclideanMeshType<2> + MeshTypeHalfEdge>(
+    mesh: &mut T::Mesh,
+    segments: &mut std::iter::Peekable<usvg::tiny_skia_path::PathSegmentsIter>,
+) -> T::F
+where
+    T::Edge: CurvedEdge<2, T>,
+    T::EP: DefaultEdgePayload,
+    T::FP: DefaultFacePayload,
+{
     let v = |p: usvg::tiny_skia_path::Point| T::Vec::from_xy(T::S::from(p.x), T::S::from(p.y));
 
     let mut pb = PathBuilder::<T, T::Trans>::new(mesh);
+    let mut face = None;
 
     let mut is_first = true;
-    for s in path.data().segments() {
+    while let Some(s) =
+        segments.next_if(|s| is_first || !matches!(s, usvg::tiny_skia_path::PathSegment::MoveTo(_)))
+    {
         match s {
             usvg::tiny_skia_path::PathSegment::MoveTo(p) => {
-                assert!(is_first);
                 pb.move_to_new(v(p));
                 //pb = PathBuilder::<T>::start(mesh, v(p));
             }
@@ -73,15 +94,13 @@ where
                 pb.cubic_to(v(c1), v(c2), end);
             }
             usvg::tiny_skia_path::PathSegment::Close => {
-                pb.close(Default::default());
+                face = Some(pb.close(Default::default()));
             }
         }
         is_first = false;
     }
 
-    if !pb.has_face() {
-        pb.close(Default::default());
-    }
+    face.unwrap_or_else(|| pb.close(Default::default()))
 }
 
 pub(crate) fn import_svg<T: EuclideanMeshType<2> + MeshTypeHalfEdge>(mesh: &mut T::Mesh, svg: &str)
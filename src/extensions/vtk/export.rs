@@ -0,0 +1,228 @@
+use crate::{
+    extensions::nalgebra::MeshNd64,
+    math::HasNormal,
+    mesh::{EdgeBasics, FaceBasics, HalfEdge, MeshBasics, ModellingError, VertexBasics},
+};
+use std::{collections::HashMap, fmt::Write};
+
+fn write_scalars(vtk: &mut String, name: &str, ty: &str, values: impl Iterator<Item = String>) {
+    writeln!(vtk, "SCALARS {} {} 1\nLOOKUP_TABLE default", name, ty).unwrap();
+    for value in values {
+        writeln!(vtk, "{}", value).unwrap();
+    }
+}
+
+impl<const D: usize> MeshNd64<D> {
+    /// Export the mesh as an ASCII legacy VTK polydata file.
+    ///
+    /// The first three coordinates of each vertex are written as the point and missing
+    /// coordinates are set to zero. The remaining coordinates (`coord_3`, `coord_4`, ...),
+    /// the vertex degrees (`degree`), and the normal components (`normal_0`, `normal_1`, ...)
+    /// are written as point data scalars. Faces are written as polygons and edges
+    /// without any adjacent face as lines.
+    ///
+    /// Panics if a face has holes since VTK polygons can't represent them.
+    pub fn to_vtk(&self, title: &str) -> String {
+        self.to_vtk_with(title, &[])
+    }
+
+    /// Like `to_vtk`, but fails instead of panicking if a face has holes.
+    pub fn try_to_vtk(&self, title: &str) -> Result<String, ModellingError> {
+        self.try_to_vtk_with(title, &[])
+    }
+
+    /// Like `to_vtk`, but also writes the given named point data scalars,
+    /// e.g., the results of the network science functions.
+    /// Each list must contain one value per vertex in the order of `vertices()`.
+    pub fn to_vtk_with(&self, title: &str, scalars: &[(&str, Vec<f64>)]) -> String {
+        self.try_to_vtk_with(title, scalars)
+            .unwrap_or_else(|e| panic!("Failed to export the mesh as VTK: {}", e))
+    }
+
+    /// Like `to_vtk_with`, but fails instead of panicking if a face has holes
+    /// or a list of scalars doesn't have one value per vertex.
+    pub fn try_to_vtk_with(
+        &self,
+        title: &str,
+        scalars: &[(&str, Vec<f64>)],
+    ) -> Result<String, ModellingError> {
+        let n = self.num_vertices();
+        if let Some((name, values)) = scalars.iter().find(|(_, values)| values.len() != n) {
+            return Err(ModellingError::InvalidArgument(format!(
+                "The scalars '{}' have {} values but the mesh has {} vertices",
+                name,
+                values.len(),
+                n
+            )));
+        }
+        if let Some(f) = self.faces().find(|f| f.has_holes()) {
+            return Err(ModellingError::InvalidTopology(format!(
+                "Face {} has holes, which can't be written as a VTK polygon",
+                f.id()
+            )));
+        }
+
+        let mut vtk = String::new();
+        // the title is limited to a single line
+        let title = title.lines().next().unwrap_or("");
+        writeln!(
+            vtk,
+            "# vtk DataFile Version 3.0\n{}\nASCII\nDATASET POLYDATA",
+            title
+        )
+        .unwrap();
+
+        // vertex ids might be sparse
+        let mut id_map = HashMap::new();
+        writeln!(vtk, "POINTS {} double", n).unwrap();
+        for (i, v) in self.vertices().enumerate() {
+            id_map.insert(v.id(), i);
+            let p = v.pos();
+            let coord = |d: usize| if d < D { p[d] } else { 0.0 };
+            writeln!(vtk, "{} {} {}", coord(0), coord(1), coord(2)).unwrap();
+        }
+
+        let polygons: Vec<Vec<usize>> = self
+            .faces()
+            .map(|f| f.vertex_ids(self).map(|v| id_map[&v]).collect())
+            .collect();
+        if !polygons.is_empty() {
+            let size: usize = polygons.iter().map(|p| p.len() + 1).sum();
+            writeln!(vtk, "POLYGONS {} {}", polygons.len(), size).unwrap();
+            for p in polygons {
+                write!(vtk, "{}", p.len()).unwrap();
+                for i in p {
+                    write!(vtk, " {}", i).unwrap();
+                }
+                vtk.push('\n');
+            }
+        }
+
+        let lines: Vec<(usize, usize)> = self
+            .edges()
+            .filter(|e| {
+                e.id() < e.twin_id() && e.is_boundary_self() && e.twin(self).is_boundary_self()
+            })
+            .map(|e| (id_map[&e.origin_id()], id_map[&e.target_id(self)]))
+            .collect();
+        if !lines.is_empty() {
+            writeln!(vtk, "LINES {} {}", lines.len(), lines.len() * 3).unwrap();
+            for (a, b) in lines {
+                writeln!(vtk, "2 {} {}", a, b).unwrap();
+            }
+        }
+
+        writeln!(vtk, "POINT_DATA {}", n).unwrap();
+        for d in 3..D {
+            write_scalars(
+                &mut vtk,
+                &format!("coord_{}", d),
+                "double",
+                self.vertices().map(|v| v.pos()[d].to_string()),
+            );
+        }
+        write_scalars(
+            &mut vtk,
+            "degree",
+            "int",
+            self.vertices().map(|v| v.degree(self).to_string()),
+        );
+        for d in 0..D {
+            write_scalars(
+                &mut vtk,
+                &format!("normal_{}", d),
+                "double",
+                self.vertices().map(|v| v.payload().normal()[d].to_string()),
+            );
+        }
+        for (name, values) in scalars {
+            // names must not contain whitespace
+            let name = name.replace(char::is_whitespace, "_");
+            write_scalars(
+                &mut vtk,
+                &name,
+                "double",
+                values.iter().map(|x| x.to_string()),
+            );
+        }
+
+        Ok(vtk)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{extensions::nalgebra::*, prelude::*};
+
+    #[test]
+    fn test_vtk_4d() {
+        let cube = Mesh3d64::cube(1.0);
+        let mesh = MeshNd64::<4>::import_mesh::<_, _, _, _, MeshType3d64PNU>(
+            &cube,
+            |vp| {
+                let p = vp.pos();
+                VertexPayloadPNU::<f64, 4>::from_pos(VecN::<f64, 4>::new(p.x, p.y, p.z, p.x + p.y))
+            },
+            |_| Default::default(),
+            |_| Default::default(),
+            |_| Default::default(),
+        );
+        let fiedler: Vec<f64> = (0..8).map(|i| i as f64).collect();
+        let vtk = mesh.to_vtk_with("hypercube\nslice", &[("fiedler vector", fiedler)]);
+        let lines: Vec<&str> = vtk.lines().collect();
+        assert_eq!(lines[1], "hypercube");
+        assert_eq!(lines[4], "POINTS 8 double");
+        assert!(vtk.contains("POLYGONS 6 30\n"));
+        assert!(!vtk.contains("LINES"));
+        assert!(vtk.contains("POINT_DATA 8\nSCALARS coord_3 double 1\nLOOKUP_TABLE default\n"));
+        assert!(vtk.contains("SCALARS degree int 1\nLOOKUP_TABLE default\n3\n3\n3\n"));
+        for d in 0..4 {
+            assert!(vtk.contains(&format!("SCALARS normal_{} double 1\n", d)));
+        }
+        assert!(!vtk.contains("normal_4"));
+        assert!(vtk.ends_with(
+            "SCALARS fiedler_vector double 1\nLOOKUP_TABLE default\n0\n1\n2\n3\n4\n5\n6\n7\n"
+        ));
+    }
+
+    #[test]
+    fn test_vtk_wireframe() {
+        let mut mesh = Mesh2d64::new();
+        mesh.insert_polygon([
+            VertexPayloadPNU::from_pos(Vec2::new(0.0, 0.0)),
+            VertexPayloadPNU::from_pos(Vec2::new(1.0, 0.0)),
+            VertexPayloadPNU::from_pos(Vec2::new(0.0, 1.0)),
+        ]);
+        let f = mesh.faces().next().unwrap().id();
+        mesh.remove_face(f);
+        let vtk = mesh.to_vtk("triangle");
+        assert!(vtk.contains("POINTS 3 double\n0 0 0\n1 0 0\n0 1 0\n"));
+        assert!(!vtk.contains("POLYGONS"));
+        assert!(vtk.contains("LINES 3 9\n"));
+        assert!(!vtk.contains("coord_"));
+    }
+
+    #[test]
+    fn test_vtk_hole() {
+        let (mesh, f, _) = crate::test_util::square_with_hole();
+        assert_eq!(
+            mesh.try_to_vtk("hole"),
+            Err(ModellingError::InvalidTopology(format!(
+                "Face {} has holes, which can't be written as a VTK polygon",
+                f
+            )))
+        );
+    }
+
+    #[test]
+    fn test_vtk_scalars_length() {
+        let mesh = Mesh3d64::cube(1.0);
+        let degrees: Vec<f64> = mesh.vertices().map(|v| v.degree(&mesh) as f64).collect();
+        assert_eq!(
+            mesh.try_to_vtk_with("cube", &[("degree", degrees), ("fiedler", vec![0.0; 5])]),
+            Err(ModellingError::InvalidArgument(
+                "The scalars 'fiedler' have 5 values but the mesh has 8 vertices".to_string()
+            ))
+        );
+    }
+}
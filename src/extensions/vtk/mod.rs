@@ -0,0 +1,3 @@
+//! This module contains the legacy VTK export for n-dimensional nalgebra meshes
+
+mod export;
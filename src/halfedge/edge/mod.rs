@@ -16,6 +16,14 @@ use crate::{
 
 /// Half-edge inspired data structure
 #[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(
+        serialize = "T::E: serde::Serialize, T::V: serde::Serialize, T::F: serde::Serialize, T::EP: serde::Serialize",
+        deserialize = "T::E: serde::Deserialize<'de>, T::V: serde::Deserialize<'de>, T::F: serde::Deserialize<'de>, T::EP: serde::Deserialize<'de>"
+    ))
+)]
 pub struct HalfEdgeImpl<T: HalfEdgeImplMeshType> {
     /// the index of the half-edge
     id: T::E,
@@ -12,8 +12,17 @@ use crate::{
 ///
 /// If you want to handle a non-orientable mesh, you have to use double covering.
 ///
-/// Also, if you have inner components, you have to use multiple faces!
-#[derive(Clone, Copy, Hash)]
+/// Inner boundary loops, e.g., the counter of an "O", are stored as holes of the face.
+/// The half-edges of a hole loop belong to the face and run clockwise around the hole.
+#[derive(Clone, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(
+        serialize = "T::F: serde::Serialize, T::E: serde::Serialize, T::FP: serde::Serialize",
+        deserialize = "T::F: serde::Deserialize<'de>, T::E: serde::Deserialize<'de>, T::FP: serde::Deserialize<'de>"
+    ))
+)]
 pub struct HalfEdgeFaceImpl<T: HalfEdgeImplMeshType> {
     /// the index of the face
     id: T::F,
@@ -21,6 +30,9 @@ pub struct HalfEdgeFaceImpl<T: HalfEdgeImplMeshType> {
     /// a half-edge incident to the face (outer component)
     edge: T::E,
 
+    /// a half-edge incident to the face for each hole (inner components)
+    holes: Vec<T::E>,
+
     /// whether the face is curved, i.e., not planar
     curved: bool,
 
@@ -66,7 +78,20 @@ impl<T: HalfEdgeImplMeshType> FaceBasics<T> for HalfEdgeFaceImpl<T> {
     }
 
     fn num_triangles(&self, mesh: &T::Mesh) -> usize {
-        (FaceBasics::num_vertices(self, mesh) - 2) * 3
+        // every hole adds two triangles
+        let n = FaceBasics::num_vertices(self, mesh)
+            + (0..self.holes.len())
+                .map(|i| self.hole_edges(mesh, i).count() + 2)
+                .sum::<usize>();
+        (n - 2) * 3
+    }
+
+    fn num_holes(&self) -> usize {
+        self.holes.len()
+    }
+
+    fn hole_edge_ids(&self) -> impl Iterator<Item = T::E> + '_ {
+        self.holes.iter().copied()
     }
 
     fn payload(&self) -> &T::FP {
@@ -90,6 +115,12 @@ impl<T: HalfEdgeImplMeshType> FaceBasics<T> for HalfEdgeFaceImpl<T> {
     fn edges<'a>(&'a self, mesh: &'a T::Mesh) -> ForwardEdgeIterator<'a, T> {
         ForwardEdgeIterator::new(self.edge(mesh), mesh)
     }
+
+    #[inline(always)]
+    #[allow(refining_impl_trait)]
+    fn hole_edges<'a>(&'a self, mesh: &'a T::Mesh, hole: usize) -> ForwardEdgeIterator<'a, T> {
+        ForwardEdgeIterator::new(mesh.edge(self.holes[hole]).clone(), mesh)
+    }
 }
 
 impl<T: HalfEdgeImplMeshType> Face for HalfEdgeFaceImpl<T> {
@@ -133,10 +164,24 @@ impl<T: HalfEdgeImplMeshType> HalfEdgeFaceImpl<T> {
         Self {
             id: IndexType::max(),
             edge,
+            holes: Vec::new(),
             curved,
             payload,
         }
     }
+
+    /// Adds a half-edge of an inner boundary loop as a hole of the face.
+    /// Doesn't update the half-edges; use `MeshHalfEdgeBuilder::insert_hole` instead.
+    pub fn add_hole(&mut self, edge: T::E) {
+        assert!(edge != IndexType::max());
+        self.holes.push(edge);
+    }
+
+    /// Removes all holes from the face and returns their half-edges.
+    /// Doesn't update the half-edges.
+    pub fn take_holes(&mut self) -> Vec<T::E> {
+        std::mem::take(&mut self.holes)
+    }
 }
 
 impl<T: HalfEdgeImplMeshType> std::fmt::Debug for HalfEdgeFaceImpl<T> {
@@ -147,7 +192,11 @@ impl<T: HalfEdgeImplMeshType> std::fmt::Debug for HalfEdgeFaceImpl<T> {
             self.id().index(),
             self.edge.index(),
             w = 2,
-        )
+        )?;
+        for hole in self.holes.iter() {
+            write!(f, " hole {}", hole.index())?;
+        }
+        Ok(())
     }
 }
 
@@ -171,6 +220,7 @@ impl<T: HalfEdgeImplMeshType> Deletable<T::F> for HalfEdgeFaceImpl<T> {
         Self {
             id: IndexType::max(),
             edge: IndexType::max(),
+            holes: Vec::new(),
             curved: false,
             payload: T::FP::allocate(),
         }
@@ -186,6 +236,7 @@ where
         Self {
             id: IndexType::max(),
             edge: IndexType::max(),
+            holes: Vec::new(),
             curved: false,
             payload: T::FP::default(),
         }
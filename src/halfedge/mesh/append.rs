@@ -0,0 +1,217 @@
+use super::HalfEdgeMeshImpl;
+use crate::{
+    halfedge::{HalfEdgeFaceImpl, HalfEdgeImplMeshType, HalfEdgeVertexImpl},
+    math::{IndexType, Transformable},
+    mesh::{
+        EdgeBasics, EuclideanMeshType, FaceBasics, HalfEdge, MeshBasics, MeshHalfEdgeBuilder,
+        MeshType, VertexBasics,
+    },
+};
+use std::collections::HashMap;
+
+fn remap<I: IndexType, J: IndexType>(map: &HashMap<I, J>, i: I) -> J {
+    if i == IndexType::max() {
+        IndexType::max()
+    } else {
+        map[&i]
+    }
+}
+
+impl<T: HalfEdgeImplMeshType> HalfEdgeMeshImpl<T> {
+    /// Inserts a copy of another mesh (possibly of a different mesh type) as new,
+    /// disconnected components. The payloads are converted using the given closures.
+    ///
+    /// Returns the other-to-self index maps of the inserted vertices, halfedges, and faces.
+    /// The payload of the mesh itself is left untouched.
+    pub fn append_mesh<FV, FE, FF, T2: MeshType>(
+        &mut self,
+        mesh: &T2::Mesh,
+        fv: FV,
+        fe: FE,
+        ff: FF,
+    ) -> (
+        HashMap<T2::V, T::V>,
+        HashMap<T2::E, T::E>,
+        HashMap<T2::F, T::F>,
+    )
+    where
+        FV: Fn(&T2::VP) -> T::VP,
+        FE: Fn(&T2::EP) -> T::EP,
+        FF: Fn(&T2::FP) -> T::FP,
+        T2::Edge: HalfEdge<T2>,
+    {
+        let mut vs = HashMap::new();
+        for vertex in MeshBasics::vertices(mesh) {
+            vs.insert(vertex.id(), self.allocate_vertex());
+        }
+        let mut fs = HashMap::new();
+        for face in MeshBasics::faces(mesh) {
+            fs.insert(face.id(), self.allocate_face());
+        }
+        let mut es = HashMap::new();
+        for edge in MeshBasics::edges(mesh) {
+            es.insert(edge.id(), self.allocate_halfedge());
+        }
+
+        for vertex in MeshBasics::vertices(mesh) {
+            self.vertices.set(
+                vs[&vertex.id()],
+                HalfEdgeVertexImpl::new(
+                    remap(&es, VertexBasics::edge_id(vertex, mesh)),
+                    fv(vertex.payload()),
+                ),
+            );
+        }
+
+        for face in MeshBasics::faces(mesh) {
+            let mut f = HalfEdgeFaceImpl::new(
+                es[&FaceBasics::edge_id(face)],
+                face.may_be_curved(),
+                ff(face.payload()),
+            );
+            for hole in face.hole_edge_ids() {
+                f.add_hole(es[&hole]);
+            }
+            self.faces.set(fs[&face.id()], f);
+        }
+
+        for edge in MeshBasics::edges(mesh) {
+            self.insert_halfedge_no_update_no_check(
+                es[&edge.id()],
+                vs[&edge.origin_id()],
+                remap(&fs, edge.face_id()),
+                es[&edge.prev_id()],
+                es[&edge.twin_id()],
+                es[&edge.next_id()],
+                fe(edge.payload()),
+            );
+        }
+
+        (vs, es, fs)
+    }
+
+    /// Creates a new mesh from a mesh of a different mesh type by converting the payloads
+    /// using the given closures, e.g., to change the vertex type or the dimension.
+    pub fn import_mesh<FE, FV, FF, FM, T2: MeshType>(
+        mesh: &T2::Mesh,
+        fv: FV,
+        fe: FE,
+        ff: FF,
+        fm: FM,
+    ) -> Self
+    where
+        FE: Fn(&T2::EP) -> T::EP,
+        FV: Fn(&T2::VP) -> T::VP,
+        FF: Fn(&T2::FP) -> T::FP,
+        FM: Fn(&T2::MP) -> T::MP,
+        T2::Edge: HalfEdge<T2>,
+    {
+        let mut res = Self::default();
+        res.append_mesh::<FV, FE, FF, T2>(mesh, fv, fe, ff);
+        res.set_payload(fm(MeshBasics::payload(mesh)));
+        res
+    }
+
+    /// Inserts a transformed copy of `other` as new, disconnected components
+    /// including its attribute layers.
+    ///
+    /// Returns the other-to-self index maps of the inserted vertices, halfedges, and faces.
+    pub fn append<const D: usize>(
+        &mut self,
+        other: &Self,
+        transform: &T::Trans,
+    ) -> (
+        HashMap<T::V, T::V>,
+        HashMap<T::E, T::E>,
+        HashMap<T::F, T::F>,
+    )
+    where
+        T: EuclideanMeshType<D>,
+        T::VP: Transformable<D, Rot = T::Rot, Vec = T::Vec, Trans = T::Trans, S = T::S>,
+        T::EP: Transformable<D, Rot = T::Rot, Vec = T::Vec, Trans = T::Trans, S = T::S>,
+        T::FP: Transformable<D, Rot = T::Rot, Vec = T::Vec, Trans = T::Trans, S = T::S>,
+    {
+        let (vs, es, fs) = self.append_mesh::<_, _, _, T>(
+            other,
+            |vp| vp.transformed(transform),
+            |ep| ep.transformed(transform),
+            |fp| fp.transformed(transform),
+        );
+        self.vertex_attributes.append(&other.vertex_attributes, &vs);
+        self.halfedge_attributes
+            .append(&other.halfedge_attributes, &es);
+        self.face_attributes.append(&other.face_attributes, &fs);
+        (vs, es, fs)
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "nalgebra")]
+mod tests {
+    use crate::{extensions::nalgebra::*, prelude::*};
+
+    #[test]
+    fn test_append() {
+        let mut mesh = Mesh3d64::cube(1.0);
+        let mut other = Mesh3d64::cube(1.0);
+        other.vertex_attributes_mut().add::<f64>("weight");
+        let v = other.vertex_ids().next().unwrap();
+        other.vertex_attributes_mut().set("weight", v, 2.0);
+        let isolated = other.add_vertex(VertexPayloadPNU::from_pos(Vec3::new(9.0, 9.0, 9.0)));
+
+        let t = NdAffine::from_translation(Vec3::new(3.0, 0.0, 0.0));
+        let (vs, es, fs) = mesh.append(&other, &t);
+        assert!(mesh.check().is_ok());
+        assert_eq!((vs.len(), es.len(), fs.len()), (9, 24, 6));
+        assert_eq!(mesh.num_vertices(), 17);
+        assert_eq!(mesh.num_faces(), 12);
+        for w in other.vertices() {
+            assert!(mesh
+                .vertex(vs[&w.id()])
+                .pos()
+                .is_about(&(w.pos() + Vec3::new(3.0, 0.0, 0.0)), 1e-10));
+        }
+        assert_eq!(mesh.vertex(vs[&isolated]).edge_id(&mesh), usize::MAX);
+        assert_eq!(
+            mesh.vertex_attributes().get::<f64>("weight", vs[&v]),
+            Some(&2.0)
+        );
+        assert_eq!(mesh.vertex_attributes().get::<f64>("weight", v), Some(&0.0));
+        for f in other.faces() {
+            assert_eq!(
+                mesh.face(fs[&f.id()]).vertex_ids(&mesh).collect::<Vec<_>>(),
+                f.vertex_ids(&other).map(|w| vs[&w]).collect::<Vec<_>>()
+            );
+        }
+    }
+
+    #[test]
+    fn test_append_mesh_conversion() {
+        let cube = Mesh3d64::cube(1.0);
+        let mut mesh = Mesh3d64Crease::regular_tetrahedron(1.0);
+        let (vs, es, fs) = mesh.append_mesh::<_, _, _, MeshType3d64PNU>(
+            &cube,
+            |vp| *vp,
+            |_| CreaseEdgePayload::new(2.0),
+            |_| SmoothingGroupFacePayload::new(3),
+        );
+        assert!(mesh.check().is_ok());
+        assert_eq!(mesh.num_vertices(), 12);
+        assert_eq!(mesh.num_faces(), 10);
+        assert!(es.values().all(|&e| mesh.edge(e).payload().crease() == 2.0));
+        assert!(fs
+            .values()
+            .all(|&f| mesh.face(f).payload().smoothing_group() == 3));
+        assert!(vs.values().all(|&v| v >= 4));
+
+        let converted = Mesh3d64Crease::import_mesh::<_, _, _, _, MeshType3d64PNU>(
+            &cube,
+            |vp| *vp,
+            |_| Default::default(),
+            |_| Default::default(),
+            |_| Default::default(),
+        );
+        assert!(converted.check().is_ok());
+        assert_eq!(converted.num_vertices(), 8);
+    }
+}
@@ -0,0 +1,159 @@
+use super::HalfEdgeMeshImpl;
+use crate::{halfedge::HalfEdgeImplMeshType, util::AttributeStore};
+
+impl<T: HalfEdgeImplMeshType> HalfEdgeMeshImpl<T> {
+    /// Returns the named attribute layers of the vertices.
+    pub fn vertex_attributes(&self) -> &AttributeStore<T::V> {
+        &self.vertex_attributes
+    }
+
+    /// Returns the named attribute layers of the vertices to add, remove, or modify layers.
+    pub fn vertex_attributes_mut(&mut self) -> &mut AttributeStore<T::V> {
+        self.vertex_attributes.reserve(self.vertices.capacity());
+        &mut self.vertex_attributes
+    }
+
+    /// Returns the named attribute layers of the halfedges.
+    pub fn halfedge_attributes(&self) -> &AttributeStore<T::E> {
+        &self.halfedge_attributes
+    }
+
+    /// Returns the named attribute layers of the halfedges to add, remove, or modify layers.
+    pub fn halfedge_attributes_mut(&mut self) -> &mut AttributeStore<T::E> {
+        self.halfedge_attributes.reserve(self.halfedges.capacity());
+        &mut self.halfedge_attributes
+    }
+
+    /// Returns the named attribute layers of the faces.
+    pub fn face_attributes(&self) -> &AttributeStore<T::F> {
+        &self.face_attributes
+    }
+
+    /// Returns the named attribute layers of the faces to add, remove, or modify layers.
+    pub fn face_attributes_mut(&mut self) -> &mut AttributeStore<T::F> {
+        self.face_attributes.reserve(self.faces.capacity());
+        &mut self.face_attributes
+    }
+
+    /// Allocates a vertex slot and resets its attributes.
+    pub(crate) fn allocate_vertex(&mut self) -> T::V {
+        let v = self.vertices.allocate();
+        self.vertex_attributes.insert(v);
+        v
+    }
+
+    /// Allocates a halfedge slot and resets its attributes.
+    pub(crate) fn allocate_halfedge(&mut self) -> T::E {
+        let e = self.halfedges.allocate();
+        self.halfedge_attributes.insert(e);
+        e
+    }
+
+    /// Allocates a face slot and resets its attributes.
+    pub(crate) fn allocate_face(&mut self) -> T::F {
+        let f = self.faces.allocate();
+        self.face_attributes.insert(f);
+        f
+    }
+
+    /// Inserts the face and resets its attributes.
+    pub(crate) fn push_face(&mut self, face: T::Face) -> T::F {
+        let f = self.faces.push(face);
+        self.face_attributes.insert(f);
+        f
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "nalgebra")]
+mod tests {
+    use crate::{extensions::nalgebra::*, prelude::*};
+
+    #[test]
+    fn test_attribute_layers() {
+        let mut mesh = Mesh3d64::cube(1.0);
+        assert!(mesh.vertex_attributes_mut().add::<f64>("weight"));
+        assert!(!mesh.vertex_attributes_mut().add::<u32>("weight"));
+        assert!(mesh.halfedge_attributes_mut().add::<bool>("seam"));
+        assert!(mesh.face_attributes_mut().add::<String>("material"));
+
+        let e = mesh.edge_ids().next().unwrap();
+        let (a, b) = (mesh.edge(e).origin_id(), mesh.edge(e).target_id(&mesh));
+        mesh.vertex_attributes_mut().set("weight", a, 1.0);
+        mesh.vertex_attributes_mut().set("weight", b, 3.0);
+        mesh.halfedge_attributes_mut().set("seam", e, true);
+        assert_eq!(mesh.vertex_attributes().get::<f64>("weight", a), Some(&1.0));
+        assert_eq!(mesh.vertex_attributes().get::<u32>("weight", a), None);
+        assert_eq!(mesh.vertex_attributes().get::<f64>("mass", a), None);
+
+        // the new vertex is interpolated and the new halfedge inherits the values
+        let twin = mesh.edge(e).twin_id();
+        let new = mesh.subdivide_unsafe(
+            e,
+            VertexPayloadPNU::from_pos(Vec3::zeros()),
+            Default::default(),
+        );
+        mesh.subdivide_unsafe_try_fixup(twin, Default::default())
+            .unwrap();
+        assert!(mesh.check().is_ok());
+        let v = mesh.edge(new).origin_id();
+        assert_eq!(mesh.vertex_attributes().get::<f64>("weight", v), Some(&2.0));
+        assert_eq!(
+            mesh.halfedge_attributes().get::<bool>("seam", new),
+            Some(&true)
+        );
+        assert_eq!(
+            mesh.halfedge_attributes().get::<bool>("seam", twin),
+            Some(&false)
+        );
+
+        // deleted faces are reset and reused slots start with the default value
+        let f = mesh.edge(e).face_id();
+        mesh.face_attributes_mut()
+            .set("material", f, "wood".to_string());
+        let cloned = mesh.clone();
+        mesh.remove_face(f);
+        assert_eq!(mesh.close_hole_default(e), f);
+        assert_eq!(
+            mesh.face_attributes().get::<String>("material", f),
+            Some(&String::new())
+        );
+        assert_eq!(
+            cloned.face_attributes().get::<String>("material", f),
+            Some(&"wood".to_string())
+        );
+
+        // compaction moves the values along with the elements
+        let mut mesh = cloned;
+        let f2 = mesh.face_ids().find(|&g| g != f).unwrap();
+        mesh.face_attributes_mut()
+            .set("material", f2, "stone".to_string());
+        let g = mesh.face_ids().find(|&g| g != f && g != f2).unwrap();
+        mesh.remove_face(g);
+        let (vs, _, fs) = mesh.compact();
+        assert_eq!(
+            mesh.face_attributes().get::<String>("material", fs[&f]),
+            Some(&"wood".to_string())
+        );
+        assert_eq!(
+            mesh.face_attributes().get::<String>("material", fs[&f2]),
+            Some(&"stone".to_string())
+        );
+        assert_eq!(
+            mesh.vertex_attributes().get::<f64>("weight", vs[&v]),
+            Some(&2.0)
+        );
+        assert_eq!(
+            mesh.face_attributes()
+                .values::<String>("material")
+                .unwrap()
+                .len(),
+            mesh.num_faces()
+        );
+
+        mesh.clear();
+        assert!(mesh.vertex_attributes().contains("weight"));
+        let v = mesh.add_vertex(VertexPayloadPNU::from_pos(Vec3::zeros()));
+        assert_eq!(mesh.vertex_attributes().get::<f64>("weight", v), Some(&0.0));
+    }
+}
@@ -28,6 +28,18 @@ impl<T: HalfEdgeImplMeshType> MeshBasics<T> for HalfEdgeMeshImpl<T> {
         f
     }
 
+    fn try_vertex(&self, index: T::V) -> Option<&T::Vertex> {
+        self.vertices.try_get(index)
+    }
+
+    fn try_edge(&self, index: T::E) -> Option<&T::Edge> {
+        self.halfedges.try_get(index)
+    }
+
+    fn try_face(&self, index: T::F) -> Option<&T::Face> {
+        self.faces.try_get(index)
+    }
+
     fn vertex_mut(&mut self, index: T::V) -> &mut T::Vertex {
         self.vertices.get_mut(index)
     }
@@ -64,6 +76,9 @@ impl<T: HalfEdgeImplMeshType> MeshBasics<T> for HalfEdgeMeshImpl<T> {
         self.vertices.clear();
         self.halfedges.clear();
         self.faces.clear();
+        self.vertex_attributes.clear();
+        self.halfedge_attributes.clear();
+        self.face_attributes.clear();
         self
     }
 
@@ -3,9 +3,12 @@ use crate::{
     math::IndexType,
     mesh::{
         DefaultEdgePayload, DefaultFacePayload, EdgeBasics, FaceBasics, HalfEdge, HalfEdgeVertex,
-        MeshBasics, MeshBuilder, MeshHalfEdgeBuilder,
+        IndexedFaceError, MeshBasics, MeshBuilder, MeshHalfEdgeBuilder, ModellingError,
+        VertexBasics,
     },
 };
+use itertools::Itertools;
+use std::collections::{HashMap, HashSet};
 
 /*
 impl<T:HalfEdgeImplMeshType> HalfEdgeMeshImpl<T> {
@@ -51,6 +54,18 @@ impl<T:HalfEdgeImplMeshType> HalfEdgeMeshImpl<T> {
 
 }*/
 
+impl<T: HalfEdgeImplMeshType> HalfEdgeMeshImpl<T> {
+    /// Returns the halfedges of the face including the ones of its holes.
+    fn face_halfedge_ids(&self, f: T::F) -> Vec<T::E> {
+        let face = self.face(f);
+        let mut edge_ids: Vec<_> = face.edges(self).map(|e| e.id()).collect();
+        for hole in 0..face.num_holes() {
+            edge_ids.extend(face.hole_edges(self, hole).map(|e| e.id()));
+        }
+        edge_ids
+    }
+}
+
 impl<T: HalfEdgeImplMeshType> MeshBuilder<T> for HalfEdgeMeshImpl<T> {
     fn add_vertex_via_vertex_default(&mut self, v: T::V, vp: T::VP) -> (T::V, T::E, T::E)
     where
@@ -77,16 +92,43 @@ impl<T: HalfEdgeImplMeshType> MeshBuilder<T> for HalfEdgeMeshImpl<T> {
         // TODO: move the payload out of the face without cloning
         let fp = face.payload().clone();
 
-        let edge_ids: Vec<_> = face.edges(self).map(|e| e.id()).collect();
-        for e in edge_ids {
+        for e in self.face_halfedge_ids(f) {
             self.edge_mut(e).delete_face();
         }
         self.faces.delete_internal(f);
+        self.face_attributes.delete(f);
         fp
     }
 
+    fn try_remove_face(&mut self, f: T::F) -> Result<T::FP, ModellingError> {
+        if self.try_face(f).is_none() {
+            return Err(ModellingError::MissingFace(f.index()));
+        }
+        Ok(self.remove_face(f))
+    }
+
+    fn try_close_hole(&mut self, e: T::E, fp: T::FP, curved: bool) -> Result<T::F, ModellingError> {
+        let edge = self
+            .try_edge(e)
+            .ok_or(ModellingError::MissingEdge(e.index()))?;
+        if !edge.is_boundary_self() {
+            return Err(ModellingError::InvalidTopology(format!(
+                "HalfEdge {} already has face {}",
+                e,
+                edge.face_id()
+            )));
+        }
+        if edge.edges_face(self).count() < 3 {
+            return Err(ModellingError::DegenerateFace(format!(
+                "The boundary of halfedge {} has less than three edges",
+                e
+            )));
+        }
+        Ok(self.close_hole(e, fp, curved))
+    }
+
     fn close_hole(&mut self, e: T::E, fp: T::FP, curved: bool) -> T::F {
-        let f = self.faces.push(HalfEdgeFaceImpl::new(e, curved, fp));
+        let f = self.push_face(HalfEdgeFaceImpl::new(e, curved, fp));
         self.edge(e)
             .clone()
             .edges_face_mut(self)
@@ -126,20 +168,21 @@ impl<T: HalfEdgeImplMeshType> MeshBuilder<T> for HalfEdgeMeshImpl<T> {
         let mut current_twin = self.edge(twin_id).next_id();
         let f1 = self.edge(e).face_id();
         let f2 = self.edge(twin_id).face_id();
-        let mut last_v = self.edge(e).origin_id();
+        let origin = self.edge(e).origin_id();
+        let mut last_v = origin;
         let mut first = true;
         for (ep1, ep2, vp) in ps {
-            let (v, e1, e2) =
-                self.add_vertex_via_edge(current, self.edge(current).twin_id(), vp, ep1, ep2);
+            // the new vertex continues the twin's face, which isn't necessarily the face of `current`'s twin
+            let (v, e1, e2) = self.add_vertex_via_edge(current, current_twin, vp, ep1, ep2);
             current = e1;
             current_twin = e2;
             last_v = v;
             self.edge_mut(current).set_face(f1);
             self.edge_mut(current_twin).set_face(f2);
-            if first {
-                self.vertex_mut(self.edge(e).origin_id()).set_edge(e1);
-                first = false;
+            if first && self.vertex(origin).edge_id(self) == e {
+                self.vertex_mut(origin).set_edge(e1);
             }
+            first = false;
         }
 
         self.edge_mut(current).set_next(e);
@@ -152,11 +195,321 @@ impl<T: HalfEdgeImplMeshType> MeshBuilder<T> for HalfEdgeMeshImpl<T> {
     }
 
     fn add_vertex(&mut self, vp: T::VP) -> T::V {
-        let new = self.vertices.allocate();
+        let new = self.allocate_vertex();
         self.vertices.set(new, T::Vertex::new(IndexType::max(), vp));
         new
     }
 
+    fn insert_indexed_faces(
+        &mut self,
+        vps: impl IntoIterator<Item = T::VP>,
+        faces: impl IntoIterator<Item = (Vec<usize>, T::FP)>,
+    ) -> Result<(Vec<T::V>, Vec<T::F>), IndexedFaceError>
+    where
+        T::EP: DefaultEdgePayload,
+    {
+        let vps = vps.into_iter().collect_vec();
+        let faces = faces.into_iter().collect_vec();
+
+        // Validate everything before touching the mesh. Since every directed edge
+        // may only be used once, the result is guaranteed to be an oriented manifold.
+        let mut inner: HashMap<(usize, usize), usize> = HashMap::new();
+        for (i, (ids, _)) in faces.iter().enumerate() {
+            if let Some(&vertex) = ids.iter().find(|&&v| v >= vps.len()) {
+                return Err(IndexedFaceError::VertexOutOfRange { face: i, vertex });
+            }
+            if ids.len() < 3 || !ids.iter().all_unique() {
+                return Err(IndexedFaceError::DegenerateFace { face: i });
+            }
+            for (&from, &to) in ids.iter().circular_tuple_windows() {
+                if inner.insert((from, to), i).is_some() {
+                    return Err(IndexedFaceError::NonManifoldEdge { face: i, from, to });
+                }
+            }
+        }
+
+        let vs = vps.into_iter().map(|vp| self.add_vertex(vp)).collect_vec();
+        let fs = faces.iter().map(|_| self.allocate_face()).collect_vec();
+
+        // allocate one pair of halfedges per undirected edge
+        let mut halfedges: HashMap<(usize, usize), T::E> = HashMap::new();
+        let mut order = Vec::new();
+        for (ids, _) in faces.iter() {
+            for (&from, &to) in ids.iter().circular_tuple_windows() {
+                if !halfedges.contains_key(&(from, to)) {
+                    halfedges.insert((from, to), self.allocate_halfedge());
+                    halfedges.insert((to, from), self.allocate_halfedge());
+                    order.push((from, to));
+                    order.push((to, from));
+                }
+            }
+        }
+
+        // the previous vertex of each inner halfedge in its face
+        let mut prev_vertex: HashMap<(usize, usize), usize> = HashMap::new();
+        for (ids, _) in faces.iter() {
+            for (&a, &b, &c) in ids.iter().circular_tuple_windows() {
+                prev_vertex.insert((b, c), a);
+            }
+        }
+
+        // The successor of a boundary halfedge is found by rotating around its
+        // target through the adjacent faces until the next boundary halfedge is hit.
+        let boundary_next = |(from, to): (usize, usize)| -> (usize, usize) {
+            let mut e = (to, from);
+            loop {
+                let candidate = (to, prev_vertex[&e]);
+                if !inner.contains_key(&candidate) {
+                    return candidate;
+                }
+                e = candidate;
+            }
+        };
+
+        let mut next: HashMap<(usize, usize), (usize, usize)> = HashMap::new();
+        for (ids, _) in faces.iter() {
+            for (&a, &b, &c) in ids.iter().circular_tuple_windows() {
+                next.insert((a, b), (b, c));
+            }
+        }
+        for &(from, to) in order.iter() {
+            if !inner.contains_key(&(from, to)) {
+                next.insert((from, to), boundary_next((from, to)));
+            }
+        }
+        let mut prev: HashMap<(usize, usize), (usize, usize)> = HashMap::new();
+        for (&e, &n) in next.iter() {
+            prev.insert(n, e);
+        }
+
+        for &(from, to) in order.iter() {
+            let face = inner.get(&(from, to)).map_or(IndexType::max(), |&i| fs[i]);
+            self.insert_halfedge_no_update_no_check(
+                halfedges[&(from, to)],
+                vs[from],
+                face,
+                halfedges[&prev[&(from, to)]],
+                halfedges[&(to, from)],
+                halfedges[&next[&(from, to)]],
+                T::EP::default(),
+            );
+        }
+
+        // prefer boundary edges as the representative of vertices
+        for &(from, to) in order.iter() {
+            let e = halfedges[&(from, to)];
+            let v = self.vertex(vs[from]);
+            if v.edge_id(self) == IndexType::max() || !inner.contains_key(&(from, to)) {
+                self.vertex_mut(vs[from]).set_edge(e);
+            }
+        }
+
+        for ((ids, fp), &f) in faces.into_iter().zip(fs.iter()) {
+            self.faces.set(
+                f,
+                HalfEdgeFaceImpl::new(halfedges[&(ids[0], ids[1])], false, fp),
+            );
+        }
+
+        Ok((vs, fs))
+    }
+
+    fn try_replace_faces(
+        &mut self,
+        remove: impl IntoIterator<Item = T::F>,
+        faces: impl IntoIterator<Item = (Vec<Vec<T::V>>, T::FP)>,
+    ) -> Result<Vec<T::F>, ModellingError>
+    where
+        T::EP: DefaultEdgePayload,
+    {
+        let remove = remove.into_iter().unique().collect_vec();
+        let removed: HashSet<T::F> = remove.iter().copied().collect();
+        let faces = faces.into_iter().collect_vec();
+
+        // Validate everything before touching the mesh.
+        if let Some(&f) = remove.iter().find(|&&f| self.try_face(f).is_none()) {
+            return Err(ModellingError::MissingFace(f.index()));
+        }
+        let mut directed: HashSet<(T::V, T::V)> = HashSet::new();
+        for (loops, _) in faces.iter() {
+            if loops.is_empty() {
+                return Err(ModellingError::DegenerateFace(
+                    "A face needs at least an outer loop".to_string(),
+                ));
+            }
+            for ids in loops {
+                if let Some(&v) = ids.iter().find(|&&v| self.try_vertex(v).is_none()) {
+                    return Err(ModellingError::MissingVertex(v.index()));
+                }
+                if ids.len() < 3 || !ids.iter().all_unique() {
+                    return Err(ModellingError::DegenerateFace(format!(
+                        "The loop {:?} has less than three distinct vertices",
+                        ids
+                    )));
+                }
+                for (&a, &b) in ids.iter().circular_tuple_windows() {
+                    if !directed.insert((a, b)) {
+                        return Err(ModellingError::NonManifold(format!(
+                            "The halfedge from {} to {} is used by more than one face",
+                            a, b
+                        )));
+                    }
+                    if let Some(e) = self.shared_edge(a, b) {
+                        if !e.is_boundary_self() && !removed.contains(&e.face_id()) {
+                            return Err(ModellingError::NonManifold(format!(
+                                "The halfedge from {} to {} already has face {}",
+                                a,
+                                b,
+                                e.face_id()
+                            )));
+                        }
+                    }
+                }
+            }
+        }
+
+        // the outgoing halfedges of all vertices whose neighborhood changes
+        let old_edges = remove
+            .iter()
+            .flat_map(|&f| self.face_halfedge_ids(f))
+            .collect_vec();
+        let touched: HashSet<T::V> = old_edges
+            .iter()
+            .map(|&e| self.edge(e).origin_id())
+            .chain(
+                faces
+                    .iter()
+                    .flat_map(|(loops, _)| loops.iter().flatten().copied()),
+            )
+            .collect();
+        let mut outgoing: HashMap<T::V, Vec<T::E>> = touched
+            .iter()
+            .map(|&v| (v, self.vertex(v).edges_out(self).map(|e| e.id()).collect()))
+            .collect();
+        let mut halfedges: HashMap<(T::V, T::V), T::E> = HashMap::new();
+        for es in outgoing.values() {
+            for &e in es {
+                let edge = self.edge(e);
+                halfedges.insert((edge.origin_id(), edge.target_id(self)), e);
+            }
+        }
+
+        // the first faces take over the slots of the removed faces
+        for (i, &f) in remove.iter().enumerate() {
+            if i < faces.len() {
+                for e in self.face_halfedge_ids(f) {
+                    self.edge_mut(e).delete_face();
+                }
+            } else {
+                self.remove_face(f);
+            }
+        }
+
+        let mut fs = Vec::new();
+        for (i, (loops, fp)) in faces.into_iter().enumerate() {
+            let reused = remove.get(i).copied();
+            let f = reused.unwrap_or_else(|| self.allocate_face());
+            let mut starts = Vec::new();
+            for ids in loops {
+                let mut es = Vec::new();
+                for (&a, &b) in ids.iter().circular_tuple_windows() {
+                    let e = match halfedges.get(&(a, b)) {
+                        Some(&e) => e,
+                        None => {
+                            let (e, t) = (self.allocate_halfedge(), self.allocate_halfedge());
+                            for (h, twin, origin) in [(e, t, a), (t, e, b)] {
+                                self.insert_halfedge_no_update_no_check(
+                                    h,
+                                    origin,
+                                    IndexType::max(),
+                                    IndexType::max(),
+                                    twin,
+                                    IndexType::max(),
+                                    T::EP::default(),
+                                );
+                                outgoing.get_mut(&origin).unwrap().push(h);
+                            }
+                            halfedges.insert((a, b), e);
+                            halfedges.insert((b, a), t);
+                            e
+                        }
+                    };
+                    es.push(e);
+                }
+                for (&e, &n) in es.iter().circular_tuple_windows() {
+                    self.edge_mut(e).set_next(n);
+                    self.edge_mut(n).set_prev(e);
+                    self.edge_mut(e).set_face(f);
+                }
+                starts.push(es[0]);
+            }
+            if reused.is_some() {
+                let face = self.face_mut(f);
+                face.set_edge(starts[0]);
+                face.take_holes();
+                *face.payload_mut() = fp;
+            } else {
+                self.faces
+                    .set(f, HalfEdgeFaceImpl::new(starts[0], false, fp));
+            }
+            for &e in starts.iter().skip(1) {
+                self.face_mut(f).add_hole(e);
+            }
+            fs.push(f);
+        }
+
+        // delete the edges that lost all their faces
+        for e in old_edges {
+            if self.try_edge(e).is_none() {
+                continue;
+            }
+            let edge = self.edge(e).clone();
+            let twin = edge.twin(self);
+            if !edge.is_boundary_self() || !twin.is_boundary_self() {
+                continue;
+            }
+            for (h, v) in [(e, edge.origin_id()), (twin.id(), twin.origin_id())] {
+                outgoing.get_mut(&v).unwrap().retain(|&x| x != h);
+                self.halfedges.delete_internal(h);
+                self.halfedge_attributes.delete(h);
+            }
+        }
+
+        for &v in touched.iter() {
+            let es = &outgoing[&v];
+            if es.is_empty() {
+                self.vertices.delete_internal(v);
+                self.vertex_attributes.delete(v);
+                continue;
+            }
+
+            // The successor of an ingoing boundary halfedge is found by rotating around
+            // the vertex through the adjacent faces until the next boundary halfedge is hit.
+            for &out in es {
+                let ingoing = self.edge(out).twin_id();
+                if !self.edge(ingoing).is_boundary_self() {
+                    continue;
+                }
+                let mut next = out;
+                while !self.edge(next).is_boundary_self() {
+                    next = self.edge(next).prev(self).twin_id();
+                }
+                self.edge_mut(ingoing).set_next(next);
+                self.edge_mut(next).set_prev(ingoing);
+            }
+
+            // prefer boundary edges as the representative of vertices
+            let e = es
+                .iter()
+                .copied()
+                .find(|&e| self.edge(e).is_boundary_self())
+                .unwrap_or(es[0]);
+            self.vertex_mut(v).set_edge(e);
+        }
+
+        Ok(fs)
+    }
+
     /// Generate a path from the finite iterator of positions and return the halfedges pointing to the first and last vertex.
     fn insert_path(&mut self, vp: impl IntoIterator<Item = T::VP>) -> (T::E, T::E)
     where
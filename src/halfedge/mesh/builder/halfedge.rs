@@ -4,9 +4,8 @@ use crate::{
     },
     math::IndexType,
     mesh::{
-        DefaultEdgePayload, DefaultFacePayload, EdgeBasics, FaceBasics, HalfEdge,
-        HalfEdgeSemiBuilder, HalfEdgeVertex, MeshBasics, MeshHalfEdgeBuilder,
-        MeshType, VertexBasics,
+        DefaultEdgePayload, DefaultFacePayload, EdgeBasics, HalfEdge, HalfEdgeSemiBuilder,
+        HalfEdgeVertex, MeshBasics, MeshHalfEdgeBuilder, ModellingError, VertexBasics,
     },
 };
 use itertools::Itertools;
@@ -25,7 +24,7 @@ impl<T: HalfEdgeImplMeshType> MeshHalfEdgeBuilder<T> for HalfEdgeMeshImpl<T> {
         let v = self.edge(output).origin_id();
         debug_assert!(self.edge(input).target_id(self) == v);
 
-        let new = self.vertices.allocate();
+        let new = self.allocate_vertex();
 
         let (e1, e2) = self.insert_edge_no_update_no_check(
             (IndexType::max(), input, v, IndexType::max(), ep1),
@@ -75,8 +74,8 @@ impl<T: HalfEdgeImplMeshType> MeshHalfEdgeBuilder<T> for HalfEdgeMeshImpl<T> {
     }
 
     fn add_isolated_edge(&mut self, a: T::VP, epa: T::EP, b: T::VP, epb: T::EP) -> (T::V, T::V) {
-        let v0 = self.vertices.allocate();
-        let v1 = self.vertices.allocate();
+        let v0 = self.allocate_vertex();
+        let v1 = self.allocate_vertex();
         let (e0, e1) = self.insert_edge_no_update_no_check(
             (
                 IndexType::max(),
@@ -190,6 +189,45 @@ impl<T: HalfEdgeImplMeshType> MeshHalfEdgeBuilder<T> for HalfEdgeMeshImpl<T> {
         (e0, e1)
     }
 
+    fn try_insert_edge_between(
+        &mut self,
+        origin0: T::V,
+        ep0: T::EP,
+        origin1: T::V,
+        ep1: T::EP,
+    ) -> Result<(T::E, T::E), ModellingError> {
+        for v in [origin0, origin1] {
+            if !self.has_vertex(v) {
+                return Err(ModellingError::MissingVertex(v.index()));
+            }
+        }
+        if origin0 == origin1 {
+            return Err(ModellingError::InvalidTopology(format!(
+                "Cannot connect vertex {} with itself",
+                origin0
+            )));
+        }
+        if self.shared_edge(origin0, origin1).is_some() {
+            return Err(ModellingError::NonManifold(format!(
+                "There is already an edge between vertices {} and {}",
+                origin0, origin1
+            )));
+        }
+        for v in [origin0, origin1] {
+            let vertex = self.vertex(v);
+            if vertex.edge_id(self) != IndexType::max()
+                && (vertex.ingoing_boundary_edge(self).is_none()
+                    || vertex.outgoing_boundary_edge(self).is_none())
+            {
+                return Err(ModellingError::NonManifold(format!(
+                    "Vertex {} is not on the boundary",
+                    v
+                )));
+            }
+        }
+        Ok(self.insert_edge_between(origin0, ep0, origin1, ep1))
+    }
+
     fn insert_edge(&mut self, inside: T::E, ep1: T::EP, outside: T::E, ep2: T::EP) -> (T::E, T::E) {
         let e_inside = self.edge(inside);
         let e_outside = self.edge(outside);
@@ -220,6 +258,17 @@ impl<T: HalfEdgeImplMeshType> MeshHalfEdgeBuilder<T> for HalfEdgeMeshImpl<T> {
         (e1, e2)
     }
 
+    fn try_insert_edge(
+        &mut self,
+        inside: T::E,
+        ep1: T::EP,
+        outside: T::E,
+        ep2: T::EP,
+    ) -> Result<(T::E, T::E), ModellingError> {
+        self.check_insert_edge(inside, outside)?;
+        Ok(self.insert_edge(inside, ep1, outside, ep2))
+    }
+
     #[inline(always)]
     fn insert_halfedge_no_update_no_check(
         &mut self,
@@ -256,7 +305,7 @@ impl<T: HalfEdgeImplMeshType> MeshHalfEdgeBuilder<T> for HalfEdgeMeshImpl<T> {
         let (e1, e2) = self.insert_edge(inside, ep1, outside, ep2);
 
         // Insert the face
-        let f = self.faces.push(HalfEdgeFaceImpl::new(inside, curved, fp));
+        let f = self.push_face(HalfEdgeFaceImpl::new(inside, curved, fp));
 
         self.edge(inside)
             .clone()
@@ -266,6 +315,43 @@ impl<T: HalfEdgeImplMeshType> MeshHalfEdgeBuilder<T> for HalfEdgeMeshImpl<T> {
         return (f, e1, e2);
     }
 
+    fn try_close_face(
+        &mut self,
+        inside: T::E,
+        ep1: T::EP,
+        outside: T::E,
+        ep2: T::EP,
+        fp: T::FP,
+        curved: bool,
+    ) -> Result<(T::F, T::E, T::E), ModellingError> {
+        self.check_insert_edge(inside, outside)?;
+        let e_inside = self.edge(inside);
+        if !e_inside.is_boundary_self() {
+            return Err(ModellingError::InvalidTopology(format!(
+                "HalfEdge {} already has face {}",
+                inside,
+                e_inside.face_id()
+            )));
+        }
+
+        // the new face consists of `inside`, the new edge, and the edges from the successor of `outside` to `inside`
+        let n = self
+            .edge(outside)
+            .next(self)
+            .edges_face(self)
+            .take_while(|e| e.id() != inside)
+            .count()
+            + 2;
+        if n < 3 {
+            return Err(ModellingError::DegenerateFace(format!(
+                "Closing the face at halfedges {} and {} would create a face with {} edges",
+                inside, outside, n
+            )));
+        }
+
+        Ok(self.close_face(inside, ep1, outside, ep2, fp, curved))
+    }
+
     fn close_face_vertices(
         &mut self,
         prev: T::V,
@@ -301,6 +387,60 @@ impl<T: HalfEdgeImplMeshType> MeshHalfEdgeBuilder<T> for HalfEdgeMeshImpl<T> {
         return self.close_face(inside, ep1, outside, ep2, fp, curved);
     }
 
+    fn try_close_face_vertices(
+        &mut self,
+        prev: T::V,
+        ep1: T::EP,
+        from: T::V,
+        ep2: T::EP,
+        to: T::V,
+        fp: T::FP,
+        curved: bool,
+    ) -> Result<(T::F, T::E, T::E), ModellingError> {
+        for v in [prev, from, to] {
+            if !self.has_vertex(v) {
+                return Err(ModellingError::MissingVertex(v.index()));
+            }
+        }
+        let inside = self
+            .shared_edge(prev, from)
+            .ok_or_else(|| {
+                ModellingError::InvalidTopology(format!(
+                    "There is no edge from vertex {} to vertex {}",
+                    prev, from
+                ))
+            })?
+            .id();
+
+        let origin = self.edge(inside).origin_id();
+        let candidates = self
+            .vertex(to)
+            .edges_in(self)
+            .filter(|e| e.is_boundary_self() && e.same_face(self, origin))
+            .map(|e| e.id())
+            .collect_vec();
+        let outside = match candidates.as_slice() {
+            [outside] => *outside,
+            [] => {
+                return Err(ModellingError::InvalidTopology(format!(
+                    "Vertex {} has no ingoing boundary edge that can reach edge {}",
+                    to, inside
+                )))
+            }
+            _ => {
+                return Err(ModellingError::NonManifold(format!(
+                    "Vertex {} has {} ingoing boundary edges that can reach edge {}: {:?}",
+                    to,
+                    candidates.len(),
+                    inside,
+                    candidates
+                )))
+            }
+        };
+
+        self.try_close_face(inside, ep1, outside, ep2, fp, curved)
+    }
+
     fn close_face_default(
         &mut self,
         inside: T::E,
@@ -320,76 +460,75 @@ impl<T: HalfEdgeImplMeshType> MeshHalfEdgeBuilder<T> for HalfEdgeMeshImpl<T> {
             curved,
         )
     }
-}
 
-impl<T: HalfEdgeImplMeshType> HalfEdgeMeshImpl<T> {
-    pub(crate) fn import_mesh<FE, FV, FF, FM, T2: MeshType>(
-        mesh: &T2::Mesh,
-        fv: FV,
-        fe: FE,
-        ff: FF,
-        fm: FM
-    ) -> Self
-    where
-        FE: Fn(&T2::EP) -> T::EP,
-        FV: Fn(&T2::VP) -> T::VP,
-        FF: Fn(&T2::FP) -> T::FP,
-        FM: Fn(&T2::MP) -> T::MP,
-        T2::Edge: HalfEdge<T2>,
-    {
-        let mut res = Self::default();
-        let mut vertex_map = std::collections::HashMap::new();
-        for vertex in MeshBasics::vertices(mesh) {
-            let v = res.vertices.allocate();
-            vertex_map.insert(vertex.id(), v);
+    fn insert_hole(&mut self, f: T::F, e: T::E) {
+        assert!(
+            self.edge(e).is_boundary_self(),
+            "The hole loop of halfedge {} already belongs to face {}",
+            e,
+            self.edge(e).face_id()
+        );
+        self.edge(e)
+            .clone()
+            .edges_face_mut(self)
+            .for_each(|e| e.set_face(f));
+        self.face_mut(f).add_hole(e);
+    }
+
+    fn try_insert_hole(&mut self, f: T::F, e: T::E) -> Result<(), ModellingError> {
+        if self.try_face(f).is_none() {
+            return Err(ModellingError::MissingFace(f.index()));
         }
-        let mut face_map = std::collections::HashMap::new();
-        face_map.insert(IndexType::max(), IndexType::max());
-        for face in MeshBasics::faces(mesh) {
-            let f = res.faces.allocate();
-            face_map.insert(face.id(), f);
+        let edge = self
+            .try_edge(e)
+            .ok_or(ModellingError::MissingEdge(e.index()))?;
+        if !edge.is_boundary_self() {
+            return Err(ModellingError::InvalidTopology(format!(
+                "The hole loop of halfedge {} already belongs to face {}",
+                e,
+                edge.face_id()
+            )));
         }
-        let mut edge_map = std::collections::HashMap::new();
-        for edge in MeshBasics::edges(mesh) {
-            let e = res.halfedges.allocate();
-            edge_map.insert(edge.id(), e);
+        if edge.edges_face(self).count() < 3 {
+            return Err(ModellingError::DegenerateFace(format!(
+                "The hole loop of halfedge {} has less than three edges",
+                e
+            )));
         }
+        self.insert_hole(f, e);
+        Ok(())
+    }
+}
 
-        for vertex in MeshBasics::vertices(mesh) {
-            res.vertices.set(
-                vertex_map[&vertex.id()],
-                HalfEdgeVertexImpl::new(
-                    edge_map[&VertexBasics::edge_id(vertex, mesh)],
-                    fv(vertex.payload()),
-                ),
-            );
+impl<T: HalfEdgeImplMeshType> HalfEdgeMeshImpl<T> {
+    /// Checks whether an edge can be inserted between the targets of `inside` and `outside`.
+    fn check_insert_edge(&self, inside: T::E, outside: T::E) -> Result<(), ModellingError> {
+        let e_inside = self
+            .try_edge(inside)
+            .ok_or(ModellingError::MissingEdge(inside.index()))?;
+        let e_outside = self
+            .try_edge(outside)
+            .ok_or(ModellingError::MissingEdge(outside.index()))?;
+        let v = e_inside.target_id(self);
+        let w = e_outside.target_id(self);
+        if v == w {
+            return Err(ModellingError::InvalidTopology(format!(
+                "HalfEdges {} and {} target the same vertex {}",
+                inside, outside, v
+            )));
         }
-
-        for face in MeshBasics::faces(mesh) {
-            res.faces.set(
-                face_map[&face.id()],
-                HalfEdgeFaceImpl::new(
-                    edge_map[&FaceBasics::edge_id(face)],
-                    false,
-                    ff(face.payload()),
-                ),
-            );
+        if self.shared_edge(v, w).is_some() {
+            return Err(ModellingError::NonManifold(format!(
+                "There is already an edge between vertices {} and {}",
+                v, w
+            )));
         }
-
-        for edge in MeshBasics::edges(mesh) {
-            res.insert_halfedge_no_update_no_check(
-                edge_map[&edge.id()],
-                vertex_map[&edge.origin_id()],
-                face_map[&edge.face_id()],
-                edge_map[&edge.prev_id()],
-                edge_map[&edge.twin_id()],
-                edge_map[&edge.next_id()],
-                fe(&edge.payload()),
-            );
+        if !e_inside.same_face_back(self, w) || !e_outside.same_face_back(self, v) {
+            return Err(ModellingError::InvalidTopology(format!(
+                "HalfEdges {} and {} are not on the same face",
+                inside, outside
+            )));
         }
-
-        res.set_payload(fm(MeshBasics::payload(mesh)));
-
-        res
+        Ok(())
     }
 }
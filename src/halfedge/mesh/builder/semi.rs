@@ -113,8 +113,8 @@ impl<T: HalfEdgeImplMeshType> HalfEdgeSemiBuilder<T> for HalfEdgeMeshImpl<T> {
     ) -> (T::E, T::E) {
         // TODO: remove the tuples!
 
-        let e1 = self.halfedges.allocate();
-        let e2 = self.halfedges.allocate();
+        let e1 = self.allocate_halfedge();
+        let e2 = self.allocate_halfedge();
         self.insert_halfedge_no_update_no_check(e1, origin1, face1, prev1, e2, next1, ep1);
         self.insert_halfedge_no_update_no_check(e2, origin2, face2, prev2, e1, next2, ep2);
         (e1, e2)
@@ -123,8 +123,8 @@ impl<T: HalfEdgeImplMeshType> HalfEdgeSemiBuilder<T> for HalfEdgeMeshImpl<T> {
     fn subdivide_unsafe(&mut self, e: T::E, vp: T::VP, ep: T::EP) -> T::E {
         let old_edge = self.edge(e).clone();
 
-        let new_v = self.vertices.allocate();
-        let new_edge = self.halfedges.allocate();
+        let new_v = self.allocate_vertex();
+        let new_edge = self.allocate_halfedge();
 
         self.halfedges.set(
             new_edge,
@@ -140,6 +140,13 @@ impl<T: HalfEdgeImplMeshType> HalfEdgeSemiBuilder<T> for HalfEdgeMeshImpl<T> {
         self.vertices
             .set(new_v, HalfEdgeVertexImpl::new(new_edge, vp));
 
+        // the new vertex lies between the endpoints and the new halfedge continues the old one
+        let target = self.edge(old_edge.next_id()).origin_id();
+        self.vertex_attributes
+            .interpolate(new_v, old_edge.origin_id(), target, 0.5);
+        self.halfedge_attributes
+            .interpolate(new_edge, old_edge.id(), old_edge.id(), 0.0);
+
         self.edge_mut(old_edge.next_id()).set_prev(new_edge);
         self.edge_mut(old_edge.id()).set_next(new_edge);
 
@@ -169,7 +176,7 @@ impl<T: HalfEdgeImplMeshType> HalfEdgeSemiBuilder<T> for HalfEdgeMeshImpl<T> {
         }
 
         // Insert the new edge
-        let new_edge = self.halfedges.allocate();
+        let new_edge = self.allocate_halfedge();
         self.halfedges.set(
             new_edge,
             HalfEdgeImpl::new(
@@ -182,6 +189,9 @@ impl<T: HalfEdgeImplMeshType> HalfEdgeSemiBuilder<T> for HalfEdgeMeshImpl<T> {
             ),
         );
 
+        self.halfedge_attributes
+            .interpolate(new_edge, old_edge.id(), old_edge.id(), 0.0);
+
         // update the neighbors
         self.edge_mut(old_edge.id()).set_twin(other_new.id());
         self.edge_mut(other_old.id()).set_twin(new_edge);
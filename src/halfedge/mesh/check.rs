@@ -1,32 +1,35 @@
 use super::HalfEdgeMeshImpl;
 use crate::{
     halfedge::HalfEdgeImplMeshType,
-    mesh::{EdgeBasics, FaceBasics, HalfEdge, MeshBasics, MeshChecker, VertexBasics},
+    mesh::{
+        EdgeBasics, FaceBasics, HalfEdge, MeshBasics, MeshChecker, ModellingError, VertexBasics,
+    },
 };
+use std::collections::HashMap;
 
 impl<T: HalfEdgeImplMeshType> HalfEdgeMeshImpl<T> {
     /// Checks whether the twin of the twin is always the edge itself,
     /// the precursor to the next edge is the same, and the successor of the previous.
-    fn check_edge_invariants(&self) -> Result<(), String> {
+    fn check_edge_invariants(&self) -> Result<(), ModellingError> {
         if let Some(unmatched_twin) = self.edges().find(|e| e.twin(self).twin_id() != e.id()) {
-            return Err(format!(
+            return Err(ModellingError::InvalidTopology(format!(
                 "HalfEdge {} has a twin {} with twin {}",
                 unmatched_twin.id(),
                 unmatched_twin.twin_id(),
                 unmatched_twin.twin(self).twin_id()
-            ));
+            )));
         }
 
         if let Some(prev_next) = self
             .edges()
             .find(|e| e.next(self).prev(self).id() != e.id() || e.prev(self).next_id() != e.id())
         {
-            return Err(format!(
+            return Err(ModellingError::InvalidTopology(format!(
                 "HalfEdge {} has prev(next) {} and next(prev) {}",
                 prev_next.id(),
                 prev_next.next(self).prev_id(),
                 prev_next.prev(self).next_id()
-            ));
+            )));
         }
 
         if let Some(face_next) = self.edges().find(|e| {
@@ -35,31 +38,31 @@ impl<T: HalfEdgeImplMeshType> HalfEdgeMeshImpl<T> {
             (f1.is_none() ^ f2.is_none())
                 || (f1.is_some() && f2.is_some() && f1.unwrap().id() != f2.unwrap().id())
         }) {
-            return Err(format!(
+            return Err(ModellingError::InvalidTopology(format!(
                 "HalfEdge {} has face {} but next has face {}",
                 face_next.id(),
                 face_next.face_id(),
                 face_next.next(self).face_id()
-            ));
+            )));
         }
 
         if let Some(bad_edge) = self.edges().find(|e| {
             e.next(self).origin_id() != e.twin(self).origin_id()
                 || e.target_id(self) != e.twin(self).origin_id()
         }) {
-            return Err(format!(
+            return Err(ModellingError::InvalidTopology(format!(
                 "HalfEdge {} has next origin {} and target {} but twin origin {}",
                 bad_edge.id(),
                 bad_edge.next(self).origin_id(),
                 bad_edge.target_id(self),
                 bad_edge.twin(self).origin_id()
-            ));
+            )));
         }
 
         Ok(())
     }
 
-    fn check_vertex_invariants(&self) -> Result<(), String> {
+    fn check_vertex_invariants(&self) -> Result<(), ModellingError> {
         if let Some(bad_vertex) = self.vertices().find(|v| {
             if let Some(e) = v.edge(self) {
                 e.origin_id() != v.id()
@@ -67,85 +70,135 @@ impl<T: HalfEdgeImplMeshType> HalfEdgeMeshImpl<T> {
                 false
             }
         }) {
-            return Err(format!(
+            return Err(ModellingError::InvalidTopology(format!(
                 "Vertex {} has edge {} with origin {}",
                 bad_vertex.id(),
                 bad_vertex.edge(self).unwrap().id(),
                 bad_vertex.edge(self).unwrap().origin_id()
-            ));
+            )));
         }
 
         Ok(())
     }
 
-    fn check_edges_are_loops(&self) -> Result<(), String> {
+    fn check_edges_are_loops(&self) -> Result<(), ModellingError> {
         if let Some(bad_edge) = self
             .edges()
             .find(|e| !e.next(self).same_face(self, e.origin_id()))
         {
-            return Err(format!(
+            return Err(ModellingError::InvalidTopology(format!(
                 "Successor of edge {} cannot reach it's origin {} during forward search",
                 bad_edge.id(),
                 bad_edge.origin_id()
-            ));
+            )));
         }
 
         if let Some(bad_edge) = self
             .edges()
             .find(|e| !e.prev(self).same_face_back(self, e.target_id(self)))
         {
-            return Err(format!(
+            return Err(ModellingError::InvalidTopology(format!(
                 "Precursor of edge {} cannot reach it's target {} during backward search",
                 bad_edge.id(),
                 bad_edge.target_id(self)
-            ));
+            )));
         }
 
         Ok(())
     }
 
-    fn check_faces_nondegenerate(&self) -> Result<(), String> {
+    fn check_faces_nondegenerate(&self) -> Result<(), ModellingError> {
         // TODO: this and many other checks would also work without half edges!
         if let Some(bad_face) = self.faces().find(|f| f.edges(self).count() < 3) {
-            return Err(format!(
+            return Err(ModellingError::DegenerateFace(format!(
                 "Face {} has only {} faces!",
                 bad_face.id(),
                 bad_face.edges(self).count()
-            ));
+            )));
         }
 
         Ok(())
     }
 
     /// This is somewhat optional; the algorithms shouldn't break when using this, but there isn't really a reason for it existing in a wellformed mesh
-    fn check_edges_have_face(&self) -> Result<(), String> {
+    fn check_edges_have_face(&self) -> Result<(), ModellingError> {
         if let Some(bad_edge) = self
             .edges()
             .find(|e| e.is_boundary_self() && e.twin(self).is_boundary_self())
         {
-            return Err(format!("HalfEdge {} has no face!", bad_edge.id()));
+            return Err(ModellingError::InvalidTopology(format!(
+                "HalfEdge {} has no face!",
+                bad_edge.id()
+            )));
         }
         Ok(())
     }
 
-    fn check_face_invariants(&self) -> Result<(), String> {
+    fn check_face_invariants(&self) -> Result<(), ModellingError> {
         if let Some(bad_face) = self.faces().find(|f| f.edge(self).face_id() != f.id()) {
-            return Err(format!(
+            return Err(ModellingError::InvalidTopology(format!(
                 "Face {} has edge {} with face {}",
                 bad_face.id(),
                 bad_face.edge(self).id(),
                 bad_face.edge(self).face_id()
-            ));
+            )));
         }
         Ok(())
     }
+
+    /// Checks that the hole loops belong to their face and that every halfedge of a face
+    /// is either on its outer boundary or on exactly one of its holes.
+    fn check_face_holes(&self) -> Result<(), ModellingError> {
+        let mut loops = HashMap::new();
+        for f in self.faces() {
+            for e in f.edges(self) {
+                loops.insert(e.id(), f.id());
+            }
+            for (hole, e) in f.hole_edge_ids().enumerate() {
+                if self.edge(e).face_id() != f.id() {
+                    return Err(ModellingError::InvalidTopology(format!(
+                        "Face {} has hole {} with edge {} of face {}",
+                        f.id(),
+                        hole,
+                        e,
+                        self.edge(e).face_id()
+                    )));
+                }
+                for e in f.hole_edges(self, hole) {
+                    if let Some(other) = loops.insert(e.id(), f.id()) {
+                        return Err(ModellingError::InvalidTopology(format!(
+                            "HalfEdge {} is on hole {} of face {} but also on a loop of face {}",
+                            e.id(),
+                            hole,
+                            f.id(),
+                            other
+                        )));
+                    }
+                }
+            }
+        }
+
+        if let Some(bad_edge) = self
+            .edges()
+            .find(|e| !e.is_boundary_self() && !loops.contains_key(&e.id()))
+        {
+            return Err(ModellingError::InvalidTopology(format!(
+                "HalfEdge {} has face {} but is neither on its boundary nor on one of its holes",
+                bad_edge.id(),
+                bad_edge.face_id()
+            )));
+        }
+
+        Ok(())
+    }
 }
 
 impl<T: HalfEdgeImplMeshType> MeshChecker<T> for HalfEdgeMeshImpl<T> {
     /// Checks the mesh for consistency
-    fn check(&self) -> Result<(), String> {
+    fn check(&self) -> Result<(), ModellingError> {
         self.check_edge_invariants()?;
         self.check_face_invariants()?;
+        self.check_face_holes()?;
         self.check_vertex_invariants()?;
         self.check_edges_are_loops()?;
         self.check_faces_nondegenerate()?;
@@ -0,0 +1,118 @@
+use super::HalfEdgeMeshImpl;
+use crate::{
+    halfedge::HalfEdgeImplMeshType,
+    math::IndexType,
+    mesh::{FaceBasics, HalfEdge, HalfEdgeVertex, MeshBasics, VertexBasics},
+};
+use std::collections::HashMap;
+
+fn remap<I: IndexType>(map: &HashMap<I, I>, i: I) -> I {
+    if i == IndexType::max() {
+        i
+    } else {
+        map[&i]
+    }
+}
+
+impl<T: HalfEdgeImplMeshType> HalfEdgeMeshImpl<T> {
+    /// Removes the gaps left by deleted vertices, halfedges, and faces so the ids
+    /// are consecutive again, e.g., to export dense buffers.
+    ///
+    /// Returns the old-to-new index maps of the vertices, halfedges, and faces
+    /// so references stored outside the mesh can be updated.
+    pub fn compact(
+        &mut self,
+    ) -> (
+        HashMap<T::V, T::V>,
+        HashMap<T::E, T::E>,
+        HashMap<T::F, T::F>,
+    ) {
+        let vs = self.vertices.compaction_map();
+        let es = self.halfedges.compaction_map();
+        let fs = self.faces.compaction_map();
+
+        let vertex_edges: Vec<_> = self.vertices().map(|v| (v.id(), v.edge_id(self))).collect();
+        for (v, e) in vertex_edges {
+            self.vertex_mut(v).set_edge(remap(&es, e));
+        }
+        for e in self.halfedges.iter_mut() {
+            e.set_origin(remap(&vs, e.origin_id()));
+            let face = e.face_id();
+            if face != IndexType::max() {
+                e.delete_face();
+                e.set_face(fs[&face]);
+            }
+            e.set_twin(remap(&es, e.twin_id()));
+            e.set_prev(remap(&es, e.prev_id()));
+            e.set_next(remap(&es, e.next_id()));
+        }
+        for f in self.faces.iter_mut() {
+            f.set_edge(remap(&es, f.edge_id()));
+            for hole in f.take_holes() {
+                f.add_hole(remap(&es, hole));
+            }
+        }
+
+        self.vertices.compact();
+        self.halfedges.compact();
+        self.faces.compact();
+        self.vertex_attributes.compact(&vs);
+        self.halfedge_attributes.compact(&es);
+        self.face_attributes.compact(&fs);
+
+        (vs, es, fs)
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "nalgebra")]
+mod tests {
+    use crate::{extensions::nalgebra::*, prelude::*};
+
+    #[test]
+    fn test_compact() {
+        let mut mesh = Mesh3d64::new();
+        let pos = |x: f64, y: f64| VertexPayloadPNU::from_pos(Vec3::new(x, y, 0.0));
+
+        // a triangle that is deleted afterwards, leaving gaps at the start of all buffers
+        let e = mesh.insert_loop([pos(5.0, 0.0), pos(6.0, 0.0), pos(5.0, 1.0)]);
+        let triangle = mesh.close_hole_default(mesh.edge(e).twin_id());
+        let square =
+            mesh.insert_polygon([pos(0.0, 0.0), pos(1.0, 0.0), pos(1.0, 1.0), pos(0.0, 1.0)]);
+        let square = mesh.edge(square).twin(&mesh).face_id();
+        let old_positions: Vec<_> = mesh
+            .face(square)
+            .vertices(&mesh)
+            .map(|v| (v.id(), v.pos()))
+            .collect();
+
+        let edges: Vec<_> = mesh.face(triangle).edges(&mesh).map(|e| e.id()).collect();
+        let vertices: Vec<_> = mesh.face(triangle).vertex_ids(&mesh).collect();
+        mesh.remove_face(triangle);
+        for e in edges {
+            let twin = mesh.edge(e).twin_id();
+            mesh.halfedges.delete_internal(e);
+            mesh.halfedges.delete_internal(twin);
+        }
+        for v in vertices {
+            mesh.vertices.delete_internal(v);
+        }
+        assert!(!mesh.has_consecutive_vertex_ids());
+
+        let (vs, es, fs) = mesh.compact();
+        assert!(mesh.check().is_ok());
+        assert!(mesh.has_consecutive_vertex_ids());
+        assert_eq!(mesh.max_vertex_index(), 4);
+        assert_eq!(mesh.num_edges(), 8);
+        assert_eq!((vs.len(), es.len(), fs.len()), (4, 8, 1));
+        assert_eq!(fs[&square], 0);
+        assert!(mesh.edges().all(|e| e.id() < 8));
+        for (v, p) in old_positions {
+            assert_eq!(mesh.vertex(vs[&v]).pos(), p);
+        }
+
+        // the free lists are cleared, so new elements are appended
+        let v = mesh.add_vertex(pos(2.0, 2.0));
+        assert_eq!(v, 4);
+    }
+}
@@ -1,8 +1,15 @@
+mod append;
+mod attributes;
 mod basics;
 mod builder;
 mod check;
+mod compact;
 mod halfedge;
 mod pseudo_winged;
+mod split;
+mod tufted;
+
+pub use tufted::TuftedCover;
 
 use super::HalfEdgeImplMeshType;
 use crate::{
@@ -10,28 +17,46 @@ use crate::{
     mesh::{
         EuclideanMeshType, MeshTopology, MeshTrait, TransformableMesh, Triangulateable, WithNormals,
     },
-    util::DeletableVector,
+    util::{AttributeStore, DeletableVector},
 };
 
 /// A halfedge-inspired mesh data structure for (open) manifold meshes.
 ///
 /// Since coordinates are a variable payload, you can use this mesh for any dimension >= 2.
 ///
-/// Non-manifold edges (multiple faces per edge) are currently not supported
-/// -- use multiple meshes or a "tufted cover".
+/// Non-manifold edges (multiple faces per edge) are not supported directly
+/// -- use multiple meshes or a tufted cover, see `insert_tufted_cover`.
 /// Non-manifold vertices are supported!
 ///
 /// Non-orientable surfaces have to be covered by multiple faces (so they become oriented).
 ///
 /// Currently only euclidean geometry is supported.
+///
+/// Besides the payloads, vertices, halfedges, and faces can carry named attribute layers
+/// that are added at runtime, see `vertex_attributes_mut`.
 #[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(
+        serialize = "T::Vertex: serde::Serialize, T::Edge: serde::Serialize, T::Face: serde::Serialize, T::V: serde::Serialize, T::E: serde::Serialize, T::F: serde::Serialize, T::MP: serde::Serialize",
+        deserialize = "T::Vertex: serde::Deserialize<'de>, T::Edge: serde::Deserialize<'de>, T::Face: serde::Deserialize<'de>, T::V: serde::Deserialize<'de>, T::E: serde::Deserialize<'de>, T::F: serde::Deserialize<'de>, T::MP: serde::Deserialize<'de>"
+    ))
+)]
 pub struct HalfEdgeMeshImpl<T: HalfEdgeImplMeshType> {
-    // TODO: to import non-manifold edges, we could use the "tufted cover" https://www.cs.cmu.edu/~kmcrane/Projects/NonmanifoldLaplace/index.html
     // TODO: non-euclidean geometry
     vertices: DeletableVector<T::Vertex, T::V>,
     halfedges: DeletableVector<T::Edge, T::E>,
     faces: DeletableVector<T::Face, T::F>,
     payload: T::MP,
+
+    // Only layers of the built-in attribute types can be serialized, see `AttributeStore`.
+    #[cfg_attr(feature = "serde", serde(default))]
+    vertex_attributes: AttributeStore<T::V>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    halfedge_attributes: AttributeStore<T::E>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    face_attributes: AttributeStore<T::F>,
 }
 
 impl<T: HalfEdgeImplMeshType> HalfEdgeMeshImpl<T> {
@@ -42,6 +67,9 @@ impl<T: HalfEdgeImplMeshType> HalfEdgeMeshImpl<T> {
             halfedges: DeletableVector::new(),
             faces: DeletableVector::new(),
             payload: T::MP::default(),
+            vertex_attributes: AttributeStore::new(),
+            halfedge_attributes: AttributeStore::new(),
+            face_attributes: AttributeStore::new(),
         }
     }
 }
@@ -77,3 +105,74 @@ impl<T: HalfEdgeImplMeshType> MeshTrait for HalfEdgeMeshImpl<T> {
 
 #[cfg(feature = "netsci")]
 impl<T: HalfEdgeImplMeshType> crate::mesh::NetworkScience<T> for HalfEdgeMeshImpl<T> {}
+
+#[cfg(test)]
+#[cfg(all(feature = "serde", feature = "nalgebra"))]
+mod tests {
+    use crate::{extensions::nalgebra::*, prelude::*};
+
+    #[test]
+    fn test_serde_round_trip() {
+        let mut mesh = Mesh3d64::cube(1.0);
+        let f = mesh.face_ids().nth(2).unwrap();
+        let e = mesh.face(f).edge_id();
+        mesh.remove_face(f);
+
+        let json = serde_json::to_string(&mesh).unwrap();
+        let mut restored: Mesh3d64 = serde_json::from_str(&json).unwrap();
+        assert!(restored.check().is_ok());
+        assert_eq!(serde_json::to_string(&restored).unwrap(), json);
+        for v in mesh.vertices() {
+            assert_eq!(restored.vertex(v.id()).pos(), v.pos());
+        }
+
+        // the free list is restored, so the deleted face id is reused
+        assert_eq!(
+            restored.close_hole(e, Default::default(), false),
+            mesh.close_hole(e, Default::default(), false)
+        );
+        assert_eq!(
+            restored.face_ids().collect::<Vec<_>>(),
+            mesh.face_ids().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_serde_attributes() {
+        let mut mesh = Mesh3d64::cube(1.0);
+        let v = mesh.vertex_ids().next().unwrap();
+        let f = mesh.face_ids().next().unwrap();
+        mesh.vertex_attributes_mut().add::<f64>("weight");
+        mesh.vertex_attributes_mut().set("weight", v, 0.5);
+        mesh.face_attributes_mut().add::<String>("material");
+        mesh.face_attributes_mut()
+            .set("material", f, "wood".to_string());
+        mesh.face_attributes_mut().add::<[f32; 3]>("color");
+
+        let json = serde_json::to_string(&mesh).unwrap();
+        let restored: Mesh3d64 = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            restored.vertex_attributes().get::<f64>("weight", v),
+            Some(&0.5)
+        );
+        assert_eq!(
+            restored.face_attributes().get::<String>("material", f),
+            Some(&"wood".to_string())
+        );
+        assert!(restored
+            .face_attributes()
+            .contains_typed::<[f32; 3]>("color"));
+        assert_eq!(serde_json::to_string(&restored).unwrap(), json);
+
+        // layers of custom types can't be serialized
+        #[derive(Clone, Default)]
+        struct Custom;
+        impl Attribute for Custom {
+            fn interpolate(&self, _: &Self, _: f64) -> Self {
+                Custom
+            }
+        }
+        mesh.halfedge_attributes_mut().add::<Custom>("custom");
+        assert!(serde_json::to_string(&mesh).is_err());
+    }
+}
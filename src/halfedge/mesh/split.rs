@@ -0,0 +1,144 @@
+use super::HalfEdgeMeshImpl;
+use crate::{
+    halfedge::{HalfEdgeImplMeshType, HalfEdgeVertexImpl},
+    math::IndexType,
+    mesh::{
+        EdgeBasics, HalfEdge, HalfEdgeVertex, MeshBasics, MeshHalfEdgeBuilder, MeshSplit,
+        VertexBasics,
+    },
+};
+use std::collections::{HashMap, HashSet};
+
+impl<T: HalfEdgeImplMeshType> HalfEdgeMeshImpl<T> {
+    /// Cuts the pair of halfedges apart by giving both a new boundary twin.
+    fn cut_edge(&mut self, e: T::E) {
+        let t = self.edge(e).twin_id();
+        let origin = self.edge(e).origin_id();
+        let target = self.edge(t).origin_id();
+        let e2 = self.allocate_halfedge();
+        let t2 = self.allocate_halfedge();
+
+        // prev and next are fixed when relinking the boundaries
+        let ep = self.edge(t).payload().clone();
+        self.insert_halfedge_no_update_no_check(
+            e2,
+            target,
+            IndexType::max(),
+            IndexType::max(),
+            e,
+            IndexType::max(),
+            ep,
+        );
+        let tp = self.edge(e).payload().clone();
+        self.insert_halfedge_no_update_no_check(
+            t2,
+            origin,
+            IndexType::max(),
+            IndexType::max(),
+            t,
+            IndexType::max(),
+            tp,
+        );
+        self.halfedge_attributes.interpolate(e2, t, t, 0.0);
+        self.halfedge_attributes.interpolate(t2, e, e, 0.0);
+
+        self.edge_mut(e).set_twin(e2);
+        self.edge_mut(t).set_twin(t2);
+    }
+
+    /// Links the boundary halfedge to the outgoing boundary halfedge that closes the fan of faces around its target.
+    fn relink_boundary(&mut self, b: T::E) {
+        let mut c = self.edge(b).twin_id();
+        while !self.edge(c).is_boundary_self() {
+            c = self.edge(self.edge(c).prev_id()).twin_id();
+        }
+        self.edge_mut(b).set_next(c);
+        self.edge_mut(c).set_prev(b);
+    }
+
+    /// Groups the outgoing halfedges of a vertex into fans of faces that are connected via edges.
+    fn outgoing_fans(&self, outgoing: &[T::E]) -> Vec<Vec<T::E>> {
+        let mut visited = HashSet::new();
+        let mut fans = Vec::new();
+        for &start in outgoing {
+            if !visited.insert(start) {
+                continue;
+            }
+            let mut fan = vec![start];
+            let mut i = 0;
+            while i < fan.len() {
+                let c = self.edge(fan[i]);
+                let twin = c.twin(self);
+                let mut neighbors = Vec::new();
+                if !c.is_boundary_self() {
+                    neighbors.push(c.prev(self).twin_id());
+                }
+                if !twin.is_boundary_self() {
+                    neighbors.push(twin.next_id());
+                }
+                for n in neighbors {
+                    if visited.insert(n) {
+                        fan.push(n);
+                    }
+                }
+                i += 1;
+            }
+            fans.push(fan);
+        }
+        fans
+    }
+}
+
+impl<T: HalfEdgeImplMeshType> MeshSplit<T> for HalfEdgeMeshImpl<T> {
+    fn split_edges(&mut self, edges: impl IntoIterator<Item = T::E>) -> Vec<T::V> {
+        let mut touched = Vec::new();
+        for e in edges {
+            let edge = self.edge(e);
+            // boundary edges and edges that were already cut don't have to be cut
+            if edge.is_boundary_self() || edge.twin(self).is_boundary_self() {
+                continue;
+            }
+            touched.push(edge.origin_id());
+            touched.push(edge.twin(self).origin_id());
+            self.cut_edge(e);
+        }
+        let mut touched_set = HashSet::new();
+        touched.retain(|v| touched_set.insert(*v));
+
+        // the boundary loops around the touched vertices have changed
+        let boundary: Vec<T::E> = self
+            .halfedges
+            .iter()
+            .filter(|e| e.is_boundary_self() && touched_set.contains(&e.twin(self).origin_id()))
+            .map(|e| e.id())
+            .collect();
+        let mut outgoing: HashMap<T::V, Vec<T::E>> = HashMap::new();
+        for e in self.halfedges.iter() {
+            if touched_set.contains(&e.origin_id()) {
+                outgoing.entry(e.origin_id()).or_default().push(e.id());
+            }
+        }
+        for b in boundary {
+            self.relink_boundary(b);
+        }
+
+        // every additional fan around a vertex gets its own copy of the vertex
+        let mut new_vertices = Vec::new();
+        for v in touched {
+            let fans = self.outgoing_fans(&outgoing[&v]);
+            self.vertex_mut(v).set_edge(fans[0][0]);
+            for fan in fans.iter().skip(1) {
+                let w = self.allocate_vertex();
+                let vp = self.vertex(v).payload().clone();
+                self.vertices.set(w, HalfEdgeVertexImpl::new(fan[0], vp));
+                self.vertex_attributes.interpolate(w, v, v, 0.0);
+                for &c in fan {
+                    self.edge_mut(c).set_origin(w);
+                }
+                new_vertices.push(w);
+            }
+        }
+
+        new_vertices
+    }
+}
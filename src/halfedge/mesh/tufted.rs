@@ -0,0 +1,386 @@
+use super::HalfEdgeMeshImpl;
+use crate::{
+    halfedge::{HalfEdgeFaceImpl, HalfEdgeImplMeshType, HalfEdgeVertexImpl},
+    math::IndexType,
+    mesh::{
+        DefaultEdgePayload, FaceBasics, HalfEdge, IndexedFaceError, MeshBasics, MeshBuilder,
+        MeshHalfEdgeBuilder, VertexBasics,
+    },
+};
+use itertools::Itertools;
+use std::collections::HashMap;
+
+/// Keeps track of a tufted cover inserted using `HalfEdgeMeshImpl::insert_tufted_cover`.
+///
+/// The tufted cover (see <https://www.cs.cmu.edu/~kmcrane/Projects/NonmanifoldLaplace/index.html>)
+/// represents arbitrary polygon soups as a closed, oriented manifold:
+/// Each face is inserted twice, once with each orientation, and the sides of all
+/// faces around an edge are glued pairwise in a cyclic order.
+/// An edge shared by `k` faces hence becomes `k` manifold edges between the same positions.
+/// Vertices are split into one vertex per fan of glued faces.
+///
+/// This structure maps the elements of the cover back to the original vertices and faces.
+#[derive(Clone)]
+pub struct TuftedCover<T: HalfEdgeImplMeshType> {
+    /// The original vertex of each vertex of the cover.
+    vertex_origin: HashMap<T::V, usize>,
+
+    /// A vertex of the cover for each original vertex.
+    representatives: Vec<T::V>,
+
+    /// The original face of each face of the cover and whether it is the back side.
+    face_origin: HashMap<T::F, (usize, bool)>,
+
+    /// The front and back side of each original face.
+    sides: Vec<(T::F, T::F)>,
+
+    /// The original faces around each undirected edge in the order they are glued.
+    edge_faces: HashMap<(usize, usize), Vec<usize>>,
+}
+
+fn undirected(a: usize, b: usize) -> (usize, usize) {
+    if a < b {
+        (a, b)
+    } else {
+        (b, a)
+    }
+}
+
+impl<T: HalfEdgeImplMeshType> TuftedCover<T> {
+    /// Returns the number of original vertices.
+    pub fn num_vertices(&self) -> usize {
+        self.representatives.len()
+    }
+
+    /// Returns the number of original faces.
+    pub fn num_faces(&self) -> usize {
+        self.sides.len()
+    }
+
+    /// Returns the original vertex of the given vertex of the cover.
+    pub fn original_vertex(&self, v: T::V) -> usize {
+        self.vertex_origin[&v]
+    }
+
+    /// Returns the original face of the given face of the cover.
+    pub fn original_face(&self, f: T::F) -> usize {
+        self.face_origin[&f].0
+    }
+
+    /// Whether the given face of the cover is the back side (reversed orientation) of its original face.
+    pub fn is_back(&self, f: T::F) -> bool {
+        self.face_origin[&f].1
+    }
+
+    /// Returns the front and back side of the given original face.
+    pub fn sides(&self, face: usize) -> (T::F, T::F) {
+        self.sides[face]
+    }
+
+    /// Returns the original faces around the edge between the original vertices `a` and `b`
+    /// in the order they are glued. Empty if there is no such edge.
+    pub fn faces_around_edge(&self, a: usize, b: usize) -> &[usize] {
+        self.edge_faces
+            .get(&undirected(a, b))
+            .map_or(&[], |fs| fs.as_slice())
+    }
+
+    /// Returns the original faces around the original edge of the given halfedge of the cover.
+    pub fn true_faces(&self, mesh: &HalfEdgeMeshImpl<T>, e: T::E) -> &[usize] {
+        let edge = mesh.edge(e);
+        self.faces_around_edge(
+            self.original_vertex(edge.origin_id()),
+            self.original_vertex(edge.target_id(mesh)),
+        )
+    }
+
+    /// Whether the original edge of the given halfedge of the cover is shared by more than two faces.
+    pub fn is_non_manifold(&self, mesh: &HalfEdgeMeshImpl<T>, e: T::E) -> bool {
+        self.true_faces(mesh, e).len() > 2
+    }
+
+    /// Returns the pairs of original vertices whose edge is shared by more than two faces.
+    pub fn non_manifold_edges(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.edge_faces
+            .iter()
+            .filter(|(_, fs)| fs.len() > 2)
+            .map(|(&e, _)| e)
+            .sorted()
+    }
+
+    /// Exports the original topology, i.e., the vertex payloads and indexed faces
+    /// as they were passed to `insert_tufted_cover`.
+    /// The payloads are read from the cover, so modifications of the front sides are preserved.
+    pub fn to_indexed_faces(
+        &self,
+        mesh: &HalfEdgeMeshImpl<T>,
+    ) -> (Vec<T::VP>, Vec<(Vec<usize>, T::FP)>) {
+        let vps = self
+            .representatives
+            .iter()
+            .map(|&v| mesh.vertex(v).payload().clone())
+            .collect_vec();
+        let faces = self
+            .sides
+            .iter()
+            .map(|&(front, _)| {
+                let face = mesh.face(front);
+                let ids = face
+                    .edges(mesh)
+                    .map(|e| self.original_vertex(e.origin_id()))
+                    .collect_vec();
+                (ids, *face.payload())
+            })
+            .collect_vec();
+        (vps, faces)
+    }
+}
+
+impl<T: HalfEdgeImplMeshType> HalfEdgeMeshImpl<T> {
+    /// Inserts the tufted cover of the given indexed faces.
+    /// Unlike `insert_indexed_faces`, this accepts edges shared by any number of faces
+    /// and inconsistently oriented faces, e.g., fins in imported CAD data.
+    ///
+    /// The faces around an edge are glued in the order of the input.
+    /// Returns the mapping between the cover and the input.
+    /// If a face is degenerate or references a missing vertex, the mesh is not modified.
+    pub fn insert_tufted_cover(
+        &mut self,
+        vps: impl IntoIterator<Item = T::VP>,
+        faces: impl IntoIterator<Item = (Vec<usize>, T::FP)>,
+    ) -> Result<TuftedCover<T>, IndexedFaceError>
+    where
+        T::EP: DefaultEdgePayload,
+    {
+        let vps = vps.into_iter().collect_vec();
+        let faces = faces.into_iter().collect_vec();
+
+        for (i, (ids, _)) in faces.iter().enumerate() {
+            if let Some(&vertex) = ids.iter().find(|&&v| v >= vps.len()) {
+                return Err(IndexedFaceError::VertexOutOfRange { face: i, vertex });
+            }
+            if ids.len() < 3 || !ids.iter().all_unique() {
+                return Err(IndexedFaceError::DegenerateFace { face: i });
+            }
+        }
+
+        // The loops of the front and back side of each face. Both start at the first vertex.
+        let loops = faces
+            .iter()
+            .flat_map(|(ids, _)| {
+                let back = std::iter::once(ids[0])
+                    .chain(ids[1..].iter().rev().copied())
+                    .collect_vec();
+                [ids.clone(), back]
+            })
+            .collect_vec();
+
+        let halfedges = loops
+            .iter()
+            .map(|l| l.iter().map(|_| self.allocate_halfedge()).collect_vec())
+            .collect_vec();
+
+        // every directed edge is used by exactly one side of each face around it
+        let mut directed: HashMap<(usize, usize, usize), T::E> = HashMap::new();
+        let mut origin: HashMap<T::E, usize> = HashMap::new();
+        let mut prev: HashMap<T::E, T::E> = HashMap::new();
+        let mut next: HashMap<T::E, T::E> = HashMap::new();
+        for (side, l) in loops.iter().enumerate() {
+            let n = l.len();
+            for j in 0..n {
+                let e = halfedges[side][j];
+                directed.insert((side / 2, l[j], l[(j + 1) % n]), e);
+                origin.insert(e, l[j]);
+                prev.insert(e, halfedges[side][(j + n - 1) % n]);
+                next.insert(e, halfedges[side][(j + 1) % n]);
+            }
+        }
+
+        let mut edge_faces: HashMap<(usize, usize), Vec<usize>> = HashMap::new();
+        for (i, (ids, _)) in faces.iter().enumerate() {
+            for (&a, &b) in ids.iter().circular_tuple_windows() {
+                edge_faces.entry(undirected(a, b)).or_default().push(i);
+            }
+        }
+
+        // glue the side of each face going from a to b with the side of the next face going back
+        let mut twin: HashMap<T::E, T::E> = HashMap::new();
+        for (&(a, b), fs) in edge_faces.iter() {
+            for (&f1, &f2) in fs.iter().circular_tuple_windows() {
+                let e1 = directed[&(f1, a, b)];
+                let e2 = directed[&(f2, b, a)];
+                twin.insert(e1, e2);
+                twin.insert(e2, e1);
+            }
+        }
+
+        // split the vertices into one vertex per fan of outgoing halfedges
+        let mut vertex_of: HashMap<T::E, T::V> = HashMap::new();
+        let mut vertex_origin = HashMap::new();
+        let mut representatives: Vec<T::V> = vec![IndexType::max(); vps.len()];
+        for e in halfedges.iter().flatten() {
+            if vertex_of.contains_key(e) {
+                continue;
+            }
+            let v = self.allocate_vertex();
+            let original = origin[e];
+            self.vertices
+                .set(v, HalfEdgeVertexImpl::new(*e, vps[original].clone()));
+            vertex_origin.insert(v, original);
+            if representatives[original] == IndexType::max() {
+                representatives[original] = v;
+            }
+            let mut current = *e;
+            loop {
+                vertex_of.insert(current, v);
+                current = twin[&prev[&current]];
+                if current == *e {
+                    break;
+                }
+            }
+        }
+
+        // isolated vertices are still part of the original topology
+        for (i, vp) in vps.iter().enumerate() {
+            if representatives[i] == IndexType::max() {
+                let v = self.add_vertex(vp.clone());
+                vertex_origin.insert(v, i);
+                representatives[i] = v;
+            }
+        }
+
+        let mut face_origin = HashMap::new();
+        let mut sides = Vec::with_capacity(faces.len());
+        for (i, (_, fp)) in faces.iter().enumerate() {
+            let mut side = |back: bool| {
+                let f = self.allocate_face();
+                let s = 2 * i + back as usize;
+                self.faces
+                    .set(f, HalfEdgeFaceImpl::new(halfedges[s][0], false, *fp));
+                face_origin.insert(f, (i, back));
+                for &e in halfedges[s].iter() {
+                    self.insert_halfedge_no_update_no_check(
+                        e,
+                        vertex_of[&e],
+                        f,
+                        prev[&e],
+                        twin[&e],
+                        next[&e],
+                        T::EP::default(),
+                    );
+                }
+                f
+            };
+            let front = side(false);
+            let back = side(true);
+            sides.push((front, back));
+        }
+
+        Ok(TuftedCover {
+            vertex_origin,
+            representatives,
+            face_origin,
+            sides,
+            edge_faces,
+        })
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "nalgebra")]
+mod tests {
+    use crate::{extensions::nalgebra::*, prelude::*};
+
+    fn fin() -> (
+        Vec<VertexPayloadPNU<f64, 3>>,
+        Vec<(Vec<usize>, EmptyFacePayload<MeshType3d64PNU>)>,
+    ) {
+        let vps = [
+            [0.0, 0.0, 0.0],
+            [1.0, 0.0, 0.0],
+            [0.5, 1.0, 0.0],
+            [0.5, -1.0, 0.0],
+            [0.5, 0.0, 1.0],
+        ]
+        .iter()
+        .map(|p| VertexPayloadPNU::from_pos(Vec3::new(p[0], p[1], p[2])))
+        .collect();
+        // a plate of two triangles and a fin attached to the shared edge
+        let faces = [vec![0, 1, 2], vec![1, 0, 3], vec![0, 1, 4]]
+            .into_iter()
+            .map(|f| (f, EmptyFacePayload::default()))
+            .collect();
+        (vps, faces)
+    }
+
+    #[test]
+    fn test_tufted_cover_fin() {
+        let (vps, faces) = fin();
+        assert!(matches!(
+            Mesh3d64::new().insert_indexed_faces(vps.clone(), faces.clone()),
+            Err(IndexedFaceError::NonManifoldEdge { .. })
+        ));
+
+        let mut mesh = Mesh3d64::new();
+        let cover = mesh
+            .insert_tufted_cover(vps.clone(), faces.clone())
+            .unwrap();
+        assert!(mesh.check().is_ok());
+        assert_eq!(mesh.num_faces(), 6);
+        assert_eq!(cover.num_faces(), 3);
+        assert!(mesh.edges().all(|e| !e.is_boundary_self()));
+        assert_eq!(cover.non_manifold_edges().collect::<Vec<_>>(), vec![(0, 1)]);
+        assert_eq!(cover.faces_around_edge(1, 0), &[0, 1, 2]);
+
+        // each of the three faces contributes one manifold copy of the fin edge
+        let copies = mesh
+            .edges()
+            .filter(|e| cover.is_non_manifold(&mesh, e.id()))
+            .collect::<Vec<_>>();
+        assert_eq!(copies.len(), 6);
+        for e in copies {
+            assert_eq!(cover.true_faces(&mesh, e.id()), &[0, 1, 2]);
+            let f = e.face_id();
+            assert!(cover
+                .faces_around_edge(0, 1)
+                .contains(&cover.original_face(f)));
+        }
+
+        let (front, back) = cover.sides(2);
+        assert!(!cover.is_back(front) && cover.is_back(back));
+        assert!(
+            mesh.face(front)
+                .normal(&mesh)
+                .dot(&Vec3::new(0.0, 1.0, 0.0))
+                < 0.0
+        );
+        assert!(mesh.face(back).normal(&mesh).dot(&Vec3::new(0.0, 1.0, 0.0)) > 0.0);
+
+        let (exported_vps, exported_faces) = cover.to_indexed_faces(&mesh);
+        assert_eq!(exported_vps, vps);
+        assert_eq!(exported_faces, faces);
+    }
+
+    #[test]
+    fn test_tufted_cover_errors() {
+        let (vps, mut faces) = fin();
+        faces.push((vec![0, 5, 1], EmptyFacePayload::default()));
+        let mut mesh = Mesh3d64::new();
+        assert_eq!(
+            mesh.insert_tufted_cover(vps.clone(), faces).err(),
+            Some(IndexedFaceError::VertexOutOfRange { face: 3, vertex: 5 })
+        );
+        assert_eq!(mesh.num_vertices(), 0);
+
+        // a single triangle is glued to its own back side along all edges
+        let mut mesh = Mesh3d64::new();
+        let cover = mesh
+            .insert_tufted_cover(vps, [(vec![0, 1, 2], EmptyFacePayload::default())])
+            .unwrap();
+        assert!(mesh.check().is_ok());
+        assert_eq!(mesh.num_faces(), 2);
+        assert_eq!(mesh.num_edges(), 6);
+        assert_eq!(cover.num_vertices(), 5);
+        assert_eq!(cover.non_manifold_edges().count(), 0);
+    }
+}
@@ -4,7 +4,10 @@ use crate::{
         DefaultEdgePayload, DefaultFacePayload, EdgeBasics, EuclideanMeshType, FaceBasics,
         HalfEdge, MeshBasics, MeshBuilder, MeshPosition, MeshType3D, MeshTypeHalfEdge,
     },
-    operations::{MeshExtrude, MeshLoft, MeshSubdivision},
+    operations::{
+        MeshBevel, MeshBoolean, MeshExtrude, MeshInset, MeshLoft, MeshRevolve, MeshSubdivision,
+        MeshSweep,
+    },
     primitives::{Make2dShape, MakePlane, MakePrismatoid, MakeSphere},
 };
 
@@ -78,3 +81,36 @@ where
     T::FP: DefaultFacePayload,
 {
 }
+
+impl<T: HalfEdgeImplMeshType + MeshType3D> MeshBoolean<T> for HalfEdgeMeshImpl<T> where
+    T::EP: DefaultEdgePayload
+{
+}
+
+impl<T: HalfEdgeImplMeshType + MeshTypeHalfEdge + MeshType3D> MeshBevel<T> for HalfEdgeMeshImpl<T>
+where
+    T::EP: DefaultEdgePayload,
+    T::FP: DefaultFacePayload,
+{
+}
+
+impl<T: HalfEdgeImplMeshType + MeshTypeHalfEdge + MeshType3D> MeshInset<T> for HalfEdgeMeshImpl<T>
+where
+    T::EP: DefaultEdgePayload,
+    T::FP: DefaultFacePayload,
+{
+}
+
+impl<T: HalfEdgeImplMeshType + MeshTypeHalfEdge + MeshType3D> MeshSweep<T> for HalfEdgeMeshImpl<T>
+where
+    T::EP: DefaultEdgePayload,
+    T::FP: DefaultFacePayload,
+{
+}
+
+impl<T: HalfEdgeImplMeshType + MeshTypeHalfEdge + MeshType3D> MeshRevolve<T> for HalfEdgeMeshImpl<T>
+where
+    T::EP: DefaultEdgePayload,
+    T::FP: DefaultFacePayload,
+{
+}
@@ -12,6 +12,14 @@ use crate::{
 
 /// A vertex in a mesh.
 #[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(
+        serialize = "T::V: serde::Serialize, T::E: serde::Serialize, T::VP: serde::Serialize",
+        deserialize = "T::V: serde::Deserialize<'de>, T::E: serde::Deserialize<'de>, T::VP: serde::Deserialize<'de>"
+    ))
+)]
 pub struct HalfEdgeVertexImpl<T: HalfEdgeImplMeshType> {
     /// the index of the vertex
     id: T::V,
@@ -11,6 +11,10 @@ pub mod primitives;
 pub mod tesselate;
 pub mod util;
 
+#[cfg(test)]
+#[cfg(feature = "nalgebra")]
+pub(crate) mod test_util;
+
 /// A prelude for easy importing of commonly used types and traits.
 pub mod prelude {
     pub use crate::halfedge::*;
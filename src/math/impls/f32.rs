@@ -67,6 +67,11 @@ impl Scalar for f32 {
         value as f32
     }
 
+    #[inline(always)]
+    fn from_f64(value: f64) -> Self {
+        value as f32
+    }
+
     #[inline(always)]
     fn max(&self, b: Self) -> Self {
         f32::max(*self, b)
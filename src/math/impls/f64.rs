@@ -67,6 +67,11 @@ impl Scalar for f64 {
         value as f64
     }
 
+    #[inline(always)]
+    fn from_f64(value: f64) -> Self {
+        value
+    }
+
     #[inline(always)]
     fn max(&self, b: Self) -> Self {
         f64::max(*self, b)
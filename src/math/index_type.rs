@@ -19,6 +19,22 @@ pub trait IndexType:
 
     /// Return the maximum value of the index type.
     fn max() -> Self;
+
+    /// Whether the index carries a generation. Containers only track generations if this is true.
+    const GENERATIONAL: bool = false;
+
+    /// Returns the generation of the index. Always 0 for plain integer indices.
+    #[inline(always)]
+    fn generation(&self) -> u32 {
+        0
+    }
+
+    /// Returns the same index with the given generation.
+    /// Plain integer indices ignore the generation.
+    #[inline(always)]
+    fn with_generation(self, _generation: u32) -> Self {
+        self
+    }
 }
 
 impl IndexType for usize {
@@ -91,3 +107,142 @@ impl IndexType for u8 {
         ::std::u8::MAX
     }
 }
+
+/// An index with a generation counter.
+///
+/// `DeletableVector` increments the generation of a slot whenever its element is deleted.
+/// Using this as the vertex, edge, or face index type of a mesh makes accessing elements
+/// via stale handles panic instead of silently returning the element that reused the slot.
+#[derive(Clone, Copy, Default, Hash, PartialEq, Eq, PartialOrd, Ord, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Generational<I: IndexType> {
+    index: I,
+    generation: u32,
+}
+
+impl<I: IndexType> std::fmt::Display for Generational<I> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}#{}", self.index, self.generation)
+    }
+}
+
+impl<I: IndexType> std::ops::Add for Generational<I> {
+    type Output = Self;
+
+    /// Adds the indices. The sum refers to a different slot than either operand,
+    /// so, like `IndexType::new`, it has generation 0 and isn't a valid handle
+    /// unless the slot is still in its first generation.
+    fn add(self, rhs: Self) -> Self {
+        Self::new(self.index.index() + rhs.index.index())
+    }
+}
+
+impl<I: IndexType> num_traits::Zero for Generational<I> {
+    fn zero() -> Self {
+        Self::new(0)
+    }
+
+    fn is_zero(&self) -> bool {
+        self.index.is_zero()
+    }
+}
+
+impl<I: IndexType> IndexType for Generational<I> {
+    const GENERATIONAL: bool = true;
+
+    #[inline(always)]
+    fn new(x: usize) -> Self {
+        Self {
+            index: I::new(x),
+            generation: 0,
+        }
+    }
+
+    #[inline(always)]
+    fn index(&self) -> usize {
+        self.index.index()
+    }
+
+    #[inline(always)]
+    fn max() -> Self {
+        Self {
+            index: <I as IndexType>::max(),
+            generation: 0,
+        }
+    }
+
+    #[inline(always)]
+    fn generation(&self) -> u32 {
+        self.generation
+    }
+
+    #[inline(always)]
+    fn with_generation(self, generation: u32) -> Self {
+        Self {
+            index: self.index,
+            generation,
+        }
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "nalgebra")]
+mod tests {
+    use crate::{extensions::nalgebra::*, prelude::*};
+
+    #[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+    struct GenerationalMeshType;
+
+    impl MeshType for GenerationalMeshType {
+        type E = Generational<usize>;
+        type V = Generational<usize>;
+        type F = Generational<u32>;
+        type EP = EmptyEdgePayload<Self>;
+        type VP = VertexPayloadPNU<f64, 3>;
+        type FP = EmptyFacePayload<Self>;
+        type MP = EmptyMeshPayload<Self>;
+        type Mesh = HalfEdgeMeshImpl<Self>;
+        type Face = HalfEdgeFaceImpl<Self>;
+        type Edge = HalfEdgeImpl<Self>;
+        type Vertex = HalfEdgeVertexImpl<Self>;
+    }
+    impl HalfEdgeImplMeshType for GenerationalMeshType {}
+
+    #[test]
+    fn test_stale_handles() {
+        let mut mesh = HalfEdgeMeshImpl::<GenerationalMeshType>::new();
+        let pos = |x: f64, y: f64| VertexPayloadPNU::from_pos(Vec3::new(x, y, 0.0));
+        let e = mesh.insert_loop([pos(0.0, 0.0), pos(1.0, 0.0), pos(0.0, 1.0)]);
+        let inside = mesh.edge(e).twin_id();
+        let f = mesh.close_hole_default(inside);
+        let e2 = mesh.insert_loop([pos(2.0, 0.0), pos(3.0, 0.0), pos(2.0, 1.0)]);
+        let g = mesh.close_hole_default(mesh.edge(e2).twin_id());
+        assert!(mesh.try_face(f).is_some());
+
+        // the new face reuses the slot, but the old handle doesn't alias it
+        mesh.remove_face(f);
+        let h = mesh.close_hole_default(inside);
+        assert_eq!(h.index(), f.index());
+        assert_ne!(h, f);
+        assert!(mesh.try_face(f).is_none());
+        assert!(mesh.try_face(h).is_some());
+        let stale = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            mesh.face(f);
+        }));
+        assert!(stale.is_err());
+        assert!(mesh.check().is_ok());
+
+        // compacting invalidates all old handles
+        let (vs, _, fs) = mesh.compact();
+        assert!(mesh.check().is_ok());
+        assert!(mesh.try_face(g).is_none());
+        assert!(mesh.try_face(fs[&g]).is_some());
+        assert!(mesh.try_face(fs[&h]).is_some());
+        assert!(mesh.vertex_ids().all(|v| vs.values().any(|&w| w == v)));
+        assert!(mesh.vertex_ids().all(|v| mesh.try_vertex(v).is_some()));
+
+        // offsets don't inherit the generation of the slot they started from
+        assert_ne!(fs[&h].generation(), 0);
+        assert_eq!((fs[&h] + Generational::new(1)).generation(), 0);
+    }
+}
@@ -75,6 +75,18 @@ pub trait Scalar:
     /// Converts a usize to the scalar.
     fn from_usize(value: usize) -> Self;
 
+    /// Converts a 64-bit floating point number to the scalar.
+    ///
+    /// The default implementation goes through two `f32`s, the rounded value and its residual,
+    /// so implementors only have to provide `From<f32>`.
+    fn from_f64(value: f64) -> Self {
+        let hi = value as f32;
+        if !hi.is_finite() {
+            return Self::from(hi);
+        }
+        Self::from(hi) + Self::from((value - hi as f64) as f32)
+    }
+
     /// Returns the absolute value of the scalar.
     fn abs(self) -> Self {
         if self.is_positive() {
@@ -5,6 +5,14 @@ use crate::{
 
 /// The type of curve that the edge represents.
 #[derive(Clone, Default, Copy, Debug, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(
+        serialize = "T::Vec: serde::Serialize",
+        deserialize = "T::Vec: serde::Deserialize<'de>"
+    ))
+)]
 pub enum CurvedEdgeType<const D: usize, T: EuclideanMeshType<D>> {
     /// A linear edge
     #[default]
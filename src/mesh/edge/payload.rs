@@ -1,7 +1,7 @@
 use std::hash::Hash;
 
 use crate::{
-    math::Transformable,
+    math::{Scalar, TransformTrait, Transformable},
     mesh::{EuclideanMeshType, MeshType},
 };
 
@@ -20,8 +20,23 @@ pub trait EdgePayload: Clone + std::fmt::Debug + PartialEq {
 /// For example, when extruding, it is ok for all new edges to have the same default payload.
 pub trait DefaultEdgePayload: EdgePayload + Default {}
 
+/// Edge payloads with a crease weight, i.e., the sharpness of the edge during subdivision.
+///
+/// The sharpness counts subdivision levels: `0` is smooth, every subdivision step
+/// reduces the sharpness of the new edges by one, and `f32::INFINITY` is sharp forever.
+/// Both halfedges of an edge should have the same crease weight, otherwise the larger one is used.
+pub trait HasCrease: EdgePayload {
+    /// Returns the sharpness of the edge.
+    fn crease(&self) -> f32;
+
+    /// Returns the payload for both halves of the edge after one subdivision step.
+    fn subdivided(&self) -> Self;
+}
+
 /// An empty edge payload if you don't need any additional information.
 #[derive(Debug, Clone, Copy, PartialEq, Default, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound = ""))]
 pub struct EmptyEdgePayload<T: MeshType> {
     _phantom: std::marker::PhantomData<T>,
 }
@@ -39,6 +54,16 @@ impl<T: MeshType> EdgePayload for EmptyEdgePayload<T> {
 
 impl<T: MeshType> DefaultEdgePayload for EmptyEdgePayload<T> {}
 
+impl<T: MeshType> HasCrease for EmptyEdgePayload<T> {
+    fn crease(&self) -> f32 {
+        0.0
+    }
+
+    fn subdivided(&self) -> Self {
+        *self
+    }
+}
+
 impl<const D: usize, T: EuclideanMeshType<D>> Transformable<D> for EmptyEdgePayload<T> {
     type Rot = T::Rot;
     type S = T::S;
@@ -56,6 +81,14 @@ impl<const D: usize, T: EuclideanMeshType<D>> Transformable<D> for EmptyEdgePayl
 
 /// A curved edge payload with nothing else
 #[derive(Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(
+        serialize = "T::Vec: serde::Serialize",
+        deserialize = "T::Vec: serde::Deserialize<'de>"
+    ))
+)]
 pub struct CurvedEdgePayload<const D: usize, T: EuclideanMeshType<D>> {
     curve: CurvedEdgeType<D, T>,
 }
@@ -88,6 +121,17 @@ impl<const D: usize, T: EuclideanMeshType<D>> EdgePayload for CurvedEdgePayload<
 
 impl<const D: usize, T: EuclideanMeshType<D>> DefaultEdgePayload for CurvedEdgePayload<D, T> {}
 
+impl<const D: usize, T: EuclideanMeshType<D>> HasCrease for CurvedEdgePayload<D, T> {
+    fn crease(&self) -> f32 {
+        0.0
+    }
+
+    fn subdivided(&self) -> Self {
+        // TODO: split the curve
+        self.clone()
+    }
+}
+
 impl<const D: usize, T: EuclideanMeshType<D>> Transformable<D> for CurvedEdgePayload<D, T> {
     type Rot = T::Rot;
     type S = T::S;
@@ -121,3 +165,221 @@ impl<const D: usize, T: EuclideanMeshType<D>> Transformable<D> for CurvedEdgePay
         self
     }
 }
+
+/// Gives access to the attributes of the face corner at the origin of a halfedge.
+///
+/// Corner attributes override the attributes of the vertex in the face of the halfedge.
+/// This way, a vertex can have different uv coordinates, normals, or colors in each of
+/// its faces, e.g., at uv seams or hard edges, without splitting the topology.
+/// `None` means the corner uses the attribute of the vertex.
+pub trait CornerPayload<const D: usize, T: EuclideanMeshType<D>>: EdgePayload {
+    /// Returns the uv coordinates of the corner.
+    fn corner_uv(&self) -> Option<T::Vec2>;
+
+    /// Returns the normal of the corner.
+    fn corner_normal(&self) -> Option<T::Vec>;
+
+    /// Returns the RGBA color of the corner.
+    fn corner_color(&self) -> Option<[f32; 4]>;
+
+    /// Returns whether both corners have the same attributes, i.e., they can share an exported vertex.
+    fn same_corner(&self, other: &Self) -> bool {
+        self.corner_uv() == other.corner_uv()
+            && self.corner_normal() == other.corner_normal()
+            && self.corner_color() == other.corner_color()
+    }
+}
+
+/// An edge payload with optional uv coordinates, normal, and color of the face corner at the origin of the halfedge.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(
+        serialize = "T::Vec: serde::Serialize, T::Vec2: serde::Serialize",
+        deserialize = "T::Vec: serde::Deserialize<'de>, T::Vec2: serde::Deserialize<'de>"
+    ))
+)]
+pub struct CornerEdgePayload<const D: usize, T: EuclideanMeshType<D>> {
+    uv: Option<T::Vec2>,
+    normal: Option<T::Vec>,
+    color: Option<[f32; 4]>,
+}
+
+impl<const D: usize, T: EuclideanMeshType<D>> CornerEdgePayload<D, T> {
+    /// Sets the uv coordinates of the corner. `None` falls back to the vertex.
+    pub fn set_corner_uv(&mut self, uv: Option<T::Vec2>) {
+        self.uv = uv;
+    }
+
+    /// Sets the normal of the corner. `None` falls back to the vertex.
+    pub fn set_corner_normal(&mut self, normal: Option<T::Vec>) {
+        self.normal = normal;
+    }
+
+    /// Sets the RGBA color of the corner. `None` falls back to the vertex.
+    pub fn set_corner_color(&mut self, color: Option<[f32; 4]>) {
+        self.color = color;
+    }
+}
+
+impl<const D: usize, T: EuclideanMeshType<D>> Default for CornerEdgePayload<D, T> {
+    fn default() -> Self {
+        Self {
+            uv: None,
+            normal: None,
+            color: None,
+        }
+    }
+}
+
+impl<const D: usize, T: EuclideanMeshType<D>> EdgePayload for CornerEdgePayload<D, T> {
+    fn allocate() -> Self {
+        Default::default()
+    }
+    fn is_empty(&self) -> bool {
+        self.uv.is_none() && self.normal.is_none() && self.color.is_none()
+    }
+}
+
+impl<const D: usize, T: EuclideanMeshType<D>> DefaultEdgePayload for CornerEdgePayload<D, T> {}
+
+impl<const D: usize, T: EuclideanMeshType<D>> HasCrease for CornerEdgePayload<D, T> {
+    fn crease(&self) -> f32 {
+        0.0
+    }
+
+    fn subdivided(&self) -> Self {
+        self.clone()
+    }
+}
+
+impl<const D: usize, T: EuclideanMeshType<D>> CornerPayload<D, T> for CornerEdgePayload<D, T> {
+    fn corner_uv(&self) -> Option<T::Vec2> {
+        self.uv
+    }
+
+    fn corner_normal(&self) -> Option<T::Vec> {
+        self.normal
+    }
+
+    fn corner_color(&self) -> Option<[f32; 4]> {
+        self.color
+    }
+}
+
+impl<const D: usize, T: EuclideanMeshType<D>> Transformable<D> for CornerEdgePayload<D, T> {
+    type Rot = T::Rot;
+    type S = T::S;
+    type Trans = T::Trans;
+    type Vec = T::Vec;
+
+    fn transform(&mut self, t: &Self::Trans) -> &mut Self {
+        self.normal = self.normal.map(|n| t.apply_vec(n));
+        self
+    }
+
+    fn lerp(&mut self, other: &Self, t: Self::S) -> &mut Self {
+        self.uv = match (self.uv, other.uv) {
+            (Some(a), Some(b)) => Some(a + (b - a) * t),
+            (a, b) => a.or(b),
+        };
+        self.normal = match (self.normal, other.normal) {
+            (Some(a), Some(b)) => Some(a + (b - a) * t),
+            (a, b) => a.or(b),
+        };
+        self.color = match (self.color, other.color) {
+            (Some(a), Some(b)) => {
+                let t = t.to_f64() as f32;
+                Some(std::array::from_fn(|i| a[i] + (b[i] - a[i]) * t))
+            }
+            (a, b) => a.or(b),
+        };
+        self
+    }
+}
+
+/// An edge payload with a crease weight, see `HasCrease`.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound = ""))]
+pub struct CreaseEdgePayload<T: MeshType> {
+    crease: f32,
+    _phantom: std::marker::PhantomData<T>,
+}
+
+impl<T: MeshType> CreaseEdgePayload<T> {
+    /// Creates a new payload with the given sharpness.
+    pub fn new(crease: f32) -> Self {
+        assert!(crease >= 0.0, "The crease weight must not be negative");
+        Self {
+            crease,
+            _phantom: std::marker::PhantomData,
+        }
+    }
+
+    /// Sets the sharpness of the edge.
+    pub fn set_crease(&mut self, crease: f32) {
+        assert!(crease >= 0.0, "The crease weight must not be negative");
+        self.crease = crease;
+    }
+}
+
+impl<T: MeshType> EdgePayload for CreaseEdgePayload<T> {
+    fn allocate() -> Self {
+        Default::default()
+    }
+    fn is_empty(&self) -> bool {
+        self.crease == 0.0
+    }
+}
+
+impl<T: MeshType> DefaultEdgePayload for CreaseEdgePayload<T> {}
+
+impl<T: MeshType> HasCrease for CreaseEdgePayload<T> {
+    fn crease(&self) -> f32 {
+        self.crease
+    }
+
+    fn subdivided(&self) -> Self {
+        Self::new((self.crease - 1.0).max(0.0))
+    }
+}
+
+impl<const D: usize, T: EuclideanMeshType<D>> Transformable<D> for CreaseEdgePayload<T> {
+    type Rot = T::Rot;
+    type S = T::S;
+    type Trans = T::Trans;
+    type Vec = T::Vec;
+
+    fn transform(&mut self, _: &Self::Trans) -> &mut Self {
+        self
+    }
+
+    fn lerp(&mut self, other: &Self, t: Self::S) -> &mut Self {
+        self.crease += (other.crease - self.crease) * t.to_f64() as f32;
+        self
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "nalgebra")]
+mod tests {
+    use crate::{extensions::nalgebra::*, prelude::*};
+
+    #[test]
+    fn test_corner_lerp() {
+        let mut a = CornerEdgePayload::<3, MeshType3d64PNUCorner>::default();
+        a.set_corner_uv(Some(Vec2::new(0.0, 0.0)));
+        a.set_corner_color(Some([1.0, 0.0, 0.0, 1.0]));
+        let mut b = CornerEdgePayload::<3, MeshType3d64PNUCorner>::default();
+        b.set_corner_uv(Some(Vec2::new(1.0, 0.0)));
+        b.set_corner_normal(Some(Vec3::new(0.0, 0.0, 1.0)));
+        b.set_corner_color(Some([0.0, 0.0, 1.0, 1.0]));
+
+        a.lerp(&b, 0.25);
+        assert_eq!(a.corner_uv(), Some(Vec2::new(0.25, 0.0)));
+        assert_eq!(a.corner_normal(), Some(Vec3::new(0.0, 0.0, 1.0)));
+        assert_eq!(a.corner_color(), Some([0.75, 0.0, 0.25, 1.0]));
+    }
+}
@@ -5,7 +5,7 @@ use super::MeshType;
 /// A face in a mesh.
 ///
 /// Isn't necessarily planar or triangular.
-pub trait FaceBasics<T: MeshType<Face = Self>>: std::fmt::Debug + Clone + Copy {
+pub trait FaceBasics<T: MeshType<Face = Self>>: std::fmt::Debug + Clone {
     /// Returns the index of the face.
     fn id(&self) -> T::F;
 
@@ -21,13 +21,13 @@ pub trait FaceBasics<T: MeshType<Face = Self>>: std::fmt::Debug + Clone + Copy {
     /// Whether the face is allowed to be curved.
     fn may_be_curved(&self) -> bool;
 
-    /// Get the number of edges of the face.
+    /// Get the number of edges of the outer boundary of the face.
     fn num_edges(&self, mesh: &T::Mesh) -> usize;
 
-    /// Get the number of vertices of the face.
+    /// Get the number of vertices of the outer boundary of the face.
     fn num_vertices(&self, mesh: &T::Mesh) -> usize;
 
-    /// Get the number of triangles of the face. (n-2)*3
+    /// Get the number of triangles of the face including its holes. (n+2h-2)*3
     fn num_triangles(&self, mesh: &T::Mesh) -> usize;
 
     /// Returns the face payload.
@@ -36,7 +36,7 @@ pub trait FaceBasics<T: MeshType<Face = Self>>: std::fmt::Debug + Clone + Copy {
     /// Returns a mutable reference to the face payload.
     fn payload_mut(&mut self) -> &mut T::FP;
 
-    /// Iterates all vertices adjacent to the face
+    /// Iterates all vertices on the outer boundary of the face
     fn vertices<'a>(
         &'a self,
         mesh: &'a T::Mesh,
@@ -51,15 +51,43 @@ pub trait FaceBasics<T: MeshType<Face = Self>>: std::fmt::Debug + Clone + Copy {
     }
 
     /// Whether the face has holes.
-    /// The data structure (currently!) cannot represent holes, so this is always false.
     fn has_holes(&self) -> bool {
-        return false;
+        self.num_holes() > 0
     }
 
-    /// Iterates all half-edges incident to the face
+    /// Returns the number of holes, i.e., inner boundary loops, of the face.
+    ///
+    /// Defaults to no holes for faces that can't store any.
+    fn num_holes(&self) -> usize {
+        0
+    }
+
+    /// Iterates the id of one half-edge of each hole of the face.
+    fn hole_edge_ids(&self) -> impl Iterator<Item = T::E> + '_ {
+        std::iter::empty()
+    }
+
+    /// Iterates the half-edges of the `hole`-th hole of the face.
+    fn hole_edges<'a>(&'a self, _mesh: &'a T::Mesh, _hole: usize) -> impl Iterator<Item = T::Edge> {
+        std::iter::empty()
+    }
+
+    /// Iterates the vertices of the `hole`-th hole of the face.
+    fn hole_vertices<'a>(
+        &'a self,
+        mesh: &'a T::Mesh,
+        hole: usize,
+    ) -> impl Iterator<Item = T::Vertex> + 'a
+    where
+        T: 'a,
+    {
+        self.hole_edges(mesh, hole).map(|e| e.target(mesh).clone())
+    }
+
+    /// Iterates all half-edges on the outer boundary of the face
     fn edges<'a>(&'a self, mesh: &'a T::Mesh) -> impl Iterator<Item = T::Edge>;
 
-    /// Iterates all half-edge ids incident to the face
+    /// Iterates all half-edge ids on the outer boundary of the face
     fn edge_ids<'a>(&'a self, mesh: &'a T::Mesh) -> impl Iterator<Item = T::E> + 'a
     where
         T: 'a,
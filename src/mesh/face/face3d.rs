@@ -120,21 +120,46 @@ pub trait Face3d<T: MeshType3D<Face = Self>>: FaceBasics<T> {
         self.vertices(mesh).map(|v| *v.vertex())
     }*/
 
+    /// Get the rotation that maps the face to the XY plane.
+    fn xy_rotation(&self, mesh: &T::Mesh) -> T::Trans {
+        // PERF: Calculating the normal from all vertices in the face is slow. Maybe use a faster normal impl? Also, we're traversing the face twice!
+        let z_axis = T::Vec::new(0.0.into(), 0.0.into(), 1.0.into());
+        <T::Trans as TransformTrait<T::S, 3>>::from_rotation_arc(
+            Face3d::normal(self, mesh).normalize(),
+            z_axis.normalize(),
+        )
+    }
+
     /// Get an iterator over the 2d vertices of the face rotated to the XY plane.
     fn vertices_2d<'a>(
         &'a self,
         mesh: &'a T::Mesh,
     ) -> impl Iterator<Item = (T::Vec2, T::V)> + Clone + ExactSizeIterator + 'a {
-        // PERF: Calculating the normal from all vertices in the face is slow. Maybe use a faster normal impl? Also, we're traversing the face twice!
-        let z_axis = T::Vec::new(0.0.into(), 0.0.into(), 1.0.into());
-        let rotation = <T::Trans as TransformTrait<T::S, 3>>::from_rotation_arc(
-            Face3d::normal(self, mesh).normalize(),
-            z_axis.normalize(),
-        );
+        let rotation = self.xy_rotation(mesh);
         self.vertices(mesh)
             .map(move |v| (rotation.apply(v.pos()).vec2(), v.id()))
     }
 
+    /// Get a vector of the 2d vertices of the outer boundary followed by the
+    /// vertices of each hole, rotated to the XY plane.
+    /// Also returns the index of the first vertex of each hole in that vector.
+    fn vec2s_with_holes(
+        &self,
+        mesh: &T::Mesh,
+    ) -> (Vec<IndexedVertex2D<T::V, T::Vec2>>, Vec<usize>) {
+        let rotation = self.xy_rotation(mesh);
+        let to_2d = |v: T::Vertex| {
+            IndexedVertex2D::<T::V, T::Vec2>::new(rotation.apply(v.pos()).vec2(), v.id())
+        };
+        let mut vec2s: Vec<_> = self.vertices(mesh).map(to_2d).collect();
+        let mut hole_starts = Vec::with_capacity(self.num_holes());
+        for hole in 0..self.num_holes() {
+            hole_starts.push(vec2s.len());
+            vec2s.extend(self.hole_vertices(mesh, hole).map(to_2d));
+        }
+        (vec2s, hole_starts)
+    }
+
     /// Get a vector of 2d vertices of the face rotated to the XY plane.
     fn vec2s<'a>(&'a self, mesh: &'a T::Mesh) -> Vec<IndexedVertex2D<T::V, T::Vec2>> {
         self.vertices_2d(mesh)
@@ -13,8 +13,22 @@ pub trait FacePayload: Clone + Copy + PartialEq + Eq + std::fmt::Debug {
 /// For example, when extruding, it is ok for all new faces to have the same default payload.
 pub trait DefaultFacePayload: FacePayload + Default {}
 
+/// Face payloads with a smoothing group.
+///
+/// Normals are smooth across edges between faces of the same group and hard everywhere else.
+/// Group `0` means that the face has no smoothing group, i.e., it is always flat.
+/// This is also the default of `SmoothingGroupFacePayload` and of payloads without groups.
+pub trait HasSmoothingGroup: FacePayload {
+    /// Returns the smoothing group of the face.
+    fn smoothing_group(&self) -> u32 {
+        0
+    }
+}
+
 /// An empty face payload if you don't need any additional information.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound = ""))]
 pub struct EmptyFacePayload<T: MeshType> {
     _phantom: std::marker::PhantomData<T>,
 }
@@ -29,6 +43,8 @@ impl<T: MeshType> FacePayload for EmptyFacePayload<T> {
 
 impl<T: MeshType> DefaultFacePayload for EmptyFacePayload<T> {}
 
+impl<T: MeshType> HasSmoothingGroup for EmptyFacePayload<T> {}
+
 impl<const D: usize, T: EuclideanMeshType<D>> Transformable<D> for EmptyFacePayload<T> {
     type Rot = T::Rot;
     type S = T::S;
@@ -43,3 +59,57 @@ impl<const D: usize, T: EuclideanMeshType<D>> Transformable<D> for EmptyFacePayl
         self
     }
 }
+
+/// A face payload with a smoothing group, see `HasSmoothingGroup`.
+/// By default, the face has no smoothing group and is flat.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound = ""))]
+pub struct SmoothingGroupFacePayload<T: MeshType> {
+    group: u32,
+    _phantom: std::marker::PhantomData<T>,
+}
+
+impl<T: MeshType> SmoothingGroupFacePayload<T> {
+    /// Creates a new payload with the given smoothing group.
+    pub fn new(group: u32) -> Self {
+        Self {
+            group,
+            _phantom: std::marker::PhantomData,
+        }
+    }
+
+    /// Sets the smoothing group of the face.
+    pub fn set_smoothing_group(&mut self, group: u32) {
+        self.group = group;
+    }
+}
+
+impl<T: MeshType> FacePayload for SmoothingGroupFacePayload<T> {
+    fn allocate() -> Self {
+        Default::default()
+    }
+}
+
+impl<T: MeshType> DefaultFacePayload for SmoothingGroupFacePayload<T> {}
+
+impl<T: MeshType> HasSmoothingGroup for SmoothingGroupFacePayload<T> {
+    fn smoothing_group(&self) -> u32 {
+        self.group
+    }
+}
+
+impl<const D: usize, T: EuclideanMeshType<D>> Transformable<D> for SmoothingGroupFacePayload<T> {
+    type Rot = T::Rot;
+    type S = T::S;
+    type Trans = T::Trans;
+    type Vec = T::Vec;
+
+    fn transform(&mut self, _: &T::Trans) -> &mut Self {
+        self
+    }
+
+    fn lerp(&mut self, _: &Self, _: Self::S) -> &mut Self {
+        self
+    }
+}
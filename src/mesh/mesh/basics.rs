@@ -39,6 +39,18 @@ pub trait MeshBasics<T: MeshType<Mesh = Self>>: Default + std::fmt::Debug + Clon
     /// Returns a reference to the requested face
     fn face(&self, index: T::F) -> &T::Face;
 
+    /// Returns a reference to the requested vertex or `None` if it doesn't exist,
+    /// is deleted, or the handle is stale
+    fn try_vertex(&self, index: T::V) -> Option<&T::Vertex>;
+
+    /// Returns a reference to the requested edge or `None` if it doesn't exist,
+    /// is deleted, or the handle is stale
+    fn try_edge(&self, index: T::E) -> Option<&T::Edge>;
+
+    /// Returns a reference to the requested face or `None` if it doesn't exist,
+    /// is deleted, or the handle is stale
+    fn try_face(&self, index: T::F) -> Option<&T::Face>;
+
     /// Returns a mutable reference to the requested vertex
     fn vertex_mut(&mut self, index: T::V) -> &mut T::Vertex;
 
@@ -103,7 +115,7 @@ pub trait MeshBasics<T: MeshType<Mesh = Self>>: Default + std::fmt::Debug + Clon
     fn has_consecutive_vertex_ids(&self) -> bool {
         let mut last_id: usize = 0;
         for v in self.vertices() {
-            if v.id() != IndexType::new(last_id) {
+            if v.id().index() != last_id {
                 return false;
             }
             last_id += 1;
@@ -1,6 +1,84 @@
 use crate::mesh::{DefaultEdgePayload, DefaultFacePayload};
 
-use super::{MeshBasics, MeshType};
+use super::{MeshBasics, MeshType, ModellingError};
+
+/// Reasons why a list of indexed faces cannot be inserted into a mesh.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IndexedFaceError {
+    /// The face with the given index references a vertex that doesn't exist.
+    VertexOutOfRange {
+        /// The index of the face in the input.
+        face: usize,
+        /// The index of the missing vertex.
+        vertex: usize,
+    },
+
+    /// The face with the given index has less than three vertices or visits a vertex twice.
+    DegenerateFace {
+        /// The index of the face in the input.
+        face: usize,
+    },
+
+    /// The directed edge `from -> to` is used by more than one face, i.e.,
+    /// the edge is shared by more than two faces or the faces are inconsistently oriented.
+    NonManifoldEdge {
+        /// The index of the face in the input that used the edge a second time.
+        face: usize,
+        /// The index of the origin vertex of the edge.
+        from: usize,
+        /// The index of the target vertex of the edge.
+        to: usize,
+    },
+}
+
+impl std::fmt::Display for IndexedFaceError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            IndexedFaceError::VertexOutOfRange { face, vertex } => {
+                write!(f, "Face {} references non-existing vertex {}", face, vertex)
+            }
+            IndexedFaceError::DegenerateFace { face } => {
+                write!(f, "Face {} is degenerate", face)
+            }
+            IndexedFaceError::NonManifoldEdge { face, from, to } => write!(
+                f,
+                "Face {} uses the edge {} -> {} which is already used by another face",
+                face, from, to
+            ),
+        }
+    }
+}
+
+impl std::error::Error for IndexedFaceError {}
+
+/// Removes consecutive duplicates from a closed loop of indices.
+pub(crate) fn dedup_loop(mut indices: Vec<usize>) -> Vec<usize> {
+    indices.dedup();
+    while indices.len() > 1 && indices.first() == indices.last() {
+        indices.pop();
+    }
+    indices
+}
+
+/// Drops the vertices that are not used by any of the indexed faces and renumbers the faces.
+/// Returns the remaining vertices in the order of their first use.
+pub(crate) fn drop_unused_vertices<VP: Clone, FP>(
+    vps: &[VP],
+    faces: &mut [(Vec<usize>, FP)],
+) -> Vec<VP> {
+    let mut used = vec![usize::MAX; vps.len()];
+    let mut used_vps = Vec::new();
+    for (indices, _) in faces.iter_mut() {
+        for i in indices.iter_mut() {
+            if used[*i] == usize::MAX {
+                used[*i] = used_vps.len();
+                used_vps.push(vps[*i].clone());
+            }
+            *i = used[*i];
+        }
+    }
+    used_vps
+}
 
 // TODO: We need a half-edge independent way of inserting vertices and edges! Most difficult part: how to handle edge payloads?
 
@@ -24,9 +102,16 @@ pub trait MeshBuilder<T: MeshType<Mesh = Self>>: MeshBasics<T> {
     /// Removes the provided face.
     fn remove_face(&mut self, f: T::F) -> T::FP;
 
+    /// Like `remove_face`, but fails instead of panicking if the face doesn't exist.
+    fn try_remove_face(&mut self, f: T::F) -> Result<T::FP, ModellingError>;
+
     /// Close the open boundary with a single face. Doesn't create new edges or vertices.
     fn close_hole(&mut self, e: T::E, fp: T::FP, curved: bool) -> T::F;
 
+    /// Like `close_hole`, but fails if the edge doesn't exist, already has a face,
+    /// or its boundary has less than three edges.
+    fn try_close_hole(&mut self, e: T::E, fp: T::FP, curved: bool) -> Result<T::F, ModellingError>;
+
     /// Close the open boundary with a single face. Doesn't create new edges or vertices.
     fn close_hole_default(&mut self, e: T::E) -> T::F
     where
@@ -65,6 +150,40 @@ pub trait MeshBuilder<T: MeshType<Mesh = Self>>: MeshBasics<T> {
     /// add a new vertex and return it's id
     fn add_vertex(&mut self, vp: T::VP) -> T::V;
 
+    /// Inserts the vertices `vps` and one face for each list of indices into `vps`.
+    /// The faces must be oriented consistently (counter-clockwise) and
+    /// each edge can be shared by at most two faces.
+    ///
+    /// Returns the ids of the inserted vertices and faces in the order of the input.
+    /// If the faces don't describe an oriented manifold surface, the mesh is not modified.
+    fn insert_indexed_faces(
+        &mut self,
+        vps: impl IntoIterator<Item = T::VP>,
+        faces: impl IntoIterator<Item = (Vec<usize>, T::FP)>,
+    ) -> Result<(Vec<T::V>, Vec<T::F>), IndexedFaceError>
+    where
+        T::EP: DefaultEdgePayload;
+
+    /// Removes the faces `remove` and inserts `faces` between existing vertices instead.
+    /// Each face is given as its outer loop followed by the loops of its holes,
+    /// with the vertices in the same order as `FaceBasics::vertices` resp. `hole_vertices`.
+    ///
+    /// The first faces take over the ids and attributes of the removed faces in the same order.
+    /// Edges that are still used keep their id, payload, and attributes.
+    /// New edges get the default payload. Edges that are no longer adjacent to any face
+    /// and vertices without edges that were part of the removed faces are deleted.
+    ///
+    /// Returns the ids of the inserted faces in the order of the input.
+    /// If a face is degenerate or an edge would have more than one face on the same side,
+    /// the mesh is not modified.
+    fn try_replace_faces(
+        &mut self,
+        remove: impl IntoIterator<Item = T::F>,
+        faces: impl IntoIterator<Item = (Vec<Vec<T::V>>, T::FP)>,
+    ) -> Result<Vec<T::F>, ModellingError>
+    where
+        T::EP: DefaultEdgePayload;
+
     /// Same as `add_isolated_edge` but with default edge payloads
     fn add_isolated_edge_default(&mut self, a: T::VP, b: T::VP) -> (T::V, T::V)
     where
@@ -103,6 +222,16 @@ pub trait MeshHalfEdgeBuilder<T: MeshType<Mesh = Self>>: MeshBasics<T> {
         ep1: T::EP,
     ) -> (T::E, T::E);
 
+    /// Like `insert_edge_between`, but fails if a vertex doesn't exist, the vertices
+    /// are already connected, or one of them is not on the boundary.
+    fn try_insert_edge_between(
+        &mut self,
+        origin0: T::V,
+        ep0: T::EP,
+        origin1: T::V,
+        ep1: T::EP,
+    ) -> Result<(T::E, T::E), ModellingError>;
+
     /// Creates a new vertex based on `vp` and connects it to vertex `v` with a pair of halfedges.
     ///
     /// TODO: Docs
@@ -131,6 +260,16 @@ pub trait MeshHalfEdgeBuilder<T: MeshType<Mesh = Self>>: MeshBasics<T> {
     /// This will also update the neighbors of the new edge so the halfedge mesh is consistent.
     fn insert_edge(&mut self, inside: T::E, ep1: T::EP, outside: T::E, ep2: T::EP) -> (T::E, T::E);
 
+    /// Like `insert_edge`, but fails if an edge doesn't exist, the edges target the same vertex,
+    /// or they are not on the same face resp. boundary.
+    fn try_insert_edge(
+        &mut self,
+        inside: T::E,
+        ep1: T::EP,
+        outside: T::E,
+        ep2: T::EP,
+    ) -> Result<(T::E, T::E), ModellingError>;
+
     /// Inserts a single half edge with the given origin and target vertex.
     /// You can set next and prev to IndexType::max() to insert the id of the twin edge there.
     fn insert_halfedge_no_update_no_check(
@@ -161,6 +300,18 @@ pub trait MeshHalfEdgeBuilder<T: MeshType<Mesh = Self>>: MeshBasics<T> {
         curved: bool,
     ) -> (T::F, T::E, T::E);
 
+    /// Like `close_face`, but fails if the edge cannot be inserted (see `try_insert_edge`),
+    /// `inside` already has a face, or the new face would have less than three edges.
+    fn try_close_face(
+        &mut self,
+        inside: T::E,
+        ep1: T::EP,
+        outside: T::E,
+        ep2: T::EP,
+        fp: T::FP,
+        curved: bool,
+    ) -> Result<(T::F, T::E, T::E), ModellingError>;
+
     /// Close the face by connecting vertex `from` (coming from `prev`) with vertex `to`.
     /// Inserts a pair of halfedges between these two vertices.
     /// This will only work if the insertion is unambiguous without having to look at the vertex positions, i.e., this must be a manifold vertex!
@@ -176,6 +327,21 @@ pub trait MeshHalfEdgeBuilder<T: MeshType<Mesh = Self>>: MeshBasics<T> {
         curved: bool,
     ) -> (T::F, T::E, T::E);
 
+    /// Like `close_face_vertices`, but fails if there is no edge from `prev` to `from`,
+    /// the ingoing boundary edge of `to` is ambiguous, or `try_close_face` fails.
+    // takes the same arguments as `close_face_vertices` so both can be swapped freely
+    #[allow(clippy::too_many_arguments)]
+    fn try_close_face_vertices(
+        &mut self,
+        prev: T::V,
+        ep1: T::EP,
+        from: T::V,
+        ep2: T::EP,
+        to: T::V,
+        fp: T::FP,
+        curved: bool,
+    ) -> Result<(T::F, T::E, T::E), ModellingError>;
+
     /// Same as `close_face` but with default edge and face payloads
     fn close_face_default(
         &mut self,
@@ -186,6 +352,15 @@ pub trait MeshHalfEdgeBuilder<T: MeshType<Mesh = Self>>: MeshBasics<T> {
     where
         T::EP: DefaultEdgePayload,
         T::FP: DefaultFacePayload;
+
+    /// Turns the boundary loop of the halfedge `e` into a hole of the face `f`.
+    /// The loop must not belong to any face yet and must run clockwise
+    /// when looking at the face from the front, e.g., the twin of a loop
+    /// inserted with `insert_loop` in counter-clockwise order.
+    fn insert_hole(&mut self, f: T::F, e: T::E);
+
+    /// Like `insert_hole`, but fails if the face or edge doesn't exist or the loop already has a face.
+    fn try_insert_hole(&mut self, f: T::F, e: T::E) -> Result<(), ModellingError>;
 }
 
 /// Some basic operations to build meshes with halfedges.
@@ -1,7 +1,7 @@
-use super::{MeshBasics, MeshType};
+use super::{MeshBasics, MeshType, ModellingError};
 
 /// A trait for checking the consistency of a mesh.
 pub trait MeshChecker<T: MeshType<Mesh = Self>>: MeshBasics<T> {
     /// Checks the mesh for consistency
-    fn check(&self) -> Result<(), String>;
+    fn check(&self) -> Result<(), ModellingError>;
 }
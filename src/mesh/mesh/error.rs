@@ -0,0 +1,204 @@
+use super::IndexedFaceError;
+
+/// Reasons why a modelling operation failed or a mesh is inconsistent.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ModellingError {
+    /// The vertex with the given index doesn't exist or is deleted.
+    MissingVertex(usize),
+
+    /// The (half)edge with the given index doesn't exist or is deleted.
+    MissingEdge(usize),
+
+    /// The face with the given index doesn't exist or is deleted.
+    MissingFace(usize),
+
+    /// The connectivity of the mesh is broken or the operation would break it.
+    InvalidTopology(String),
+
+    /// The operation would create a non-manifold edge or vertex.
+    NonManifold(String),
+
+    /// A face has less than three vertices, visits a vertex twice, or intersects itself.
+    DegenerateFace(String),
+
+    /// The vertices with the given indices have the same position.
+    DuplicatePositions {
+        /// The index of the first vertex.
+        first: usize,
+        /// The index of the second vertex.
+        second: usize,
+    },
+
+    /// A list of indexed faces cannot be inserted.
+    IndexedFace(IndexedFaceError),
+
+    /// A position or a value derived from it is NaN or infinite.
+    NonFinite(String),
+
+    /// An argument of the operation doesn't match the mesh, e.g., a list has the wrong length.
+    InvalidArgument(String),
+}
+
+impl std::fmt::Display for ModellingError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ModellingError::MissingVertex(v) => write!(f, "Vertex {} does not exist", v),
+            ModellingError::MissingEdge(e) => write!(f, "Edge {} does not exist", e),
+            ModellingError::MissingFace(face) => write!(f, "Face {} does not exist", face),
+            ModellingError::InvalidTopology(msg) => write!(f, "Invalid topology: {}", msg),
+            ModellingError::NonManifold(msg) => write!(f, "Non-manifold: {}", msg),
+            ModellingError::DegenerateFace(msg) => write!(f, "Degenerate face: {}", msg),
+            ModellingError::DuplicatePositions { first, second } => write!(
+                f,
+                "Vertices {} and {} have the same position",
+                first, second
+            ),
+            ModellingError::IndexedFace(e) => write!(f, "{}", e),
+            ModellingError::NonFinite(msg) => write!(f, "Non-finite value: {}", msg),
+            ModellingError::InvalidArgument(msg) => write!(f, "Invalid argument: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for ModellingError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ModellingError::IndexedFace(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<IndexedFaceError> for ModellingError {
+    fn from(e: IndexedFaceError) -> Self {
+        ModellingError::IndexedFace(e)
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "nalgebra")]
+mod tests {
+    use crate::{extensions::nalgebra::*, prelude::*};
+
+    fn polygon(mesh: &mut Mesh3d64, ps: &[[f64; 2]]) -> usize {
+        let e = mesh.insert_polygon(
+            ps.iter()
+                .map(|p| VertexPayloadPNU::from_pos(Vec3::new(p[0], p[1], 0.0))),
+        );
+        mesh.edge(e).twin(mesh).face_id()
+    }
+
+    #[test]
+    fn test_try_builder_errors() {
+        let mut mesh = Mesh3d64::cube(1.0);
+        let f = mesh.face_ids().next().unwrap();
+        let e = mesh.face(f).edge_id();
+        assert!(matches!(
+            mesh.try_close_hole(e, Default::default(), false),
+            Err(ModellingError::InvalidTopology(_))
+        ));
+        assert!(mesh.try_remove_face(f).is_ok());
+        assert_eq!(mesh.try_remove_face(f), Err(ModellingError::MissingFace(f)));
+        assert!(mesh.try_close_hole(e, Default::default(), false).is_ok());
+        assert!(mesh.check().is_ok());
+
+        let v = mesh.vertex_ids().next().unwrap();
+        let w = mesh.vertex(v).neighbor_ids(&mesh).next().unwrap();
+        assert_eq!(
+            mesh.try_insert_edge_between(v, Default::default(), 100, Default::default()),
+            Err(ModellingError::MissingVertex(100))
+        );
+        assert!(matches!(
+            mesh.try_insert_edge_between(v, Default::default(), w, Default::default()),
+            Err(ModellingError::NonManifold(_))
+        ));
+
+        // a single edge has a boundary with only two halfedges
+        let (a, b) = mesh.add_isolated_edge_default(
+            VertexPayloadPNU::from_pos(Vec3::new(5.0, 0.0, 0.0)),
+            VertexPayloadPNU::from_pos(Vec3::new(6.0, 0.0, 0.0)),
+        );
+        let ab = mesh.shared_edge_id(a, b).unwrap();
+        assert!(matches!(
+            mesh.try_close_hole(ab, Default::default(), false),
+            Err(ModellingError::DegenerateFace(_))
+        ));
+        assert!(matches!(
+            mesh.try_close_face_vertices(
+                a,
+                Default::default(),
+                b,
+                Default::default(),
+                a,
+                Default::default(),
+                false
+            ),
+            Err(ModellingError::NonManifold(_))
+        ));
+
+        // a triangle can be closed once
+        let (c, _, _) = mesh
+            .add_vertex_via_vertex_default(b, VertexPayloadPNU::from_pos(Vec3::new(6.0, 1.0, 0.0)));
+        let close = |mesh: &mut Mesh3d64| {
+            mesh.try_close_face_vertices(
+                b,
+                Default::default(),
+                c,
+                Default::default(),
+                a,
+                Default::default(),
+                false,
+            )
+        };
+        assert!(close(&mut mesh).is_ok());
+        assert!(matches!(
+            close(&mut mesh),
+            Err(ModellingError::NonManifold(_))
+        ));
+        assert!(mesh.check().is_ok());
+    }
+
+    #[test]
+    fn test_try_delaunay_errors() {
+        let mut mesh = Mesh3d64::new();
+
+        // two triangles touching in a single point
+        let f = polygon(
+            &mut mesh,
+            &[
+                [0.0, 0.0],
+                [2.0, 0.0],
+                [1.0, 1.0],
+                [2.0, 2.0],
+                [0.0, 2.0],
+                [1.0, 1.0],
+            ],
+        );
+        let mut indices = Vec::new();
+        let mut tri = Triangulation::new(&mut indices);
+        let ids: Vec<_> = mesh.face(f).vertex_ids(&mesh).collect();
+        match try_delaunay_triangulation::<MeshType3d64PNU>(mesh.face(f), &mesh, &mut tri) {
+            Err(ModellingError::DuplicatePositions { first, second }) => {
+                assert_eq!(mesh.vertex(first).pos(), mesh.vertex(second).pos());
+                assert!(ids.contains(&first) && ids.contains(&second) && first != second);
+            }
+            r => panic!("Expected duplicate positions but got {:?}", r),
+        }
+        assert_eq!(tri.len(), 0);
+
+        let f = polygon(
+            &mut mesh,
+            &[[0.0, 0.0], [4.0, 0.0], [4.0, 4.0], [1.0, 4.0], [3.0, -2.0]],
+        );
+        assert!(matches!(
+            try_delaunay_triangulation::<MeshType3d64PNU>(mesh.face(f), &mesh, &mut tri),
+            Err(ModellingError::DegenerateFace(_))
+        ));
+
+        let f = polygon(&mut mesh, &[[0.0, 0.0], [4.0, 0.0], [4.0, 4.0], [0.0, 4.0]]);
+        assert!(
+            try_delaunay_triangulation::<MeshType3d64PNU>(mesh.face(f), &mesh, &mut tri).is_ok()
+        );
+        assert_eq!(tri.len(), 2);
+    }
+}
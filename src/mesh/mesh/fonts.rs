@@ -7,7 +7,7 @@ use crate::{
     },
 };
 
-use super::{EuclideanMeshType, MeshHalfEdgeBuilder, MeshType, MeshTypeHalfEdge};
+use super::{nest_holes, EuclideanMeshType, MeshHalfEdgeBuilder, MeshType, MeshTypeHalfEdge};
 
 /// A font that can be used to render text.
 pub struct Font<'a> {
@@ -82,7 +82,7 @@ impl<'a> Font<'a> {
         T::EP: DefaultEdgePayload,
         T::FP: DefaultFacePayload,
     {
-        // TODO: Improve stability. Detect when to close and when to insert holes
+        // TODO: Improve stability. Detect when to close
 
         let Some(outline) = self.font.outline(glyph) else {
             println!("No outline found for glyph");
@@ -101,6 +101,7 @@ impl<'a> Font<'a> {
         let mut cur_v = IndexType::max();
         let mut start_v = IndexType::max();
         let mut prev_v = IndexType::max();
+        let mut faces = Vec::new();
 
         outline.curves.iter().for_each(|curve| {
             let (p0, p1, c0, c1) = match curve {
@@ -136,7 +137,7 @@ impl<'a> Font<'a> {
             if let Some(_p) = cur {
                 // TODO: don't use eq but similarity
                 if first.unwrap() == p1 {
-                    let (_, _, e) = mesh.close_face_vertices(
+                    let (f, _, e) = mesh.close_face_vertices(
                         prev_v,
                         Default::default(),
                         cur_v,
@@ -151,6 +152,7 @@ impl<'a> Font<'a> {
                     first = None;
                     cur = None;
                     cur_e = e;
+                    faces.push(f);
                 } else {
                     //assert!(p0 == p || p1 == first.unwrap(), "Expected {:?} but got {:?}", p, p0);
                     let (v, e, _) = mesh
@@ -184,5 +186,24 @@ impl<'a> Font<'a> {
                     .set_curve_type(CurvedEdgeType::QuadraticBezier(c0.unwrap() * scale + trans));
             }
         });
+
+        // the counters of glyphs like "O" are separate contours inside the outer contour
+        nest_holes::<D, T>(mesh, &faces);
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "nalgebra")]
+mod tests {
+    use crate::{extensions::nalgebra::*, prelude::*};
+
+    #[test]
+    fn test_glyph_with_counter() {
+        let mut mesh = Mesh2d64Curved::new();
+        Font::new(include_bytes!("../../../assets/Cochineal-Roman.otf"), 1.0)
+            .layout_text::<2, MeshType2d64PNUCurved>("O", &mut mesh);
+        assert!(mesh.check().is_ok(), "{:?}", mesh.check());
+        assert_eq!(mesh.num_faces(), 1);
+        assert_eq!(mesh.faces().next().unwrap().num_holes(), 1);
     }
 }
@@ -2,6 +2,7 @@
 mod basics;
 mod builder;
 mod check;
+mod error;
 mod halfedge;
 mod iso;
 mod mesh_type;
@@ -9,13 +10,16 @@ mod normals;
 mod path_builder;
 mod payload;
 mod position;
+mod split;
 mod topology;
 mod transform;
 mod triangulate;
+mod weld;
 
 pub use basics::*;
 pub use builder::*;
 pub use check::*;
+pub use error::*;
 pub use halfedge::*;
 pub use iso::*;
 pub use mesh_type::*;
@@ -23,9 +27,11 @@ pub use normals::*;
 pub use path_builder::*;
 pub use payload::*;
 pub use position::*;
+pub use split::*;
 pub use topology::*;
 pub use transform::*;
 pub use triangulate::*;
+pub(crate) use weld::*;
 
 #[cfg(feature = "netsci")]
 mod netsci;
@@ -1,11 +1,14 @@
 use crate::{
     math::{HasNormal, Scalar, Vector, VectorIteratorExt},
-    mesh::{EuclideanMeshType, Face3d, FaceBasics, MeshBasics, MeshType3D, VertexBasics},
+    mesh::{
+        EdgeBasics, EuclideanMeshType, Face3d, FaceBasics, HasSmoothingGroup, MeshBasics,
+        MeshSplit, MeshType3D, VertexBasics,
+    },
 };
 use std::collections::HashMap;
 
 /// The algorithm to use for generating normals.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, Copy, Default)]
 pub enum GenerateNormals {
     /// Do not generate normals. (no vertex duplication)
     None,
@@ -16,8 +19,41 @@ pub enum GenerateNormals {
 
     /// Generate only smooth normals. (no vertex duplication)
     Smooth,
-    // Use face groups to decide whether to generate flat or smooth normals.
-    //Groups,
+
+    /// Generate smooth normals but keep edges sharper than the given angle (in radians) hard.
+    /// (vertex duplication only along the sharp edges)
+    AutoSmooth(f64),
+
+    /// Use the smoothing groups of the faces to decide whether to generate flat or smooth normals.
+    /// (vertex duplication only between different groups, see `HasSmoothingGroup`)
+    Groups,
+}
+
+impl GenerateNormals {
+    /// Returns the key used for comparing and hashing, i.e., the bits of the angle.
+    fn key(&self) -> (u8, u64) {
+        match self {
+            GenerateNormals::None => (0, 0),
+            GenerateNormals::Flat => (1, 0),
+            GenerateNormals::Smooth => (2, 0),
+            GenerateNormals::AutoSmooth(angle) => (3, angle.to_bits()),
+            GenerateNormals::Groups => (4, 0),
+        }
+    }
+}
+
+impl PartialEq for GenerateNormals {
+    fn eq(&self, other: &Self) -> bool {
+        self.key() == other.key()
+    }
+}
+
+impl Eq for GenerateNormals {}
+
+impl std::hash::Hash for GenerateNormals {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.key().hash(state);
+    }
 }
 
 /// Methods to work with normals in a mesh.
@@ -31,11 +67,96 @@ pub trait WithNormals<
 >: MeshBasics<T> where
     T::VP: HasNormal<D, VecN, S = SN>,
 {
+    /// Generates normals using the given algorithm and safes them in the mesh.
+    /// Duplicates vertices where the normals are not continuous, see `MeshSplit`.
+    fn generate_normals(&mut self, algorithm: GenerateNormals) -> &mut Self
+    where
+        T: MeshType3D,
+        T::VP: HasNormal<3, <T as EuclideanMeshType<3>>::Vec, S = <T as EuclideanMeshType<3>>::S>,
+        T::FP: HasSmoothingGroup,
+        Self: MeshSplit<T>,
+    {
+        match algorithm {
+            GenerateNormals::None => self,
+            GenerateNormals::Flat => self.generate_flat_normals(),
+            GenerateNormals::Smooth => self.generate_smooth_normals(),
+            GenerateNormals::AutoSmooth(angle) => {
+                self.generate_auto_smooth_normals(<T as EuclideanMeshType<3>>::S::from_f64(angle))
+            }
+            GenerateNormals::Groups => self.generate_group_normals(),
+        }
+    }
+
     /// Generates flat normals and safes them in the mesh.
-    /// Requires all vertices in the mesh to be duplicated.
-    /// TODO: Implement this function and also the duplication methods.
-    fn generate_flat_normals(&mut self) -> &mut Self {
-        todo!("generate_normals_flat is not implemented yet");
+    /// Splits all vertices so every face has its own vertices.
+    fn generate_flat_normals(&mut self) -> &mut Self
+    where
+        T: MeshType3D,
+        T::VP: HasNormal<3, <T as EuclideanMeshType<3>>::Vec, S = <T as EuclideanMeshType<3>>::S>,
+        Self: MeshSplit<T>,
+    {
+        self.split_faces();
+
+        let normals = MeshBasics::faces(self)
+            .flat_map(|f| {
+                let normal = Face3d::normal(f, self).normalize();
+                f.vertex_ids(self).map(move |v| (v, normal))
+            })
+            .collect::<Vec<_>>();
+        for (v, normal) in normals {
+            self.vertex_mut(v).payload_mut().set_normal(normal);
+        }
+
+        self
+    }
+
+    /// Generates smooth normals but splits the vertices along edges where the
+    /// faces meet at an angle larger than `max_angle` (in radians) so these edges stay hard.
+    fn generate_auto_smooth_normals(
+        &mut self,
+        max_angle: <T as EuclideanMeshType<3>>::S,
+    ) -> &mut Self
+    where
+        T: MeshType3D,
+        T::VP: HasNormal<3, <T as EuclideanMeshType<3>>::Vec, S = <T as EuclideanMeshType<3>>::S>,
+        Self: MeshSplit<T>,
+    {
+        let face_normals: HashMap<T::F, _> = MeshBasics::faces(self)
+            .map(|f| (f.id(), Face3d::normal(f, self).normalize()))
+            .collect();
+        let sharp = MeshBasics::edges(self)
+            .filter(|e| {
+                let fs = e.face_ids(self).collect::<Vec<_>>();
+                fs.len() == 2
+                    && face_normals[&fs[0]].angle_between(face_normals[&fs[1]]) > max_angle
+            })
+            .map(|e| e.id())
+            .collect::<Vec<_>>();
+        self.split_edges(sharp);
+        self.generate_smooth_normals()
+    }
+
+    /// Generates smooth normals but splits the vertices along edges between
+    /// faces of different smoothing groups and around faces without a group, see `HasSmoothingGroup`.
+    fn generate_group_normals(&mut self) -> &mut Self
+    where
+        T: MeshType3D,
+        T::VP: HasNormal<3, <T as EuclideanMeshType<3>>::Vec, S = <T as EuclideanMeshType<3>>::S>,
+        T::FP: HasSmoothingGroup,
+        Self: MeshSplit<T>,
+    {
+        let hard = MeshBasics::edges(self)
+            .filter(|e| {
+                let groups = e
+                    .face_ids(self)
+                    .map(|f| self.face(f).payload().smoothing_group())
+                    .collect::<Vec<_>>();
+                groups.len() == 2 && (groups[0] != groups[1] || groups[0] == 0)
+            })
+            .map(|e| e.id())
+            .collect::<Vec<_>>();
+        self.split_edges(hard);
+        self.generate_smooth_normals()
     }
 
     /// Generates smooth normals and safes them in the mesh.
@@ -68,3 +189,108 @@ pub trait WithNormals<
         self
     }
 }
+
+#[cfg(test)]
+#[cfg(feature = "nalgebra")]
+mod tests {
+    use crate::{extensions::nalgebra::*, prelude::*};
+
+    #[test]
+    fn test_flat_normals() {
+        let mut mesh = Mesh3d64::cube(1.0);
+        mesh.generate_normals(GenerateNormals::Flat);
+        assert!(mesh.check().is_ok());
+        assert_eq!(mesh.num_vertices(), 24);
+        assert_eq!(mesh.num_faces(), 6);
+        for f in mesh.faces() {
+            let normal = Face3d::normal(f, &mesh).normalize();
+            for v in f.vertices(&mesh) {
+                assert_eq!(v.faces(&mesh).count(), 1);
+                assert!(v.payload().normal().is_about(&normal, 1e-10));
+            }
+        }
+
+        // splitting again doesn't change anything
+        assert!(mesh.split_faces().is_empty());
+    }
+
+    #[test]
+    fn test_auto_smooth_normals() {
+        let mut cube = Mesh3d64::cube(1.0);
+        cube.generate_normals(GenerateNormals::AutoSmooth(100f64.to_radians()));
+        assert_eq!(cube.num_vertices(), 8);
+        cube.generate_normals(GenerateNormals::AutoSmooth(0.5));
+        assert_eq!(cube.num_vertices(), 24);
+
+        // fractional angles are kept exactly
+        let mut cube = Mesh3d64::cube(1.0);
+        cube.generate_normals(GenerateNormals::AutoSmooth(90.5f64.to_radians()));
+        assert_eq!(cube.num_vertices(), 8);
+        let mut cube = Mesh3d64::cube(1.0);
+        cube.generate_normals(GenerateNormals::AutoSmooth(89.5f64.to_radians()));
+        assert_eq!(cube.num_vertices(), 24);
+        assert_eq!(
+            GenerateNormals::AutoSmooth(0.5),
+            GenerateNormals::AutoSmooth(0.5)
+        );
+        assert_ne!(
+            GenerateNormals::AutoSmooth(0.5),
+            GenerateNormals::AutoSmooth(0.6)
+        );
+
+        // the caps are split off, but the sides stay smooth
+        let n = 16;
+        let mut cylinder = Mesh3d64::regular_prism(1.0, 2.0, n);
+        assert_eq!(cylinder.num_vertices(), 2 * n);
+        cylinder.generate_normals(GenerateNormals::AutoSmooth(30f64.to_radians()));
+        assert!(cylinder.check().is_ok());
+        assert_eq!(cylinder.num_vertices(), 4 * n);
+        assert_eq!(cylinder.num_faces(), n + 2);
+        for v in cylinder.vertices() {
+            let normal = v.payload().normal();
+            if v.faces(&cylinder).count() == 1 {
+                // cap vertices point up or down
+                assert!(normal.y.abs().is_about(1.0, 1e-10));
+            } else {
+                // side vertices point outwards
+                assert!(normal.y.is_about(0.0, 1e-10));
+                assert!(normal
+                    .xz()
+                    .normalize()
+                    .is_about(&v.pos().xz().normalize(), 1e-10));
+            }
+        }
+    }
+
+    #[test]
+    fn test_group_normals() {
+        let groups = |mesh: &Mesh3d64Crease, caps: u32, sides: u32| {
+            let mut mesh = mesh.clone();
+            let ids: Vec<_> = mesh.face_ids().collect();
+            for f in ids {
+                let normal = Face3d::normal(mesh.face(f), &mesh).normalize();
+                let group = if normal.y.abs() > 0.5 { caps } else { sides };
+                mesh.face_mut(f).payload_mut().set_smoothing_group(group);
+            }
+            mesh.generate_normals(GenerateNormals::Groups);
+            assert!(mesh.check().is_ok());
+            mesh.num_vertices()
+        };
+
+        let cube = Mesh3d64Crease::cube(1.0);
+        assert_eq!(groups(&cube, 0, 0), 24);
+        assert_eq!(groups(&cube, 1, 1), 8);
+        assert_eq!(groups(&cube, 2, 1), 16);
+        assert_eq!(groups(&cube, 0, 1), 16);
+
+        // by default, faces have no smoothing group and are flat
+        let mut mesh = Mesh3d64Crease::cube(1.0);
+        mesh.generate_normals(GenerateNormals::Groups);
+        assert_eq!(mesh.num_vertices(), 24);
+
+        // the same holds for payloads without smoothing groups
+        let mut mesh = Mesh3d64::cube(1.0);
+        mesh.generate_normals(GenerateNormals::Groups);
+        assert_eq!(mesh.num_vertices(), 24);
+    }
+}
@@ -1,8 +1,10 @@
 use crate::{
-    math::{HasPosition, IndexType, TransformTrait, Transformable},
+    math::{
+        HasPosition, IndexType, Polygon, Scalar, TransformTrait, Transformable, Vector, Vector2D,
+    },
     mesh::{
-        CurvedEdge, CurvedEdgeType, DefaultEdgePayload, EdgeBasics, HalfEdge, MeshBasics,
-        MeshBuilder, MeshHalfEdgeBuilder, MeshType, VertexBasics,
+        CurvedEdge, CurvedEdgeType, DefaultEdgePayload, EdgeBasics, FaceBasics, HalfEdge,
+        MeshBasics, MeshBuilder, MeshHalfEdgeBuilder, MeshType, VertexBasics,
     },
 };
 
@@ -221,12 +223,11 @@ where
             return self.mesh().close_hole(start_inner, fp, false);
         }
 
-        self.closed = true;
-
         let Some((current_inner, current_outer)) = self.current_edges() else {
             // The current vertex doesn't have any edges yet.
             assert!(self.start_edges().is_none());
             assert!(self.start_vertex() == self.current_vertex());
+            self.closed = true;
             return IndexType::max();
         };
         let Some((start_inner, _start_outer)) = self.start_edges() else {
@@ -248,6 +249,7 @@ where
             //self.mesh().close
             self.line_to(self.start_vertex());
         }
+        self.closed = true;
 
         // TODO: is this necessary or not? Generally, is the correction above correct? Or is the winding in the opposite direction?
         /*debug_assert!(self
@@ -459,3 +461,73 @@ where
         self
     }
 }
+
+/// Turns the faces that lie inside an odd number of the other given faces into holes of
+/// the innermost face containing them, e.g., the counter of an "O" drawn as two closed paths.
+/// Only compares the vertex positions in the XY plane, i.e., ignores curved edges.
+/// Returns the faces that are left.
+pub(crate) fn nest_holes<const D: usize, T: EuclideanMeshType<D> + MeshTypeHalfEdge>(
+    mesh: &mut T::Mesh,
+    faces: &[T::F],
+) -> Vec<T::F> {
+    let polygons: Vec<T::Poly> = faces
+        .iter()
+        .map(|&f| {
+            T::Poly::from_iter(mesh.face(f).vertices(mesh).map(|v| {
+                let p = v.pos();
+                T::Vec2::new(p.x(), p.y())
+            }))
+        })
+        .collect();
+    let inside =
+        |i: usize, j: usize| i != j && polygons[i].points().iter().all(|p| polygons[j].contains(p));
+
+    let mut kept = Vec::new();
+    for i in 0..faces.len() {
+        let containing: Vec<usize> = (0..faces.len()).filter(|&j| inside(i, j)).collect();
+        if containing.len() % 2 == 0 {
+            kept.push(faces[i]);
+            continue;
+        }
+
+        // the containing face with the most containing faces itself is the innermost one
+        let parent = *containing
+            .iter()
+            .max_by_key(|&&j| (0..faces.len()).filter(|&k| inside(j, k)).count())
+            .unwrap();
+
+        // holes run against the face, so use the other side of the loop if both run the same way
+        let mut e = mesh.face(faces[i]).edge_id();
+        mesh.remove_face(faces[i]);
+        let area = polygons[i].signed_area() * polygons[parent].signed_area();
+        if area.is_positive() {
+            e = mesh.edge(e).twin_id();
+        }
+        mesh.insert_hole(faces[parent], e);
+    }
+    kept
+}
+
+#[cfg(test)]
+#[cfg(feature = "nalgebra")]
+mod tests {
+    use crate::{extensions::nalgebra::*, prelude::*};
+
+    #[test]
+    fn test_close_open_path() {
+        // closing an open path draws the missing edge back to the start before marking it closed
+        let mut mesh = Mesh2d64Curved::new();
+        let mut pb = PathBuilder::<MeshType2d64PNUCurved, NdAffine<f64, 2>>::start(
+            &mut mesh,
+            Vec2::new(0.0, 0.0),
+        );
+        pb.line(Vec2::new(1.0, 0.0)).line(Vec2::new(1.0, 1.0));
+        assert!(!pb.is_closed());
+        let f = pb.close(Default::default());
+        assert!(pb.is_closed());
+        assert!(mesh.check().is_ok());
+        assert_eq!(mesh.num_faces(), 1);
+        assert_eq!(mesh.num_edges(), 6);
+        assert_eq!(mesh.face(f).num_vertices(&mesh), 3);
+    }
+}
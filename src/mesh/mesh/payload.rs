@@ -9,6 +9,8 @@ pub trait MeshPayload<T: MeshType>: Default + Clone + PartialEq + std::fmt::Debu
 
 /// An empty mesh payload that can be used when no additional data is needed.
 #[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound = ""))]
 pub struct EmptyMeshPayload<T: MeshType> {
     _phantom: std::marker::PhantomData<T>,
 }
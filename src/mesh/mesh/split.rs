@@ -0,0 +1,20 @@
+use super::{MeshBasics, MeshType};
+
+/// Methods to duplicate vertices so that faces stop sharing them, e.g., to store flat normals or uv seams in the mesh itself.
+pub trait MeshSplit<T: MeshType<Mesh = Self>>: MeshBasics<T> {
+    /// Cuts the mesh along the given edges so the faces on both sides are not connected anymore.
+    /// Both sides become boundary edges and the vertices are duplicated so every
+    /// fan of faces that is still connected around a vertex gets its own vertex.
+    /// Edges that are already at the boundary are ignored.
+    ///
+    /// The new vertices copy the payload of the vertex they were split from.
+    /// Returns the new vertices.
+    fn split_edges(&mut self, edges: impl IntoIterator<Item = T::E>) -> Vec<T::V>;
+
+    /// Cuts the mesh along all edges so no two faces share a vertex.
+    /// Returns the new vertices.
+    fn split_faces(&mut self) -> Vec<T::V> {
+        let edges = self.edge_ids().collect::<Vec<_>>();
+        self.split_edges(edges)
+    }
+}
@@ -2,8 +2,8 @@ use std::collections::HashMap;
 
 use super::{basics::MeshBasics, MeshType, MeshType3D};
 use crate::{
-    math::{HasNormal, IndexType, Vector},
-    mesh::{Face3d, FaceBasics, Triangulation, VertexBasics},
+    math::{HasNormal, HasUV, IndexType, Vector},
+    mesh::{CornerPayload, EdgeBasics, Face3d, FaceBasics, Triangulation, VertexBasics},
     tesselate::{triangulate_face, TesselationMeta, TriangulationAlgorithm},
 };
 
@@ -50,7 +50,8 @@ pub trait Triangulateable<T: MeshType<Mesh = Self>>: MeshBasics<T> {
             let face_normal = Face3d::normal(f, self).normalize();
             let mut id_map = HashMap::new();
             // generate a new list of vertices (full duplication)
-            f.vertices(self).for_each(|v| {
+            let holes = (0..f.num_holes()).flat_map(|hole| f.hole_vertices(self, hole));
+            f.vertices(self).chain(holes).for_each(|v| {
                 let mut p = v.payload().clone();
                 id_map.insert(v.id(), IndexType::new(vertices.len()));
                 p.set_normal(face_normal);
@@ -62,4 +63,117 @@ pub trait Triangulateable<T: MeshType<Mesh = Self>>: MeshBasics<T> {
 
         (indices, vertices)
     }
+
+    /// Triangulates the mesh and exports a vertex buffer that respects the corner attributes.
+    /// Unlike `triangulate_and_generate_flat_normals_post`, a vertex is only duplicated
+    /// where the corners around it have different attributes, e.g., along uv seams or hard edges.
+    ///
+    /// Returns the indices, the vertices with the corner uvs and normals applied,
+    /// and the corner payload each vertex was created from, e.g., to read the colors.
+    fn triangulate_with_corners(
+        &self,
+        algorithm: TriangulationAlgorithm,
+        meta: &mut TesselationMeta<T::V>,
+    ) -> (Vec<T::V>, Vec<T::VP>, Vec<T::EP>)
+    where
+        T: MeshType3D,
+        T::EP: CornerPayload<3, T>,
+        T::VP: HasNormal<3, T::Vec, S = T::S> + HasUV<T::Vec2, S = T::S>,
+    {
+        let mut vertices = Vec::new();
+        let mut corners: Vec<T::EP> = Vec::new();
+        let mut indices = Vec::new();
+
+        // the exported vertices of each mesh vertex
+        let mut exported: HashMap<T::V, Vec<T::V>> = HashMap::new();
+
+        for f in self.faces() {
+            let mut tri = Triangulation::new(&mut indices);
+            let mut id_map = HashMap::new();
+            let holes = (0..f.num_holes()).flat_map(|hole| f.hole_edges(self, hole));
+            f.edges(self).chain(holes).for_each(|e| {
+                let v = e.origin(self);
+                let corner = e.payload();
+                let candidates = exported.entry(v.id()).or_default();
+                let id = match candidates
+                    .iter()
+                    .find(|&&i| corners[i.index()].same_corner(corner))
+                {
+                    Some(&i) => i,
+                    None => {
+                        let mut p = v.payload().clone();
+                        if let Some(uv) = corner.corner_uv() {
+                            p.set_uv(uv);
+                        }
+                        if let Some(normal) = corner.corner_normal() {
+                            p.set_normal(normal);
+                        }
+                        let i = IndexType::new(vertices.len());
+                        vertices.push(p);
+                        corners.push(corner.clone());
+                        candidates.push(i);
+                        i
+                    }
+                };
+                id_map.insert(v.id(), id);
+            });
+            triangulate_face::<T>(f, self, &mut tri, algorithm, meta);
+            tri.map_indices(&id_map);
+        }
+
+        (indices, vertices, corners)
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "nalgebra")]
+mod tests {
+    use crate::{extensions::nalgebra::*, prelude::*};
+
+    fn export(mesh: &Mesh3d64Corner) -> (Vec<usize>, Vec<VertexPayloadPNU<f64, 3>>) {
+        let mut meta = TesselationMeta::default();
+        let (indices, vertices, corners) =
+            mesh.triangulate_with_corners(TriangulationAlgorithm::Auto, &mut meta);
+        assert_eq!(indices.len(), 36);
+        assert_eq!(vertices.len(), corners.len());
+        assert!(indices.iter().all(|&i| i < vertices.len()));
+        (indices, vertices)
+    }
+
+    #[test]
+    fn test_triangulate_with_corners() {
+        let mut mesh = Mesh3d64Corner::cube(1.0);
+
+        // without corner attributes, no vertex is split
+        assert_eq!(export(&mesh).1.len(), 8);
+
+        // a uv seam around one face splits only the four vertices of that face
+        let f = mesh.face_ids().next().unwrap();
+        let seam: Vec<_> = mesh.face(f).edge_ids(&mesh).collect();
+        for (i, &e) in seam.iter().enumerate() {
+            let uv = Vec2::new((i % 2) as f64, (i / 2) as f64);
+            mesh.edge_mut(e).payload_mut().set_corner_uv(Some(uv));
+        }
+        let (_, vertices) = export(&mesh);
+        assert_eq!(vertices.len(), 12);
+        assert!(vertices.iter().any(|v| *v.uv() == Vec2::new(1.0, 1.0)));
+
+        // hard edges everywhere: every vertex is split once per face
+        let face_ids: Vec<_> = mesh.face_ids().collect();
+        for f in face_ids {
+            let normal = Face3d::normal(mesh.face(f), &mesh).normalize();
+            let edges: Vec<_> = mesh.face(f).edge_ids(&mesh).collect();
+            for e in edges {
+                mesh.edge_mut(e)
+                    .payload_mut()
+                    .set_corner_normal(Some(normal));
+            }
+        }
+        let (indices, vertices) = export(&mesh);
+        assert_eq!(vertices.len(), 24);
+        for tri in indices.chunks(3) {
+            let n = vertices[tri[0]].normal();
+            assert!(tri.iter().all(|&i| vertices[i].normal() == n));
+        }
+    }
 }
@@ -0,0 +1,91 @@
+use std::collections::HashMap;
+
+/// Merges positions that are closer than the tolerance.
+/// Returns the welded positions and the index of each input position.
+pub(crate) fn weld(
+    positions: impl Iterator<Item = [f64; 3]>,
+    tolerance: f64,
+) -> (Vec<[f64; 3]>, Vec<usize>) {
+    let cell = |p: &[f64; 3]| -> [i64; 3] {
+        if tolerance > 0.0 {
+            p.map(|x| (x / tolerance).floor() as i64)
+        } else {
+            // -0.0 and 0.0 have different bits but are the same position
+            p.map(|x| if x == 0.0 { 0.0f64 } else { x }.to_bits() as i64)
+        }
+    };
+
+    let mut welded: Vec<[f64; 3]> = Vec::new();
+    let mut grid: HashMap<[i64; 3], Vec<usize>> = HashMap::new();
+    let mut indices = Vec::new();
+    for p in positions {
+        let c = cell(&p);
+        let mut found = None;
+        if tolerance > 0.0 {
+            // a position within the tolerance must be in one of the neighboring cells
+            'search: for dx in -1..=1 {
+                for dy in -1..=1 {
+                    for dz in -1..=1 {
+                        // cells of huge coordinates saturate, so the neighbors must saturate too
+                        let n = [
+                            c[0].saturating_add(dx),
+                            c[1].saturating_add(dy),
+                            c[2].saturating_add(dz),
+                        ];
+                        let Some(candidates) = grid.get(&n) else {
+                            continue;
+                        };
+                        for &j in candidates {
+                            let q = welded[j];
+                            let d2 = (0..3).map(|k| (p[k] - q[k]).powi(2)).sum::<f64>();
+                            if d2 <= tolerance * tolerance {
+                                found = Some(j);
+                                break 'search;
+                            }
+                        }
+                    }
+                }
+            }
+        } else {
+            found = grid.get(&c).map(|candidates| candidates[0]);
+        }
+
+        indices.push(found.unwrap_or_else(|| {
+            welded.push(p);
+            grid.entry(c).or_default().push(welded.len() - 1);
+            welded.len() - 1
+        }));
+    }
+
+    (welded, indices)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::weld;
+
+    #[test]
+    fn test_weld_extreme_coordinates() {
+        let (welded, indices) = weld(
+            [
+                [f64::MAX, -f64::MAX, 0.0],
+                [f64::MAX, -f64::MAX, 0.0],
+                [0.0, 0.0, 0.0],
+            ]
+            .into_iter(),
+            1e-300,
+        );
+        assert_eq!(welded.len(), 2);
+        assert_eq!(indices, vec![0, 0, 1]);
+    }
+
+    #[test]
+    fn test_weld_signed_zero() {
+        let (welded, indices) = weld(
+            [[0.0, -0.0, 1.0], [-0.0, 0.0, 1.0], [0.0, 0.0, -1.0]].into_iter(),
+            0.0,
+        );
+        assert_eq!(welded.len(), 2);
+        assert_eq!(indices, vec![0, 0, 1]);
+    }
+}
@@ -18,4 +18,5 @@ pub trait DefaultVertexPayload: VertexPayload + Default {}
 /// Notice that your mesh will behave more like a graph without any payload.
 // TODO: implement this. Requires the VertexPayload to be weaker and use a separate, stronger trait (e.g., `EuclideanVertexPayload`) for the full payload.
 #[derive(Debug, Clone, PartialEq, Default, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct EmptyVertexPayload;
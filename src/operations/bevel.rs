@@ -0,0 +1,695 @@
+use crate::{
+    math::{HasPosition, IndexType, Scalar, Transformable, Vector, Vector3D},
+    mesh::{
+        dedup_loop, DefaultEdgePayload, DefaultFacePayload, EdgeBasics, Face3d, FaceBasics,
+        HalfEdge, MeshBasics, MeshBuilder, MeshType3D, MeshTypeHalfEdge, ModellingError,
+        VertexBasics,
+    },
+};
+use std::collections::{HashMap, HashSet};
+
+/// The shape of the cross section of a bevel with more than one segment.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum BevelProfile {
+    /// Straight segments between the two sides of the bevel.
+    Flat,
+
+    /// A circular arc that is tangent to both faces adjacent to the bevelled edge.
+    #[default]
+    Round,
+
+    /// A rational quadratic Bézier curve using the original edge as control point with the given weight.
+    /// A weight of `0` is flat, larger weights pull the profile towards the original edge.
+    Weight(f64),
+}
+
+/// Collects the payloads of the bevelled mesh.
+struct BevelBuilder<'a, T: MeshType3D> {
+    mesh: &'a T::Mesh,
+    vps: Vec<T::VP>,
+    original: HashMap<T::V, usize>,
+}
+
+impl<'a, T: MeshType3D> BevelBuilder<'a, T> {
+    fn pos(&self, v: T::V) -> T::Vec {
+        *self.mesh.vertex(v).payload().pos()
+    }
+
+    fn original(&mut self, v: T::V) -> usize {
+        if let Some(&i) = self.original.get(&v) {
+            return i;
+        }
+        let i = self.push(self.mesh.vertex(v).payload().clone());
+        self.original.insert(v, i);
+        i
+    }
+
+    fn push(&mut self, vp: T::VP) -> usize {
+        self.vps.push(vp);
+        self.vps.len() - 1
+    }
+
+    /// Interpolates the payload between `a` (at `t = 0`) and `b` (at `t = 1`) and moves it to the given position.
+    fn interpolated(&mut self, a: usize, b: usize, t: T::S, pos: T::Vec) -> usize {
+        let mut vp = self.vps[a].lerped(&self.vps[b], t);
+        vp.set_pos(pos);
+        self.push(vp)
+    }
+}
+
+/// Bevels the halfedges in `selected` (both halves of every edge) and chamfers the vertices in `chamfered`.
+/// Only the faces around the affected vertices are replaced. If that fails, the mesh is not modified.
+fn bevel<T: MeshTypeHalfEdge + MeshType3D>(
+    target: &mut T::Mesh,
+    selected: HashSet<T::E>,
+    chamfered: HashSet<T::V>,
+    width: T::S,
+    segments: usize,
+    profile: BevelProfile,
+) -> Result<(), ModellingError>
+where
+    T::EP: DefaultEdgePayload,
+    T::FP: DefaultFacePayload,
+{
+    if !width.is_finite() || width <= T::S::ZERO {
+        return Err(ModellingError::DegenerateFace(format!(
+            "The bevel width {} must be positive and finite",
+            width
+        )));
+    }
+    // the new vertices must stay inside the edges they are placed on
+    let too_wide = |v: T::V, w: T::V| {
+        ModellingError::DegenerateFace(format!(
+            "The bevel width {} is too large for the edge from vertex {} to {}",
+            width, v, w
+        ))
+    };
+
+    let mesh = &*target;
+    let mut b = BevelBuilder::<T> {
+        mesh,
+        vps: Vec::new(),
+        original: HashMap::new(),
+    };
+    let dir = |h: T::E| {
+        let e = mesh.edge(h);
+        (*e.target(mesh).payload().pos() - *e.origin(mesh).payload().pos()).normalize()
+    };
+    let affected: HashSet<T::V> = selected
+        .iter()
+        .map(|&h| mesh.edge(h).origin_id())
+        .chain(chamfered.iter().copied())
+        .collect();
+
+    // unselected edges slide their end along the edge so the adjacent bevels have the given width
+    let mut slide: HashMap<T::E, usize> = HashMap::new();
+    let mut slide_distance: HashMap<T::E, T::S> = HashMap::new();
+    for &v in &affected {
+        for h in mesh.vertex(v).edges_out(mesh) {
+            if selected.contains(&h.id()) {
+                continue;
+            }
+            let neighbors = [h.prev_id(), h.twin(mesh).next_id()];
+            let distance = if chamfered.contains(&v) {
+                Some(width)
+            } else {
+                neighbors
+                    .iter()
+                    .filter(|&&k| selected.contains(&k))
+                    .map(|&k| {
+                        let k = if k == h.prev_id() {
+                            mesh.edge(k).twin_id()
+                        } else {
+                            k
+                        };
+                        let sin = dir(h.id()).cross(&dir(k)).length();
+                        if sin > T::S::from_f64(1e-6) {
+                            width / sin
+                        } else {
+                            width
+                        }
+                    })
+                    .reduce(|a, b| a.max(b))
+            };
+            if let Some(d) = distance {
+                let w = h.target_id(mesh);
+                let len = b.pos(v).distance(&b.pos(w));
+                let other = slide_distance
+                    .get(&h.twin_id())
+                    .copied()
+                    .unwrap_or(T::S::ZERO);
+                if d + other >= len {
+                    return Err(too_wide(v, w));
+                }
+                slide_distance.insert(h.id(), d);
+                let (vi, wi) = (b.original(v), b.original(w));
+                let pos = b.pos(v) + dir(h.id()) * d;
+                let i = b.interpolated(vi, wi, d / len, pos);
+                slide.insert(h.id(), i);
+            }
+        }
+    }
+    let end = |h: T::E, b: &mut BevelBuilder<T>| match slide.get(&h) {
+        Some(&i) => i,
+        None => b.original(mesh.edge(h).origin_id()),
+    };
+
+    // the new corners of the faces at the affected vertices, keyed by the halfedge leaving the corner
+    let mut corners: HashMap<T::E, Vec<usize>> = HashMap::new();
+    for &v in &affected {
+        for h in mesh.vertex(v).edges_out(mesh) {
+            if h.is_boundary_self() {
+                continue;
+            }
+            let p = h.prev(mesh);
+            let corner = match (selected.contains(&p.id()), selected.contains(&h.id())) {
+                (true, true) => {
+                    let (d1, d2) = (dir(h.id()), dir(p.twin_id()));
+                    let sin = d1.cross(&d2).length();
+                    let vi = b.original(v);
+                    if sin > T::S::from_f64(1e-6) {
+                        let k = width / sin;
+                        let pos = b.pos(v) + (d1 + d2) * k;
+                        let (ui, wi) = (b.original(h.target_id(mesh)), b.original(p.origin_id()));
+                        let len1 = b.pos(v).distance(&b.pos(h.target_id(mesh)));
+                        let len2 = b.pos(v).distance(&b.pos(p.origin_id()));
+                        if k >= len1 {
+                            return Err(too_wide(v, h.target_id(mesh)));
+                        }
+                        if k >= len2 {
+                            return Err(too_wide(v, p.origin_id()));
+                        }
+                        let a = b.interpolated(vi, ui, k / len1, pos);
+                        let c = b.interpolated(vi, wi, k / len2, pos);
+                        vec![b.interpolated(a, c, T::S::HALF, pos)]
+                    } else {
+                        let normal = Face3d::normal(h.face(mesh).unwrap(), mesh).normalize();
+                        let pos = b.pos(v) + normal.cross(&d1) * width;
+                        vec![b.interpolated(vi, vi, T::S::ZERO, pos)]
+                    }
+                }
+                (true, false) => vec![end(h.id(), &mut b)],
+                (false, true) => vec![end(p.twin_id(), &mut b)],
+                (false, false) => dedup_loop(vec![end(p.twin_id(), &mut b), end(h.id(), &mut b)]),
+            };
+            corners.insert(h.id(), corner);
+        }
+    }
+
+    // the cross sections of the bevels at both ends of the selected edges,
+    // keyed by the halfedge leaving that end and running from its face to the face of its twin
+    let mut profiles: HashMap<T::E, Vec<usize>> = HashMap::new();
+    for &h in &selected {
+        let edge = mesh.edge(h);
+        let a = *corners[&h].last().unwrap();
+        let d = *corners[&edge.twin(mesh).next_id()].first().unwrap();
+        let control = b.pos(edge.origin_id());
+        let (pa, pd) = (*b.vps[a].pos(), *b.vps[d].pos());
+        let weight = match profile {
+            BevelProfile::Flat => T::S::ZERO,
+            BevelProfile::Round => {
+                let (u, w) = ((pa - control).normalize(), (pd - control).normalize());
+                ((T::S::ONE - u.dot(&w)) * T::S::HALF)
+                    .max(T::S::ZERO)
+                    .sqrt()
+            }
+            BevelProfile::Weight(w) => T::S::from_f64(w),
+        };
+        let mut indices = vec![a];
+        for k in 1..segments {
+            let t = T::S::from_usize(k) / T::S::from_usize(segments);
+            let (s0, s1, s2) = (
+                (T::S::ONE - t) * (T::S::ONE - t),
+                T::S::TWO * t * (T::S::ONE - t) * weight,
+                t * t,
+            );
+            let pos = (pa * s0 + control * s1 + pd * s2) / (s0 + s1 + s2);
+            indices.push(b.interpolated(a, d, t, pos));
+        }
+        indices.push(d);
+        profiles.insert(h, indices);
+    }
+
+    // the faces at the affected vertices are replaced by the same faces with the new corners
+    let mut removed: Vec<T::F> = affected
+        .iter()
+        .flat_map(|&v| mesh.vertex(v).edges_out(mesh))
+        .filter(|h| !h.is_boundary_self())
+        .map(|h| h.face_id())
+        .collect();
+    removed.sort();
+    removed.dedup();
+
+    // the rebuilt faces come first and keep their ids, degenerate ones are moved to the end
+    let mut faces: Vec<(Vec<Vec<usize>>, T::FP)> = Vec::new();
+    let mut degenerate = Vec::new();
+    for &f in &removed {
+        let face = mesh.face(f);
+        let mut with_corners = |edges: Vec<T::E>| {
+            let mut indices = Vec::new();
+            for h in edges {
+                match corners.get(&h) {
+                    Some(corner) => indices.extend(corner.iter().copied()),
+                    None => indices.push(b.original(mesh.edge(h).origin_id())),
+                }
+            }
+            dedup_loop(indices)
+        };
+        let mut loops = vec![with_corners(face.edge_ids(mesh).collect())];
+        if loops[0].len() < 3 {
+            degenerate.push(f);
+            continue;
+        }
+        for hole in 0..face.num_holes() {
+            let hole = with_corners(face.hole_edges(mesh, hole).map(|e| e.id()).collect());
+            if hole.len() >= 3 {
+                loops.push(hole);
+            }
+        }
+        faces.push((loops, *face.payload()));
+    }
+    removed.retain(|f| !degenerate.contains(f));
+    removed.extend(degenerate);
+
+    // a strip of quads along every selected edge
+    for &h in &selected {
+        let t = mesh.edge(h).twin_id();
+        if t < h {
+            continue;
+        }
+        let (pv, pu) = (&profiles[&h], &profiles[&t]);
+        for k in 0..segments {
+            let quad = vec![pv[k], pv[k + 1], pu[segments - k - 1], pu[segments - k]];
+            faces.push((vec![dedup_loop(quad)], T::FP::default()));
+        }
+    }
+
+    // close the holes at the affected vertices
+    for &v in &affected {
+        let vertex = mesh.vertex(v);
+        if vertex.is_boundary(mesh) {
+            continue;
+        }
+        // walk around the vertex in the opposite direction of `edges_out`, i.e., from a halfedge to its face
+        let outgoing: Vec<T::Edge> = vertex.edges_out(mesh).collect();
+        let mut ring = Vec::new();
+        for h in outgoing.iter().rev() {
+            match profiles.get(&h.id()) {
+                Some(profile) => ring.extend(profile.iter().rev()),
+                None => ring.push(end(h.id(), &mut b)),
+            }
+            ring.extend(corners[&h.id()].iter().rev());
+        }
+        let ring = dedup_loop(ring);
+        if ring.len() < 3 {
+            continue;
+        }
+
+        // non-planar holes are filled with a fan around their center
+        let points: Vec<T::Vec> = ring.iter().map(|&i| *b.vps[i].pos()).collect();
+        let center =
+            points.iter().fold(T::Vec::zero(), |acc, p| acc + *p) / T::S::from_usize(points.len());
+        let normal = (0..points.len())
+            .map(|i| (points[i] - center).cross(&(points[(i + 1) % points.len()] - center)))
+            .fold(T::Vec::zero(), |acc, n| acc + n)
+            .normalize();
+        let planar = points
+            .iter()
+            .all(|p| (*p - center).dot(&normal).abs() <= width * T::S::from_f64(1e-4));
+        if ring.len() == 3 || planar {
+            faces.push((vec![ring], T::FP::default()));
+            continue;
+        }
+        let mut c = ring[0];
+        for (k, &i) in ring.iter().enumerate().skip(1) {
+            c = b.interpolated(c, i, T::S::ONE / T::S::from_usize(k + 1), center);
+        }
+        for k in 0..ring.len() {
+            faces.push((
+                vec![vec![c, ring[k], ring[(k + 1) % ring.len()]]],
+                T::FP::default(),
+            ));
+        }
+    }
+
+    faces.retain(|(loops, _)| loops[0].len() >= 3);
+    let (vps, original) = (b.vps, b.original);
+    let backup = target.clone();
+
+    // The slid edges are split so the part that is kept keeps its id, payload, and attributes.
+    let mut ids: Vec<Option<T::V>> = vec![None; vps.len()];
+    for (&v, &i) in original.iter() {
+        ids[i] = Some(v);
+    }
+    let mut slid: Vec<(T::E, usize)> = slide.into_iter().collect();
+    slid.sort();
+    for (h, i) in slid {
+        let edge = target.edge(h);
+        let eps = (edge.payload().clone(), edge.twin(target).payload().clone());
+        target.insert_vertices_into_edge(h, std::iter::once((eps.0, eps.1, vps[i].clone())));
+        ids[i] = Some(target.edge(h).origin_id());
+    }
+
+    // only the vertices that are used by a face are inserted, e.g., not the tips of chamfered vertices
+    let mut new_faces = Vec::new();
+    for (loops, fp) in faces {
+        let mut vertex_loops = Vec::new();
+        for indices in loops {
+            let mut vs = Vec::new();
+            for i in indices {
+                let v = match ids[i] {
+                    Some(v) => v,
+                    None => target.add_vertex(vps[i].clone()),
+                };
+                ids[i] = Some(v);
+                vs.push(v);
+            }
+            vertex_loops.push(vs);
+        }
+        new_faces.push((vertex_loops, fp));
+    }
+
+    if let Err(e) = target.try_replace_faces(removed, new_faces) {
+        *target = backup;
+        return Err(e);
+    }
+    Ok(())
+}
+
+/// Bevel and chamfer operations for meshes.
+///
+/// Both operations only replace the faces around the affected vertices.
+/// All other vertices, edges, and faces keep their ids, payloads, and attributes.
+/// So do the shortened parts of edges that end in an affected vertex.
+/// The payloads of new vertices are interpolated from the original vertices.
+/// New edges and faces get the default payloads.
+pub trait MeshBevel<T: MeshTypeHalfEdge<Mesh = Self> + MeshType3D>: MeshBasics<T>
+where
+    T::EP: DefaultEdgePayload,
+    T::FP: DefaultFacePayload,
+{
+    /// Replaces the given edges with strips of `segments` faces that are `width` away from the
+    /// original edge on both adjacent faces. The cross section of the strips follows the `profile`.
+    /// Where bevelled edges meet, the hole is closed with a polygon resp. a fan of triangles.
+    ///
+    /// Boundary edges are ignored. The width must be small enough that the bevels don't overlap.
+    fn try_bevel_edges(
+        &mut self,
+        edges: impl IntoIterator<Item = T::E>,
+        width: T::S,
+        segments: usize,
+        profile: BevelProfile,
+    ) -> Result<&mut Self, ModellingError> {
+        if segments == 0 {
+            return Err(ModellingError::DegenerateFace(
+                "A bevel needs at least one segment".to_string(),
+            ));
+        }
+        let mut selected = HashSet::new();
+        for e in edges {
+            let edge = self
+                .try_edge(e)
+                .ok_or(ModellingError::MissingEdge(e.index()))?;
+            if !edge.is_boundary_self() && !edge.twin(self).is_boundary_self() {
+                selected.insert(e);
+                selected.insert(edge.twin_id());
+            }
+        }
+        bevel::<T>(self, selected, HashSet::new(), width, segments, profile)?;
+        Ok(self)
+    }
+
+    /// Same as `try_bevel_edges` but panics if the result is not a manifold.
+    fn bevel_edges(
+        &mut self,
+        edges: impl IntoIterator<Item = T::E>,
+        width: T::S,
+        segments: usize,
+        profile: BevelProfile,
+    ) -> &mut Self {
+        self.try_bevel_edges(edges, width, segments, profile)
+            .unwrap_or_else(|e| panic!("Failed to bevel the edges: {}", e))
+    }
+
+    /// Cuts off the given vertices by moving them `width` along each of their edges
+    /// and closing the hole with a new face.
+    ///
+    /// Boundary vertices are cut off without closing the hole.
+    fn try_chamfer_vertices(
+        &mut self,
+        vertices: impl IntoIterator<Item = T::V>,
+        width: T::S,
+    ) -> Result<&mut Self, ModellingError> {
+        let mut chamfered = HashSet::new();
+        for v in vertices {
+            if self.try_vertex(v).is_none() {
+                return Err(ModellingError::MissingVertex(v.index()));
+            }
+            chamfered.insert(v);
+        }
+        bevel::<T>(
+            self,
+            HashSet::new(),
+            chamfered,
+            width,
+            1,
+            BevelProfile::Flat,
+        )?;
+        Ok(self)
+    }
+
+    /// Same as `try_chamfer_vertices` but panics if the result is not a manifold.
+    fn chamfer_vertices(
+        &mut self,
+        vertices: impl IntoIterator<Item = T::V>,
+        width: T::S,
+    ) -> &mut Self {
+        self.try_chamfer_vertices(vertices, width)
+            .unwrap_or_else(|e| panic!("Failed to chamfer the vertices: {}", e))
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "nalgebra")]
+mod tests {
+    use crate::test_util::{assert_closed, square_with_hole, volume};
+    use crate::{extensions::nalgebra::*, prelude::*};
+
+    #[test]
+    fn test_bevel_single_edge() {
+        let mut mesh = Mesh3d64::cube(1.0);
+        let e = mesh.edge_ids().next().unwrap();
+        mesh.bevel_edges([e], 0.1, 1, BevelProfile::Flat);
+        assert_closed(&mesh);
+        assert_eq!(mesh.num_faces(), 7);
+        assert_eq!(mesh.num_vertices(), 10);
+        assert!((volume(&mesh) - (1.0 - 0.1 * 0.1 / 2.0)).abs() < 1e-10);
+
+        // a round profile lies on a cylinder with the width as radius
+        let mut mesh = Mesh3d64::cube(1.0);
+        let edge = mesh.edge(e);
+        let (a, b) = (edge.origin(&mesh).pos(), edge.target(&mesh).pos());
+        let axis = (b - a).normalize();
+        mesh.bevel_edges([e], 0.2, 4, BevelProfile::Round);
+        assert_closed(&mesh);
+        assert_eq!(mesh.num_vertices(), 16);
+        let center = a * 0.5 + b * 0.5;
+        let arc: Vec<_> = mesh
+            .vertices()
+            .map(|v| v.pos() - center)
+            .filter(|p| (p - axis * p.dot(&axis)).norm() < 0.5)
+            .collect();
+        assert_eq!(arc.len(), 10);
+        let inner = arc
+            .iter()
+            .map(|p| p - axis * p.dot(&axis))
+            .fold(Vec3::zeros(), |acc, p| acc + p)
+            / arc.len() as f64;
+        let axis_center = inner.normalize() * (0.2 * 2.0f64.sqrt());
+        for p in &arc {
+            let radial = p - axis * p.dot(&axis) - axis_center;
+            assert!((radial.norm() - 0.2).abs() < 1e-10);
+        }
+    }
+
+    #[test]
+    fn test_bevel_keeps_unaffected_edges() {
+        // the vertices of an octahedron keep an edge that is not next to the bevelled one
+        let mut mesh = Mesh3d64::regular_octahedron(1.0);
+        let before = volume(&mesh);
+        let e = mesh.edge_ids().next().unwrap();
+        let (v, w) = (mesh.edge(e).origin_id(), mesh.edge(e).target_id(&mesh));
+        let endpoints = |mesh: &Mesh3d64, h: usize| {
+            let edge = mesh.edge(h);
+            let (a, b) = (edge.origin_id(), edge.target_id(mesh));
+            (a, b, mesh.vertex(a).pos(), mesh.vertex(b).pos())
+        };
+        let unaffected: Vec<_> = mesh
+            .edge_ids()
+            .map(|h| endpoints(&mesh, h))
+            .filter(|&(a, b, _, _)| ![a, b].iter().any(|&x| x == v || x == w))
+            .collect();
+        assert!(!unaffected.is_empty());
+
+        mesh.bevel_edges([e], 0.05, 2, BevelProfile::Round);
+        assert_closed(&mesh);
+        assert_eq!(mesh.num_vertices(), 6 - 2 + 2 * (3 + 1));
+        assert_eq!(mesh.num_faces(), 8 + 2 + 2);
+        assert!(volume(&mesh) < before);
+        for &(a, b, pa, pb) in unaffected.iter() {
+            let h = mesh.shared_edge_id(a, b).unwrap();
+            assert_eq!(endpoints(&mesh, h), (a, b, pa, pb));
+        }
+    }
+
+    #[test]
+    fn test_bevel_keeps_payloads() {
+        let mut mesh = Mesh3d64Corner::cube(1.0);
+        let e = mesh.edge_ids().next().unwrap();
+        let (v, w) = (mesh.edge(e).origin_id(), mesh.edge(e).target_id(&mesh));
+        let touches = |mesh: &Mesh3d64Corner, h: usize| {
+            let edge = mesh.edge(h);
+            [edge.origin_id(), edge.target_id(mesh)]
+                .iter()
+                .any(|&x| x == v || x == w)
+        };
+        let far = mesh.edge_ids().find(|&h| !touches(&mesh, h)).unwrap();
+        let slid = mesh
+            .vertex(v)
+            .edges_out(&mesh)
+            .map(|h| h.id())
+            .find(|&h| h != e)
+            .unwrap();
+        let kept = mesh.edge(far).origin_id();
+
+        let uv = Vec2::new(0.25, 0.75);
+        mesh.edge_mut(far).payload_mut().set_corner_uv(Some(uv));
+        mesh.edge_mut(slid).payload_mut().set_corner_uv(Some(uv));
+        mesh.vertex_attributes_mut().add::<f64>("weight");
+        mesh.vertex_attributes_mut().set("weight", kept, 2.0);
+        let face_ids: Vec<_> = mesh.face_ids().collect();
+        let f = mesh.edge(e).face_id();
+        mesh.face_attributes_mut().add::<u32>("material");
+        mesh.face_attributes_mut().set("material", f, 7u32);
+
+        mesh.bevel_edges([e], 0.1, 2, BevelProfile::Round);
+        assert!(mesh.check().is_ok(), "{:?}", mesh.check());
+        assert_eq!(mesh.num_faces(), 10);
+        assert!(mesh.try_edge(e).is_none());
+        assert!(mesh.try_vertex(v).is_none());
+
+        // unselected edges keep their payload, even if they were shortened
+        assert_eq!(mesh.edge(far).payload().corner_uv(), Some(uv));
+        assert_eq!(mesh.edge(slid).payload().corner_uv(), Some(uv));
+        assert_ne!(mesh.edge(slid).origin_id(), v);
+        assert_eq!(
+            mesh.edges()
+                .filter(|h| h.payload().corner_uv().is_some())
+                .count(),
+            2
+        );
+        assert_eq!(
+            mesh.vertex_attributes().get::<f64>("weight", kept),
+            Some(&2.0)
+        );
+        // the faces at the bevelled edge are replaced in place
+        assert!(face_ids.iter().all(|&f| mesh.try_face(f).is_some()));
+        assert_eq!(mesh.face_attributes().get::<u32>("material", f), Some(&7));
+
+        assert_eq!(
+            mesh.try_bevel_edges([far], 0.1, 0, BevelProfile::Flat)
+                .err(),
+            Some(ModellingError::DegenerateFace(
+                "A bevel needs at least one segment".to_string()
+            ))
+        );
+
+        // invalid widths fail without modifying the mesh
+        let before = mesh.clone();
+        for width in [0.0, -0.1, f64::NAN, f64::INFINITY] {
+            assert!(matches!(
+                mesh.try_bevel_edges([far], width, 1, BevelProfile::Flat),
+                Err(ModellingError::DegenerateFace(_))
+            ));
+        }
+        assert!(matches!(
+            mesh.try_bevel_edges([far], 10.0, 1, BevelProfile::Flat),
+            Err(ModellingError::DegenerateFace(_))
+        ));
+        assert_eq!(mesh.num_vertices(), before.num_vertices());
+        assert_eq!(mesh.num_faces(), before.num_faces());
+    }
+
+    #[test]
+    fn test_chamfer_keeps_holes() {
+        let (mut mesh, _, outer) = square_with_hole();
+        let v = mesh.edge(outer).origin_id();
+        mesh.chamfer_vertices([v], 0.5);
+        assert!(mesh.check().is_ok(), "{:?}", mesh.check());
+        assert_eq!(mesh.num_faces(), 1);
+        assert_eq!(mesh.num_vertices(), 9);
+        let face = mesh.faces().next().unwrap();
+        assert_eq!(face.num_vertices(&mesh), 5);
+        assert_eq!(face.num_holes(), 1);
+        assert_eq!(face.hole_vertices(&mesh, 0).count(), 4);
+    }
+
+    #[test]
+    fn test_bevel_all_edges() {
+        let mut mesh = Mesh3d64::cube(1.0);
+        let edges: Vec<_> = mesh
+            .edges()
+            .filter(|e| e.id() < e.twin_id())
+            .map(|e| e.id())
+            .collect();
+        mesh.bevel_edges(edges.clone(), 0.1, 1, BevelProfile::Flat);
+        assert_closed(&mesh);
+        assert_eq!(mesh.num_faces(), 6 + 12 + 8);
+        assert_eq!(mesh.num_vertices(), 24);
+        let w = 0.1;
+        let expected = 1.0 - 6.0 * w * w + 16.0 * w * w * w / 3.0;
+        assert!((volume(&mesh) - expected).abs() < 1e-10);
+
+        let mut mesh = Mesh3d64::cube(1.0);
+        mesh.bevel_edges(edges, 0.1, 3, BevelProfile::Round);
+        assert_closed(&mesh);
+        assert!(volume(&mesh) < 1.0 && volume(&mesh) > expected);
+    }
+
+    #[test]
+    fn test_chamfer_vertices() {
+        let mut mesh = Mesh3d64::cube(1.0);
+        let v = mesh.vertex_ids().next().unwrap();
+        mesh.chamfer_vertices([v], 0.2);
+        assert_closed(&mesh);
+        assert_eq!(mesh.num_faces(), 7);
+        assert_eq!(mesh.num_vertices(), 10);
+        assert!((volume(&mesh) - (1.0 - 0.2 * 0.2 * 0.2 / 6.0)).abs() < 1e-10);
+
+        let mut mesh = Mesh3d64::regular_octahedron(1.0);
+        let vs: Vec<_> = mesh.vertex_ids().collect();
+        mesh.chamfer_vertices(vs, 0.25);
+        assert_closed(&mesh);
+        assert_eq!(mesh.num_faces(), 8 + 6);
+        assert_eq!(mesh.num_vertices(), 24);
+
+        assert_eq!(
+            mesh.try_chamfer_vertices([1000], 0.1).err(),
+            Some(ModellingError::MissingVertex(1000))
+        );
+
+        // the cuts at both ends of an edge must not overlap
+        let mut mesh = Mesh3d64::cube(1.0);
+        let vs: Vec<_> = mesh.vertex_ids().collect();
+        assert!(matches!(
+            mesh.try_chamfer_vertices(vs.clone(), 0.5),
+            Err(ModellingError::DegenerateFace(_))
+        ));
+        assert!(matches!(
+            mesh.try_chamfer_vertices(vs, -0.1),
+            Err(ModellingError::DegenerateFace(_))
+        ));
+        assert_closed(&mesh);
+        assert_eq!(mesh.num_vertices(), 8);
+    }
+}
@@ -0,0 +1,726 @@
+use crate::{
+    math::{HasPosition, Scalar, Transformable, Vector, Vector3D},
+    mesh::{
+        dedup_loop, drop_unused_vertices, weld, DefaultEdgePayload, Face3d, FaceBasics, MeshBasics,
+        MeshBuilder, MeshType3D, ModellingError, Triangulation, VertexBasics,
+    },
+    tesselate::{triangulate_face, TesselationMeta, TriangulationAlgorithm},
+};
+
+/// The boolean operations supported by `MeshBoolean`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BooleanOperation {
+    /// The volume covered by at least one of the meshes.
+    Union,
+
+    /// The volume covered by both meshes.
+    Intersection,
+
+    /// The volume covered by the first but not by the second mesh.
+    Difference,
+
+    /// The volume covered by exactly one of the meshes.
+    SymmetricDifference,
+}
+
+const COPLANAR: u8 = 0;
+const FRONT: u8 = 1;
+const BACK: u8 = 2;
+const SPANNING: u8 = 3;
+
+#[derive(Debug, Clone, Copy)]
+struct Plane<S: Scalar, V: Vector3D<S = S>> {
+    normal: V,
+    w: S,
+}
+
+/// A convex, planar polygon that remembers the payload of the face it was cut from.
+struct Polygon<T: MeshType3D> {
+    vertices: Vec<T::VP>,
+    plane: Plane<T::S, T::Vec>,
+    fp: T::FP,
+}
+
+impl<T: MeshType3D> Clone for Polygon<T> {
+    fn clone(&self) -> Self {
+        Self {
+            vertices: self.vertices.clone(),
+            plane: self.plane,
+            fp: self.fp,
+        }
+    }
+}
+
+impl<T: MeshType3D> Polygon<T> {
+    fn flip(&mut self) {
+        self.vertices.reverse();
+        self.plane.normal = -self.plane.normal;
+        self.plane.w = -self.plane.w;
+    }
+}
+
+/// Where a polygon ended up after splitting it by a plane.
+enum Split<T: MeshType3D> {
+    CoplanarFront(Polygon<T>),
+    CoplanarBack(Polygon<T>),
+    Front(Polygon<T>),
+    Back(Polygon<T>),
+    Spanning(Option<Polygon<T>>, Option<Polygon<T>>),
+}
+
+impl<S: Scalar, V: Vector3D<S = S>> Plane<S, V> {
+    fn classify(&self, p: V, eps: S) -> u8 {
+        let d = self.normal.dot(&p) - self.w;
+        if d < -eps {
+            BACK
+        } else if d > eps {
+            FRONT
+        } else {
+            COPLANAR
+        }
+    }
+
+    /// Interpolates the payloads at the intersection of the segment with the plane.
+    /// The endpoints are ordered first so the shared edge of two neighboring polygons is cut at the exact same point.
+    fn intersect<VP: Transformable<3, S = S> + HasPosition<3, V, S = S>>(
+        &self,
+        a: &VP,
+        b: &VP,
+    ) -> VP {
+        let (pa, pb) = (a.pos().to_array(), b.pos().to_array());
+        let (a, b) = if pb.partial_cmp(&pa) == Some(std::cmp::Ordering::Less) {
+            (b, a)
+        } else {
+            (a, b)
+        };
+        let t = (self.w - self.normal.dot(a.pos())) / self.normal.dot(&(*b.pos() - *a.pos()));
+        a.lerped(b, t)
+    }
+
+    fn split<T: MeshType3D<S = S, Vec = V>>(&self, polygon: Polygon<T>, eps: S) -> Split<T> {
+        let types: Vec<u8> = polygon
+            .vertices
+            .iter()
+            .map(|v| self.classify(*v.pos(), eps))
+            .collect();
+        match types.iter().fold(COPLANAR, |acc, t| acc | t) {
+            COPLANAR => {
+                if self.normal.dot(&polygon.plane.normal) > S::ZERO {
+                    Split::CoplanarFront(polygon)
+                } else {
+                    Split::CoplanarBack(polygon)
+                }
+            }
+            FRONT => Split::Front(polygon),
+            BACK => Split::Back(polygon),
+            _ => {
+                let n = polygon.vertices.len();
+                let mut front = Vec::new();
+                let mut back = Vec::new();
+                for i in 0..n {
+                    let j = (i + 1) % n;
+                    let (vi, vj) = (&polygon.vertices[i], &polygon.vertices[j]);
+                    if types[i] != BACK {
+                        front.push(vi.clone());
+                    }
+                    if types[i] != FRONT {
+                        back.push(vi.clone());
+                    }
+                    if types[i] | types[j] == SPANNING {
+                        let v = self.intersect(vi, vj);
+                        front.push(v.clone());
+                        back.push(v);
+                    }
+                }
+                let fragment = |vertices: Vec<T::VP>| {
+                    (vertices.len() >= 3).then(|| Polygon {
+                        vertices,
+                        plane: polygon.plane,
+                        fp: polygon.fp,
+                    })
+                };
+                Split::Spanning(fragment(front), fragment(back))
+            }
+        }
+    }
+}
+
+struct BspNode<T: MeshType3D> {
+    plane: Option<Plane<T::S, T::Vec>>,
+    front: Option<usize>,
+    back: Option<usize>,
+    polygons: Vec<Polygon<T>>,
+}
+
+impl<T: MeshType3D> BspNode<T> {
+    fn new() -> Self {
+        Self {
+            plane: None,
+            front: None,
+            back: None,
+            polygons: Vec::new(),
+        }
+    }
+}
+
+/// A binary space partitioning tree of polygons stored in an arena to avoid deep recursion.
+///
+/// Based on the algorithm of [csg.js](https://github.com/evanw/csg.js).
+struct BspTree<T: MeshType3D> {
+    nodes: Vec<BspNode<T>>,
+    eps: T::S,
+}
+
+impl<T: MeshType3D> BspTree<T> {
+    fn new(polygons: Vec<Polygon<T>>, eps: T::S) -> Self {
+        let mut tree = Self {
+            nodes: vec![BspNode::new()],
+            eps,
+        };
+        tree.build(polygons);
+        tree
+    }
+
+    fn child(&mut self, i: usize, front: bool) -> usize {
+        let existing = if front {
+            self.nodes[i].front
+        } else {
+            self.nodes[i].back
+        };
+        existing.unwrap_or_else(|| {
+            let c = self.nodes.len();
+            self.nodes.push(BspNode::new());
+            if front {
+                self.nodes[i].front = Some(c);
+            } else {
+                self.nodes[i].back = Some(c);
+            }
+            c
+        })
+    }
+
+    /// Inserts the polygons into the tree, splitting them where necessary.
+    fn build(&mut self, polygons: Vec<Polygon<T>>) {
+        let mut stack = vec![(0, polygons)];
+        while let Some((i, polygons)) = stack.pop() {
+            if polygons.is_empty() {
+                continue;
+            }
+            let plane = *self.nodes[i].plane.get_or_insert(polygons[0].plane);
+            let mut front = Vec::new();
+            let mut back = Vec::new();
+            for polygon in polygons {
+                match plane.split(polygon, self.eps) {
+                    Split::CoplanarFront(p) | Split::CoplanarBack(p) => {
+                        self.nodes[i].polygons.push(p);
+                    }
+                    Split::Front(p) => front.push(p),
+                    Split::Back(p) => back.push(p),
+                    Split::Spanning(f, b) => {
+                        front.extend(f);
+                        back.extend(b);
+                    }
+                }
+            }
+            if !front.is_empty() {
+                stack.push((self.child(i, true), front));
+            }
+            if !back.is_empty() {
+                stack.push((self.child(i, false), back));
+            }
+        }
+    }
+
+    /// Swaps solid and empty space.
+    fn invert(&mut self) {
+        for node in self.nodes.iter_mut() {
+            for polygon in node.polygons.iter_mut() {
+                polygon.flip();
+            }
+            if let Some(plane) = node.plane.as_mut() {
+                plane.normal = -plane.normal;
+                plane.w = -plane.w;
+            }
+            std::mem::swap(&mut node.front, &mut node.back);
+        }
+    }
+
+    /// Removes all parts of the polygons that are inside the solid of this tree.
+    fn clip_polygons(&self, polygons: Vec<Polygon<T>>) -> Vec<Polygon<T>> {
+        if self.nodes[0].plane.is_none() {
+            return polygons;
+        }
+        let mut result = Vec::new();
+        let mut stack = vec![(0, polygons)];
+        while let Some((i, polygons)) = stack.pop() {
+            let node = &self.nodes[i];
+            let plane = node.plane.expect("BSP nodes always have a plane");
+            let mut front = Vec::new();
+            let mut back = Vec::new();
+            for polygon in polygons {
+                match plane.split(polygon, self.eps) {
+                    Split::CoplanarFront(p) | Split::Front(p) => front.push(p),
+                    Split::CoplanarBack(p) | Split::Back(p) => back.push(p),
+                    Split::Spanning(f, b) => {
+                        front.extend(f);
+                        back.extend(b);
+                    }
+                }
+            }
+            match node.front {
+                Some(c) => stack.push((c, front)),
+                None => result.extend(front),
+            }
+            if let Some(c) = node.back {
+                stack.push((c, back));
+            }
+        }
+        result
+    }
+
+    /// Removes all parts of the polygons in this tree that are inside the solid of the other tree.
+    fn clip_to(&mut self, other: &Self) {
+        for node in self.nodes.iter_mut() {
+            node.polygons = other.clip_polygons(std::mem::take(&mut node.polygons));
+        }
+    }
+
+    fn all_polygons(&self) -> Vec<Polygon<T>> {
+        self.nodes
+            .iter()
+            .flat_map(|node| node.polygons.iter().cloned())
+            .collect()
+    }
+}
+
+fn is_convex<T: MeshType3D>(vertices: &[T::VP], normal: T::Vec, eps: T::S) -> bool {
+    let n = vertices.len();
+    (0..n).all(|i| {
+        let a = *vertices[i].pos();
+        let b = *vertices[(i + 1) % n].pos();
+        let c = *vertices[(i + 2) % n].pos();
+        (b - a).cross(&(c - b)).dot(&normal) >= -eps * eps
+    })
+}
+
+/// Collects the faces of the mesh as convex polygons. Non-convex faces and faces with holes are triangulated.
+fn polygons<T: MeshType3D>(mesh: &T::Mesh, eps: T::S) -> Vec<Polygon<T>> {
+    let mut res = Vec::new();
+    let mut indices = Vec::new();
+    let mut meta = TesselationMeta::default();
+    for face in mesh.faces() {
+        // faces without area don't bound any volume and have no plane
+        let normal = Face3d::normal(face, mesh);
+        if normal.length() <= eps * eps {
+            continue;
+        }
+        let normal = normal.normalize();
+        let vertices: Vec<T::VP> = face.vertices(mesh).map(|v| v.payload().clone()).collect();
+        let w = vertices
+            .iter()
+            .map(|v| normal.dot(v.pos()))
+            .fold(T::S::ZERO, |acc, x| acc + x)
+            / T::S::from_usize(vertices.len());
+        let plane = Plane { normal, w };
+        if !face.has_holes() && is_convex::<T>(&vertices, normal, eps) {
+            res.push(Polygon {
+                vertices,
+                plane,
+                fp: *face.payload(),
+            });
+            continue;
+        }
+
+        indices.clear();
+        let mut tri = Triangulation::new(&mut indices);
+        triangulate_face::<T>(
+            face,
+            mesh,
+            &mut tri,
+            TriangulationAlgorithm::Auto,
+            &mut meta,
+        );
+        for t in indices.chunks(3) {
+            res.push(Polygon {
+                vertices: t
+                    .iter()
+                    .map(|&v| mesh.vertex(v).payload().clone())
+                    .collect(),
+                plane,
+                fp: *face.payload(),
+            });
+        }
+    }
+    res
+}
+
+/// Returns the welded vertices lying strictly inside the segment from `a` to `b`, sorted from `a` to `b`.
+fn vertices_on_segment<T: MeshType3D>(
+    vps: &[T::VP],
+    eps: T::S,
+    by_x: &[usize],
+    a: usize,
+    b: usize,
+) -> Result<Vec<usize>, ModellingError> {
+    let (pa, pb) = (*vps[a].pos(), *vps[b].pos());
+    let d = pb - pa;
+    let len2 = d.length_squared();
+    let (lo, hi) = if pa.x() < pb.x() {
+        (pa.x() - eps, pb.x() + eps)
+    } else {
+        (pb.x() - eps, pa.x() + eps)
+    };
+    let start = by_x.partition_point(|&i| vps[i].pos().x() < lo);
+    let mut res: Vec<(T::S, usize)> = by_x[start..]
+        .iter()
+        .take_while(|&&i| vps[i].pos().x() <= hi)
+        .filter(|&&i| i != a && i != b)
+        .filter_map(|&i| {
+            let p = *vps[i].pos();
+            let t = (p - pa).dot(&d) / len2;
+            let inside = t * len2.sqrt() > eps && (T::S::ONE - t) * len2.sqrt() > eps;
+            (inside && (pa + d * t).distance(&p) <= eps).then_some((t, i))
+        })
+        .collect();
+    if let Some(&(_, i)) = res.iter().find(|(t, _)| !t.is_finite()) {
+        return Err(ModellingError::NonFinite(format!(
+            "The position of vertex {} on the segment from {} to {}",
+            i, a, b
+        )));
+    }
+    res.sort_by(|x, y| x.0.partial_cmp(&y.0).unwrap());
+    Ok(res.into_iter().map(|(_, i)| i).collect())
+}
+
+/// Splits the loop at vertices that are visited twice and drops loops without area.
+fn clean_loop<T: MeshType3D>(
+    vps: &[T::VP],
+    eps: T::S,
+    indices: Vec<usize>,
+    res: &mut Vec<Vec<usize>>,
+) {
+    let mut stack: Vec<usize> = Vec::new();
+    for i in indices {
+        if let Some(pos) = stack.iter().position(|&j| j == i) {
+            // the inner loop starts and the outer loop continues at the shared vertex
+            let inner = stack.split_off(pos);
+            stack.push(i);
+            clean_loop::<T>(vps, eps, inner, res);
+            continue;
+        }
+        stack.push(i);
+    }
+
+    if stack.len() < 3 {
+        return;
+    }
+    let p0 = *vps[stack[0]].pos();
+    let area = stack
+        .windows(2)
+        .skip(1)
+        .map(|w| (*vps[w[0]].pos() - p0).cross(&(*vps[w[1]].pos() - p0)))
+        .fold(T::Vec::zero(), |acc, x| acc + x)
+        .length();
+    if area > eps * eps {
+        res.push(stack);
+    }
+}
+
+/// Builds a mesh from groups of polygons. Vertices are only welded within the same group.
+fn build_mesh<T: MeshType3D>(
+    groups: Vec<Vec<Polygon<T>>>,
+    eps: T::S,
+) -> Result<T::Mesh, ModellingError>
+where
+    T::EP: DefaultEdgePayload,
+{
+    let mut vps = Vec::new();
+    let mut faces = Vec::new();
+    for group in groups {
+        let (positions, ids) = weld(
+            group.iter().flat_map(|p| p.vertices.iter()).map(|v| {
+                let p = v.pos();
+                [p.x().to_f64(), p.y().to_f64(), p.z().to_f64()]
+            }),
+            eps.to_f64(),
+        );
+        // the welded vertices are numbered by their first occurrence and keep its payload
+        let mut welded: Vec<T::VP> = Vec::with_capacity(positions.len());
+        for (v, &i) in group.iter().flat_map(|p| p.vertices.iter()).zip(ids.iter()) {
+            if i == welded.len() {
+                welded.push(v.clone());
+            }
+        }
+        let mut ids = ids.into_iter();
+        let loops: Vec<(Vec<usize>, T::FP)> = group
+            .iter()
+            .map(|p| (ids.by_ref().take(p.vertices.len()).collect(), p.fp))
+            .collect();
+
+        // fix t-junctions so every edge has a matching twin
+        if let Some(i) =
+            (0..welded.len()).find(|&i| !welded[i].pos().to_array().iter().all(|x| x.is_finite()))
+        {
+            return Err(ModellingError::NonFinite(format!(
+                "The position of the welded vertex {}",
+                i
+            )));
+        }
+        let mut by_x: Vec<usize> = (0..welded.len()).collect();
+        by_x.sort_by(|&i, &j| {
+            welded[i]
+                .pos()
+                .x()
+                .partial_cmp(&welded[j].pos().x())
+                .unwrap()
+        });
+        let offset = vps.len();
+        for (indices, fp) in loops {
+            let n = indices.len();
+            let mut fixed = Vec::with_capacity(n);
+            for k in 0..n {
+                let (a, b) = (indices[k], indices[(k + 1) % n]);
+                fixed.push(a);
+                if a != b {
+                    fixed.extend(vertices_on_segment::<T>(&welded, eps, &by_x, a, b)?);
+                }
+            }
+            let mut cleaned = Vec::new();
+            clean_loop::<T>(&welded, eps, dedup_loop(fixed), &mut cleaned);
+            for l in cleaned {
+                faces.push((l.into_iter().map(|i| i + offset).collect::<Vec<_>>(), fp));
+            }
+        }
+        vps.extend(welded);
+    }
+
+    let used_vps = drop_unused_vertices(&vps, &mut faces);
+    let mut mesh = T::Mesh::default();
+    mesh.insert_indexed_faces(used_vps, faces)?;
+    Ok(mesh)
+}
+
+/// Boolean operations (constructive solid geometry) for closed meshes.
+///
+/// The faces of both meshes are cut along the other mesh using binary space partitioning trees.
+/// Payloads of new vertices at the cuts are interpolated using `Transformable::lerp`.
+/// Faces that are coplanar with a face of the other mesh are handled consistently,
+/// i.e., touching solids are fused and coincident surfaces are removed.
+///
+/// The result is not triangulated, but faces can be split into several convex pieces.
+/// Faces of `other` that end up on the inside of the result (e.g., when subtracting)
+/// are inverted but keep their vertex normals, so regenerate the normals if necessary.
+///
+/// Returns an error if the result is not an oriented manifold,
+/// e.g., if the two solids only touch in an edge.
+pub trait MeshBoolean<T: MeshType3D<Mesh = Self>>: MeshBasics<T>
+where
+    T::EP: DefaultEdgePayload,
+{
+    /// Applies the given boolean operation to `self` and `other` and returns the result as a new mesh.
+    /// Both meshes must be closed and consistently oriented.
+    fn boolean(&self, other: &Self, operation: BooleanOperation) -> Result<Self, ModellingError> {
+        // use a tolerance relative to the size of the meshes
+        let coordinates = || {
+            self.vertices()
+                .chain(other.vertices())
+                .flat_map(|v| v.pos().to_array())
+        };
+        if coordinates().any(|x| !x.is_finite()) {
+            return Err(ModellingError::NonFinite(
+                "A vertex position is NaN or infinite".to_string(),
+            ));
+        }
+        let size = coordinates().fold(T::S::ZERO, |acc, x| acc.max(x.abs()));
+        // only clamp to a small minimum so tiny meshes don't get a tolerance of zero
+        let eps = (size * T::S::from_f64(1e-5)).max(T::S::EPS);
+
+        let mut a = BspTree::new(polygons::<T>(self, eps), eps);
+        let mut b = BspTree::new(polygons::<T>(other, eps), eps);
+        let groups = match operation {
+            BooleanOperation::Union => {
+                a.clip_to(&b);
+                b.clip_to(&a);
+                b.invert();
+                b.clip_to(&a);
+                b.invert();
+                a.build(b.all_polygons());
+                vec![a.all_polygons()]
+            }
+            BooleanOperation::Intersection => {
+                a.invert();
+                b.clip_to(&a);
+                b.invert();
+                a.clip_to(&b);
+                b.clip_to(&a);
+                a.build(b.all_polygons());
+                a.invert();
+                vec![a.all_polygons()]
+            }
+            BooleanOperation::Difference => {
+                a.invert();
+                a.clip_to(&b);
+                b.clip_to(&a);
+                b.invert();
+                b.clip_to(&a);
+                b.invert();
+                a.build(b.all_polygons());
+                a.invert();
+                vec![a.all_polygons()]
+            }
+            BooleanOperation::SymmetricDifference => {
+                // both differences only touch along the intersection curve, so don't weld them
+                let ab = self.boolean(other, BooleanOperation::Difference)?;
+                let ba = other.boolean(self, BooleanOperation::Difference)?;
+                vec![polygons::<T>(&ab, eps), polygons::<T>(&ba, eps)]
+            }
+        };
+        build_mesh::<T>(groups, eps)
+    }
+
+    /// Returns the volume covered by at least one of the meshes.
+    fn union(&self, other: &Self) -> Result<Self, ModellingError> {
+        self.boolean(other, BooleanOperation::Union)
+    }
+
+    /// Returns the volume covered by both meshes.
+    fn intersection(&self, other: &Self) -> Result<Self, ModellingError> {
+        self.boolean(other, BooleanOperation::Intersection)
+    }
+
+    /// Returns the volume covered by `self` but not by `other`.
+    fn difference(&self, other: &Self) -> Result<Self, ModellingError> {
+        self.boolean(other, BooleanOperation::Difference)
+    }
+
+    /// Returns the volume covered by exactly one of the meshes.
+    fn symmetric_difference(&self, other: &Self) -> Result<Self, ModellingError> {
+        self.boolean(other, BooleanOperation::SymmetricDifference)
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "nalgebra")]
+mod tests {
+    use crate::test_util::{assert_closed, volume};
+    use crate::{extensions::nalgebra::*, prelude::*};
+
+    fn assert_solid(mesh: &Mesh3d64, expected_volume: f64) {
+        assert_closed(mesh);
+        assert!(
+            (volume(mesh) - expected_volume).abs() < 1e-8,
+            "expected volume {} but got {}",
+            expected_volume,
+            volume(mesh)
+        );
+    }
+
+    #[test]
+    fn test_clean_figure_eight() {
+        let vps = [
+            (-1.0, -1.0),
+            (0.0, 0.0),
+            (1.0, -1.0),
+            (1.0, 1.0),
+            (-1.0, 1.0),
+            (-1.5, 0.0),
+        ]
+        .map(|(x, y)| VertexPayloadPNU::from_pos(Vec3::new(x, y, 0.0)));
+
+        // both lobes keep the vertex where the loop touches itself
+        let mut loops = Vec::new();
+        super::clean_loop::<MeshType3d64PNU>(&vps, 1e-6, vec![0, 1, 2, 3, 1, 4, 5], &mut loops);
+        assert_eq!(loops, vec![vec![1, 2, 3], vec![0, 1, 4, 5]]);
+    }
+
+    #[test]
+    fn test_polygons_skip_degenerate_faces() {
+        let mut mesh = Mesh3d64::new();
+        for ps in [
+            vec![(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0)],
+            vec![(0.0, 2.0), (1.0, 2.0), (2.0, 2.0)],
+        ] {
+            mesh.insert_polygon(
+                ps.into_iter()
+                    .map(|(x, y)| VertexPayloadPNU::from_pos(Vec3::new(x, y, 0.0))),
+            );
+        }
+        let polygons = super::polygons::<MeshType3d64PNU>(&mesh, 1e-6);
+        assert_eq!(polygons.len(), 1);
+        assert_eq!(polygons[0].vertices.len(), 4);
+        assert!((polygons[0].plane.normal.z.abs() - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_boolean_overlapping_cubes() {
+        let a = Mesh3d64::cube(1.0);
+        let b = a.translated(&Vec3::new(0.5, 0.5, 0.5));
+        assert_solid(&a.union(&b).unwrap(), 1.875);
+        assert_solid(&a.intersection(&b).unwrap(), 0.125);
+        assert_solid(&a.difference(&b).unwrap(), 0.875);
+        assert_solid(&b.difference(&a).unwrap(), 0.875);
+        assert_solid(&a.symmetric_difference(&b).unwrap(), 1.75);
+
+        // cut vertices are interpolated and lie on the surface of both cubes
+        let c = a.intersection(&b).unwrap();
+        assert_eq!(c.num_vertices(), 8);
+        for v in c.vertices() {
+            assert!(v.pos().iter().all(|x| x.abs() < 0.5 + 1e-10));
+            assert!(v.pos().iter().all(|x| x > &-1e-10));
+        }
+    }
+
+    #[test]
+    fn test_boolean_coplanar_faces() {
+        let a = Mesh3d64::cube(1.0);
+
+        // touching in a face
+        let b = a.translated(&Vec3::new(1.0, 0.0, 0.0));
+        assert_solid(&a.union(&b).unwrap(), 2.0);
+        assert_eq!(a.intersection(&b).unwrap().num_faces(), 0);
+
+        // sharing the top, bottom, front, and back planes
+        let b = a.translated(&Vec3::new(0.5, 0.0, 0.0));
+        assert_solid(&a.union(&b).unwrap(), 1.5);
+        assert_solid(&a.intersection(&b).unwrap(), 0.5);
+        assert_solid(&a.difference(&b).unwrap(), 0.5);
+        assert_solid(&a.symmetric_difference(&b).unwrap(), 1.0);
+
+        // identical solids
+        assert_solid(&a.union(&a).unwrap(), 1.0);
+        assert_eq!(a.difference(&a).unwrap().num_faces(), 0);
+
+        // a hole through the cube
+        let b = a.scaled(&Vec3::new(0.5, 2.0, 0.5));
+        assert_solid(&a.difference(&b).unwrap(), 0.75);
+    }
+
+    #[test]
+    fn test_boolean_curved() {
+        let sphere = Mesh3d64::uv_sphere(1.0, 16, 16);
+        let prism = Mesh3d64::regular_prism(0.4, 3.0, 7).translated(&Vec3::new(0.1, -1.5, 0.2));
+        for op in [
+            BooleanOperation::Union,
+            BooleanOperation::Intersection,
+            BooleanOperation::Difference,
+            BooleanOperation::SymmetricDifference,
+        ] {
+            let mesh = sphere.boolean(&prism, op).unwrap();
+            assert!(mesh.check().is_ok(), "{:?}", op);
+            assert!(mesh.edges().all(|e| !e.is_boundary_self()), "{:?}", op);
+        }
+        let v = |op| volume(&sphere.boolean(&prism, op).unwrap());
+        assert!(
+            (v(BooleanOperation::Union) + v(BooleanOperation::Intersection)
+                - volume(&sphere)
+                - volume(&prism))
+            .abs()
+                < 1e-8
+        );
+        assert!(
+            (v(BooleanOperation::Difference) + v(BooleanOperation::Intersection) - volume(&sphere))
+                .abs()
+                < 1e-8
+        );
+    }
+}
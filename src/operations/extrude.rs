@@ -2,11 +2,12 @@ use crate::{
     math::{Scalar, Transformable},
     mesh::{
         DefaultEdgePayload, DefaultFacePayload, EdgeBasics, EuclideanMeshType, FaceBasics,
-        HalfEdge, MeshTypeHalfEdge, VertexBasics,
+        HalfEdge, MeshType3D, MeshTypeHalfEdge, ModellingError, VertexBasics,
     },
-    operations::MeshLoft,
+    operations::{offset_faces, selected_faces, MeshLoft},
 };
 use itertools::Itertools;
+use std::collections::HashMap;
 
 // TODO: Adjust this to not be halfedge-specific
 
@@ -33,6 +34,35 @@ where
         }
     }
 
+    /// Moves the given faces along their averaged normals by `distance` and connects them to
+    /// the rest of the mesh with a row of quads. Unlike `extrude_face`, faces that share an edge
+    /// stay connected, i.e., only the outer boundary of the region gets side walls.
+    ///
+    /// The extruded faces keep their ids. Only the vertices on the boundary of the region are duplicated.
+    /// Fails without modifying the mesh if `distance` is negative or not finite.
+    /// Returns the ids of the extruded faces in the order of `faces`.
+    fn try_extrude_region(
+        &mut self,
+        faces: impl IntoIterator<Item = T::F>,
+        distance: T::S,
+    ) -> Result<Vec<T::F>, ModellingError>
+    where
+        T: MeshType3D<Mesh = Self>,
+    {
+        let faces = selected_faces::<T>(self, faces)?;
+        let region: HashMap<T::F, usize> = faces.iter().map(|&f| (f, 0)).collect();
+        offset_faces::<T>(self, &faces, &region, T::S::ZERO, distance)
+    }
+
+    /// Same as `try_extrude_region` but panics if the result is not a manifold.
+    fn extrude_region(&mut self, faces: impl IntoIterator<Item = T::F>, distance: T::S) -> Vec<T::F>
+    where
+        T: MeshType3D<Mesh = Self>,
+    {
+        self.try_extrude_region(faces, distance)
+            .unwrap_or_else(|e| panic!("Failed to extrude the region: {}", e))
+    }
+
     /// Extrudes the given edge using the given transformation.
     /// Returns an edge on the boundary of the extrusion.
     ///
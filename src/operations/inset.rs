@@ -0,0 +1,469 @@
+use crate::{
+    math::{HasPosition, IndexType, Scalar, Vector, Vector3D},
+    mesh::{
+        DefaultEdgePayload, DefaultFacePayload, EdgeBasics, Face3d, FaceBasics, HalfEdge,
+        MeshBasics, MeshBuilder, MeshType3D, MeshTypeHalfEdge, ModellingError, VertexBasics,
+    },
+};
+use std::collections::{HashMap, HashSet};
+
+/// How to inset multiple faces.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum InsetMode {
+    /// Every face is inset on its own.
+    Individual,
+
+    /// Connected faces are inset together, i.e., only the outer boundary of the region is inset
+    /// and the edges between selected faces are kept.
+    #[default]
+    Region,
+}
+
+/// Where a vertex of a selected face ends up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Corner<E: IndexType, V: IndexType> {
+    /// The vertex is on the boundary of the region. Identified by the outgoing boundary halfedge.
+    Boundary(E),
+
+    /// All faces around the vertex belong to the region.
+    Interior(V),
+}
+
+/// Moves the selected faces inwards by `inset` (in the plane of the faces) and along their
+/// averaged normals by `distance`. Connects the moved faces to the rest of the mesh with quads
+/// along the boundaries of the regions. Faces with the same region id are moved together.
+/// The offset along the normals is scaled so that the faces keep their thickness at corners.
+///
+/// Only the selected faces are replaced and they keep their ids. Vertices inside a region are moved
+/// in place. If the offsets are negative or not finite, the inset collapses an edge of a face,
+/// or the faces cannot be replaced, the mesh is not modified.
+/// Returns the ids of the moved faces in the order of `faces`.
+pub(crate) fn offset_faces<T: MeshTypeHalfEdge + MeshType3D>(
+    target: &mut T::Mesh,
+    faces: &[T::F],
+    region: &HashMap<T::F, usize>,
+    inset: T::S,
+    distance: T::S,
+) -> Result<Vec<T::F>, ModellingError>
+where
+    T::EP: DefaultEdgePayload,
+    T::FP: DefaultFacePayload,
+{
+    for (name, value) in [("inset", inset), ("distance", distance)] {
+        if !value.is_finite() {
+            return Err(ModellingError::NonFinite(format!(
+                "The {} {} is NaN or infinite",
+                name, value
+            )));
+        }
+        if value < T::S::ZERO {
+            return Err(ModellingError::DegenerateFace(format!(
+                "The {} {} must not be negative",
+                name, value
+            )));
+        }
+    }
+
+    let mesh = &*target;
+    let same_region = |h: T::E, g: T::E| {
+        let (e, f) = (mesh.edge(h), mesh.edge(g));
+        !e.is_boundary_self()
+            && !f.is_boundary_self()
+            && region.get(&e.face_id()).is_some()
+            && region.get(&e.face_id()) == region.get(&f.face_id())
+    };
+    let normal = |h: T::E| Face3d::normal(mesh.edge(h).face(mesh).unwrap(), mesh).normalize();
+    let dir = |h: T::E| {
+        let e = mesh.edge(h);
+        (*e.target(mesh).payload().pos() - *e.origin(mesh).payload().pos()).normalize()
+    };
+
+    // the averaged normal scaled such that the adjacent faces are moved by the same distance
+    let shell = |normals: &[T::Vec]| {
+        let n = normals
+            .iter()
+            .fold(T::Vec::zero(), |acc, &m| acc + m)
+            .normalize();
+        let cos = normals
+            .iter()
+            .map(|m| n.dot(m))
+            .fold(T::S::ZERO, |a, b| a + b)
+            / T::S::from_usize(normals.len());
+        if cos > T::S::from_f64(1e-6) {
+            n * (T::S::ONE / cos)
+        } else {
+            n
+        }
+    };
+
+    // rotates across the edges inside the region until the boundary of the region is reached
+    let corner = |h: T::E| {
+        let mut c = h;
+        loop {
+            let twin = mesh.edge(c).twin_id();
+            if !same_region(c, twin) {
+                return Corner::Boundary(c);
+            }
+            c = mesh.edge(twin).next_id();
+            if c == h {
+                return Corner::Interior(mesh.edge(h).origin_id());
+            }
+        }
+    };
+
+    let moved_payload = |c: Corner<T::E, T::V>| -> T::VP {
+        let (v, offset) = match c {
+            Corner::Interior(v) => {
+                let normals: Vec<T::Vec> = mesh
+                    .vertex(v)
+                    .edges_out(mesh)
+                    .map(|h| normal(h.id()))
+                    .collect();
+                (v, shell(&normals) * distance)
+            }
+            Corner::Boundary(b) => {
+                // collect the faces of the region between the incoming and the outgoing boundary halfedge
+                let mut normals = vec![normal(b)];
+                let mut a = mesh.edge(b).prev_id();
+                while same_region(a, mesh.edge(a).twin_id()) {
+                    let twin = mesh.edge(a).twin_id();
+                    normals.push(normal(twin));
+                    a = mesh.edge(twin).prev_id();
+                }
+                let offset = shell(&normals);
+                let n = offset.normalize();
+                let (in_a, in_b) = (n.cross(&dir(a)).normalize(), n.cross(&dir(b)).normalize());
+                let m = (in_a + in_b).normalize();
+                let cos = m.dot(&in_b);
+                let inward = if cos > T::S::from_f64(1e-6) {
+                    m * (inset / cos)
+                } else {
+                    in_b * inset
+                };
+                (mesh.edge(b).origin_id(), inward + offset * distance)
+            }
+        };
+        let mut vp = mesh.vertex(v).payload().clone();
+        vp.set_pos(*vp.pos() + offset);
+        vp
+    };
+
+    // the corners of the moved faces (including their holes) and the side quads along the region boundaries
+    let mut payloads: HashMap<Corner<T::E, T::V>, T::VP> = HashMap::new();
+    let mut moved = Vec::new();
+    let mut sides = Vec::new();
+    let mut moved_edges = Vec::new();
+    for &f in faces {
+        let face = mesh.face(f);
+        let mut loops = vec![face.edge_ids(mesh).collect::<Vec<_>>()];
+        for hole in 0..face.num_holes() {
+            loops.push(face.hole_edges(mesh, hole).map(|e| e.id()).collect());
+        }
+        let mut corners = Vec::new();
+        for edges in loops {
+            let mut cs = Vec::new();
+            for h in edges {
+                let c = corner(h);
+                payloads.entry(c).or_insert_with(|| moved_payload(c));
+                cs.push(c);
+                moved_edges.push((h, c, corner(mesh.edge(h).next_id())));
+                if c == Corner::Boundary(h) {
+                    let e = mesh.edge(h);
+                    sides.push((e.origin_id(), e.target_id(mesh), corner(e.next_id()), c));
+                }
+            }
+            corners.push(cs);
+        }
+        moved.push((corners, *face.payload()));
+    }
+
+    // an inset larger than the face collapses or flips the edges of the moved face
+    for (h, a, b) in moved_edges {
+        let e = mesh.edge(h);
+        let before = *e.target(mesh).payload().pos() - *e.origin(mesh).payload().pos();
+        let after = *payloads[&b].pos() - *payloads[&a].pos();
+        if after.dot(&before) <= T::S::ZERO {
+            return Err(ModellingError::DegenerateFace(format!(
+                "The inset {} is too large for face {}",
+                inset,
+                e.face_id()
+            )));
+        }
+    }
+
+    // vertices on the boundary of a region are duplicated, the ones inside are moved
+    let backup = target.clone();
+    let mut ids: HashMap<Corner<T::E, T::V>, T::V> = HashMap::new();
+    let mut vertex = |c: Corner<T::E, T::V>, target: &mut T::Mesh| -> T::V {
+        *ids.entry(c).or_insert_with(|| match c {
+            Corner::Interior(v) => v,
+            Corner::Boundary(_) => target.add_vertex(payloads[&c].clone()),
+        })
+    };
+    let mut new_faces = Vec::new();
+    for (corners, fp) in moved {
+        let loops = corners
+            .into_iter()
+            .map(|cs| cs.into_iter().map(|c| vertex(c, target)).collect())
+            .collect();
+        new_faces.push((loops, fp));
+    }
+    for (a, b, c, d) in sides {
+        let quad = vec![a, b, vertex(c, target), vertex(d, target)];
+        new_faces.push((vec![quad], T::FP::default()));
+    }
+
+    let fs = match target.try_replace_faces(faces.iter().copied(), new_faces) {
+        Ok(fs) => fs,
+        Err(e) => {
+            *target = backup;
+            return Err(e);
+        }
+    };
+    for (c, vp) in payloads {
+        if let Corner::Interior(v) = c {
+            *target.vertex_mut(v).payload_mut() = vp;
+        }
+    }
+    Ok(fs[..faces.len()].to_vec())
+}
+
+/// Checks the faces and removes duplicates.
+pub(crate) fn selected_faces<T: MeshTypeHalfEdge>(
+    mesh: &T::Mesh,
+    faces: impl IntoIterator<Item = T::F>,
+) -> Result<Vec<T::F>, ModellingError> {
+    let mut seen = HashSet::new();
+    let mut res = Vec::new();
+    for f in faces {
+        if mesh.try_face(f).is_none() {
+            return Err(ModellingError::MissingFace(f.index()));
+        }
+        if seen.insert(f) {
+            res.push(f);
+        }
+    }
+    Ok(res)
+}
+
+/// Inset operations for meshes.
+///
+/// The inset faces keep their ids and payloads, as do all vertices, edges, and faces around them.
+/// The new vertices and the ring of quads around the inset faces are new elements.
+pub trait MeshInset<T: MeshTypeHalfEdge<Mesh = Self> + MeshType3D>: MeshBasics<T>
+where
+    T::EP: DefaultEdgePayload,
+    T::FP: DefaultFacePayload,
+{
+    /// Shrinks the given faces by moving their boundaries `thickness` inwards
+    /// and fills the gap with a ring of quads. See `InsetMode` for how neighboring faces are handled.
+    ///
+    /// Fails without modifying the mesh if `thickness` is negative or not finite,
+    /// or if it is so large that an edge of an inset face would collapse.
+    /// Returns the ids of the inset faces in the order of `faces`.
+    fn try_inset_faces(
+        &mut self,
+        faces: impl IntoIterator<Item = T::F>,
+        thickness: T::S,
+        mode: InsetMode,
+    ) -> Result<Vec<T::F>, ModellingError> {
+        let faces = selected_faces::<T>(self, faces)?;
+        let region = faces
+            .iter()
+            .enumerate()
+            .map(|(i, &f)| match mode {
+                InsetMode::Individual => (f, i),
+                InsetMode::Region => (f, 0),
+            })
+            .collect();
+        offset_faces::<T>(self, &faces, &region, thickness, T::S::ZERO)
+    }
+
+    /// Same as `try_inset_faces` but panics if the result is not a manifold.
+    fn inset_faces(
+        &mut self,
+        faces: impl IntoIterator<Item = T::F>,
+        thickness: T::S,
+        mode: InsetMode,
+    ) -> Vec<T::F> {
+        self.try_inset_faces(faces, thickness, mode)
+            .unwrap_or_else(|e| panic!("Failed to inset the faces: {}", e))
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "nalgebra")]
+mod tests {
+    use crate::test_util::{assert_closed, face_area, square_with_hole};
+    use crate::{extensions::nalgebra::*, prelude::*};
+
+    #[test]
+    fn test_inset_individual() {
+        let mut mesh = Mesh3d64::cube(1.0);
+        let faces: Vec<_> = mesh.face_ids().take(2).collect();
+        let inset = mesh.inset_faces(faces, 0.1, InsetMode::Individual);
+        assert_closed(&mesh);
+        assert_eq!(inset.len(), 2);
+        assert_eq!(mesh.num_faces(), 6 + 2 * 4);
+        assert_eq!(mesh.num_vertices(), 8 + 2 * 4);
+        for f in inset {
+            assert!((face_area(&mesh, f) - 0.8 * 0.8).abs() < 1e-10);
+            assert!(mesh
+                .face(f)
+                .vertices(&mesh)
+                .all(|v| v.pos().amax() <= 0.5 + 1e-10));
+        }
+    }
+
+    #[test]
+    fn test_inset_region() {
+        // two adjacent faces of a cube are inset together
+        let mut mesh = Mesh3d64::cube(1.0);
+        let e = mesh.edge_ids().next().unwrap();
+        let faces = [mesh.edge(e).face_id(), mesh.edge(e).twin(&mesh).face_id()];
+        let inset = mesh.inset_faces(faces, 0.1, InsetMode::Region);
+        assert_closed(&mesh);
+        assert_eq!(mesh.num_faces(), 6 + 6);
+        assert_eq!(mesh.num_vertices(), 8 + 6);
+        let area: f64 = inset.iter().map(|&f| face_area(&mesh, f)).sum();
+        assert!((area - 2.0 * 0.8 * 0.9).abs() < 1e-10);
+
+        // all faces of a closed mesh don't have a boundary
+        let mut mesh = Mesh3d64::cube(1.0);
+        let faces: Vec<_> = mesh.face_ids().collect();
+        mesh.inset_faces(faces, 0.1, InsetMode::Region);
+        assert_closed(&mesh);
+        assert_eq!(mesh.num_faces(), 6);
+
+        assert_eq!(
+            mesh.try_inset_faces([100], 0.1, InsetMode::Region),
+            Err(ModellingError::MissingFace(100))
+        );
+    }
+
+    #[test]
+    fn test_offset_keeps_payloads() {
+        for extrude in [false, true] {
+            let mut mesh = Mesh3d64Corner::cube(1.0);
+            let f = mesh.face_ids().next().unwrap();
+            let vs: Vec<_> = mesh.face(f).vertex_ids(&mesh).collect();
+            let boundary = mesh.face(f).edge_id();
+            let far = mesh
+                .edge_ids()
+                .find(|&h| {
+                    let e = mesh.edge(h);
+                    !vs.contains(&e.origin_id()) && !vs.contains(&e.target_id(&mesh))
+                })
+                .unwrap();
+            let uv = Vec2::new(0.25, 0.75);
+            for h in [boundary, far] {
+                mesh.edge_mut(h).payload_mut().set_corner_uv(Some(uv));
+            }
+            mesh.face_attributes_mut().add::<u32>("material");
+            mesh.face_attributes_mut().set("material", f, 7u32);
+            let face_ids: Vec<_> = mesh.face_ids().collect();
+
+            let moved = if extrude {
+                mesh.extrude_region([f], 0.5)
+            } else {
+                mesh.inset_faces([f], 0.1, InsetMode::Individual)
+            };
+            assert!(mesh.check().is_ok(), "{:?}", mesh.check());
+            assert_eq!(moved, vec![f]);
+            assert!(face_ids.iter().all(|&g| mesh.try_face(g).is_some()));
+            assert_eq!(mesh.face_attributes().get::<u32>("material", f), Some(&7));
+
+            // the boundary of the moved face is now the base of a side quad
+            assert_ne!(mesh.edge(boundary).face_id(), f);
+            assert_eq!(mesh.edge(boundary).payload().corner_uv(), Some(uv));
+            assert_eq!(mesh.edge(far).payload().corner_uv(), Some(uv));
+            assert_eq!(
+                mesh.edges()
+                    .filter(|h| h.payload().corner_uv().is_some())
+                    .count(),
+                2
+            );
+        }
+    }
+
+    #[test]
+    fn test_inset_keeps_holes() {
+        let (mut mesh, f, _) = square_with_hole();
+        mesh.inset_faces([f], 0.1, InsetMode::Individual);
+        assert!(mesh.check().is_ok(), "{:?}", mesh.check());
+        assert_eq!(mesh.num_faces(), 1 + 4 + 4);
+        assert_eq!(mesh.num_vertices(), 16);
+        let face = mesh.face(f);
+        assert_eq!(face.num_holes(), 1);
+        assert!((face_area(&mesh, f) - 3.8 * 3.8).abs() < 1e-10);
+        assert!(face
+            .hole_vertices(&mesh, 0)
+            .all(|v| (v.pos().amax() - 1.1).abs() < 1e-10));
+    }
+
+    #[test]
+    fn test_offset_errors() {
+        let mut mesh = Mesh3d64::cube(1.0);
+        let f = mesh.face_ids().next().unwrap();
+        let before = mesh.clone();
+        for thickness in [f64::NAN, f64::INFINITY] {
+            assert!(matches!(
+                mesh.try_inset_faces([f], thickness, InsetMode::Individual),
+                Err(ModellingError::NonFinite(_))
+            ));
+        }
+        assert!(matches!(
+            mesh.try_extrude_region([f], f64::NAN),
+            Err(ModellingError::NonFinite(_))
+        ));
+        // the face is 1 wide, so an inset of 0.5 collapses it
+        for thickness in [-0.1, 0.5, 0.7] {
+            assert!(matches!(
+                mesh.try_inset_faces([f], thickness, InsetMode::Individual),
+                Err(ModellingError::DegenerateFace(_))
+            ));
+        }
+        assert!(matches!(
+            mesh.try_extrude_region([f], -0.1),
+            Err(ModellingError::DegenerateFace(_))
+        ));
+        assert!(mesh
+            .is_isomorphic_by_pos::<_, 3, _, MeshType3d64PNU>(&before, 1e-12)
+            .eq());
+        assert_eq!(mesh.num_faces(), 6);
+    }
+
+    #[test]
+    fn test_extrude_region() {
+        // extruding the top and a side face keeps the shared edge connected
+        let mut mesh = Mesh3d64::cube(1.0);
+        let e = mesh.edge_ids().next().unwrap();
+        let faces = [mesh.edge(e).face_id(), mesh.edge(e).twin(&mesh).face_id()];
+        let normals: Vec<_> = faces
+            .iter()
+            .map(|&f| Face3d::normal(mesh.face(f), &mesh).normalize())
+            .collect();
+        let extruded = mesh.extrude_region(faces, 0.5);
+        assert_closed(&mesh);
+        assert_eq!(mesh.num_faces(), 6 + 6);
+        assert_eq!(mesh.num_vertices(), 8 + 6);
+        for (f, n) in extruded.iter().zip(normals) {
+            let face = mesh.face(*f);
+            assert!(Face3d::normal(face, &mesh).normalize().is_about(&n, 1e-10));
+        }
+
+        let shared = mesh
+            .face(extruded[0])
+            .vertex_ids(&mesh)
+            .filter(|v| mesh.face(extruded[1]).vertex_ids(&mesh).any(|w| w == *v))
+            .count();
+        assert_eq!(shared, 2);
+
+        // extruding all faces inflates the mesh
+        let mut mesh = Mesh3d64::cube(1.0);
+        let faces: Vec<_> = mesh.face_ids().collect();
+        mesh.extrude_region(faces, 0.1);
+        assert_closed(&mesh);
+        assert_eq!(mesh.num_vertices(), 8);
+    }
+}
@@ -1,9 +1,19 @@
 //! This module contains the builder functions for the mesh representation.
 
+mod bevel;
+mod boolean;
 mod extrude;
+mod inset;
 mod loft;
+mod revolve;
 mod subdivision;
+mod sweep;
 
+pub use bevel::*;
+pub use boolean::*;
 pub use extrude::*;
+pub use inset::*;
 pub use loft::*;
+pub use revolve::*;
 pub use subdivision::*;
+pub use sweep::*;
@@ -0,0 +1,532 @@
+use crate::{
+    math::{HasPosition, Scalar, Vector, Vector3D},
+    mesh::{
+        dedup_loop, CurvedEdge, DefaultEdgePayload, DefaultFacePayload, EdgeBasics,
+        EuclideanMeshType, HalfEdge, MeshBasics, MeshType3D, MeshTypeHalfEdge, ModellingError,
+        VertexBasics,
+    },
+};
+use std::collections::HashSet;
+
+/// Collects the boundary loops of a 2d mesh after flattening its curved edges.
+///
+/// The loops are oriented such that outer boundaries are counter-clockwise and holes are clockwise.
+pub fn boundary_profiles<T: MeshTypeHalfEdge + EuclideanMeshType<2>>(
+    mesh: &T::Mesh,
+    tol: T::S,
+) -> Vec<Vec<T::Vec>>
+where
+    T::Edge: CurvedEdge<2, T>,
+    T::EP: DefaultEdgePayload,
+{
+    let mut mesh = mesh.clone();
+    mesh.flatten_curved_edges(tol);
+
+    let mut visited = HashSet::new();
+    let mut res = Vec::new();
+    for edge in mesh.edges() {
+        if !edge.is_boundary_self() || visited.contains(&edge.id()) {
+            continue;
+        }
+        let mut profile = Vec::new();
+        let mut e = edge.id();
+        while visited.insert(e) {
+            profile.push(mesh.edge(e).origin(&mesh).pos());
+            e = mesh.edge(e).next_id();
+        }
+        res.push(profile);
+    }
+
+    // the boundary halfedges run opposite to the faces, so flip all loops if the faces are clockwise
+    let area = res
+        .iter()
+        .flat_map(|profile| profile.iter().zip(profile.iter().cycle().skip(1)))
+        .map(|(a, b)| a.x() * b.y() - a.y() * b.x())
+        .fold(T::S::ZERO, |acc, x| acc + x);
+    if area < T::S::ZERO {
+        res.iter_mut().for_each(|profile| profile.reverse());
+    }
+    res
+}
+
+/// A trait for revolving (lathing) profiles around an axis.
+pub trait MeshRevolve<T: MeshTypeHalfEdge<Mesh = Self> + MeshType3D<Mesh = Self>>:
+    MeshBasics<T>
+where
+    T::EP: DefaultEdgePayload,
+    T::FP: DefaultFacePayload,
+{
+    /// Revolves the 2d `profile` by `angle` radians around the `axis` through the origin
+    /// using `segments` steps. The x-coordinate of the profile is the distance from the axis
+    /// and the y-coordinate is the position along the axis.
+    ///
+    /// The axis must not be zero and all profile points must have a non-negative x-coordinate.
+    /// Profile points on the axis are welded to a single vertex, so a profile that starts
+    /// or ends on the axis is closed with a pole. Segments on the axis don't generate faces.
+    /// If `closed` is true, the last profile point is connected to the first one and
+    /// a partial revolution is capped with two copies of the profile.
+    ///
+    /// The angle must not exceed a full revolution. A negative angle revolves clockwise
+    /// around the axis and produces the same orientation of the faces as a positive angle.
+    /// Closed profiles should be counter-clockwise and open profiles should go in the direction
+    /// of the axis on the positive side to get outward facing normals.
+    ///
+    /// Returns the ids of the inserted faces.
+    fn try_insert_revolve(
+        &mut self,
+        profile: impl IntoIterator<Item = T::Vec2>,
+        closed: bool,
+        axis: T::Vec,
+        angle: T::S,
+        segments: usize,
+    ) -> Result<Vec<T::F>, ModellingError> {
+        let mut profile: Vec<T::Vec2> = profile.into_iter().collect();
+        if let Some(i) = profile
+            .iter()
+            .position(|p| !p.x().is_finite() || !p.y().is_finite())
+        {
+            return Err(ModellingError::NonFinite(format!(
+                "Point {} of the profile is NaN or infinite",
+                i
+            )));
+        }
+        if !angle.is_finite() {
+            return Err(ModellingError::NonFinite(format!(
+                "The angle {} is NaN or infinite",
+                angle
+            )));
+        }
+        if ![axis.x(), axis.y(), axis.z()].iter().all(|x| x.is_finite()) {
+            return Err(ModellingError::NonFinite(
+                "The axis is NaN or infinite".to_string(),
+            ));
+        }
+        // a tiny axis might underflow when normalizing it
+        let a = axis.normalize();
+        if ![a.x(), a.y(), a.z()].iter().all(|x| x.is_finite()) || a.length() < T::S::HALF {
+            return Err(ModellingError::DegenerateFace(
+                "The axis must not be zero".to_string(),
+            ));
+        }
+
+        // points closer than the rounding errors of the profile's size are the same,
+        // and points closer to the axis than that are on the axis
+        let extent = profile
+            .iter()
+            .fold(T::S::ZERO, |acc, p| acc.max(p.x().abs()).max(p.y().abs()));
+        let tol = T::S::EPS * T::S::from_usize(16) * extent;
+        profile.dedup_by(|a, b| a.is_about(b, tol));
+        if closed && profile.len() > 1 && profile[0].is_about(profile.last().unwrap(), tol) {
+            profile.pop();
+        }
+        if profile.len() < 2 || (closed && profile.len() < 3) {
+            return Err(ModellingError::DegenerateFace(
+                "The profile must have at least two distinct points".to_string(),
+            ));
+        }
+        if segments < 2 || angle.abs() <= T::S::EPS {
+            return Err(ModellingError::DegenerateFace(
+                "The revolution needs at least two segments and a non-zero angle".to_string(),
+            ));
+        }
+        let turn = T::S::PI * T::S::TWO;
+        let full = angle.abs() >= turn - T::S::EPS * T::S::from_usize(16);
+        if angle.abs() > turn + T::S::EPS * T::S::from_usize(16) {
+            return Err(ModellingError::InvalidTopology(format!(
+                "The angle {} exceeds a full revolution",
+                angle
+            )));
+        }
+
+        if let Some(i) = profile.iter().position(|p| p.x() < -tol) {
+            return Err(ModellingError::InvalidTopology(format!(
+                "Profile point {} is on the negative side of the axis",
+                i
+            )));
+        }
+
+        let n = profile.len();
+        let on_axis: Vec<bool> = profile.iter().map(|p| p.x() <= tol).collect();
+        for i in 0..n {
+            if !closed && (i == 0 || i == n - 1) {
+                continue;
+            }
+            if on_axis[i] && !on_axis[(i + n - 1) % n] && !on_axis[(i + 1) % n] {
+                return Err(ModellingError::NonManifold(format!(
+                    "Profile point {} touches the axis between two points off the axis",
+                    i
+                )));
+            }
+        }
+
+        let steps = if full { segments } else { segments + 1 };
+
+        // an orthonormal frame around the axis
+        let helper = if a.x().abs() < T::S::HALF {
+            T::Vec::from_xyz(T::S::ONE, T::S::ZERO, T::S::ZERO)
+        } else {
+            T::Vec::from_xyz(T::S::ZERO, T::S::ONE, T::S::ZERO)
+        };
+        let u = (helper - a * a.dot(&helper)).normalize();
+        let w = a.cross(&u);
+
+        // vertices on the axis are inserted once, all others once per step
+        let mut vps = Vec::new();
+        let mut first = Vec::with_capacity(n);
+        for (i, p) in profile.iter().enumerate() {
+            first.push(vps.len());
+            if on_axis[i] {
+                vps.push(T::VP::from_pos(a * p.y()));
+                continue;
+            }
+            for j in 0..steps {
+                let phi = angle * T::S::from_usize(j) / T::S::from_usize(segments);
+                let pos = a * p.y() + (u * phi.cos() - w * phi.sin()) * p.x();
+                vps.push(T::VP::from_pos(pos));
+            }
+        }
+        let index = |i: usize, j: usize| {
+            if on_axis[i] {
+                first[i]
+            } else {
+                first[i] + j % steps
+            }
+        };
+
+        let mut faces = Vec::new();
+        let num_edges = if closed { n } else { n - 1 };
+        for i in 0..num_edges {
+            let k = (i + 1) % n;
+            if on_axis[i] && on_axis[k] {
+                continue;
+            }
+            for j in 0..segments {
+                let face = dedup_loop(vec![
+                    index(i, j),
+                    index(k, j),
+                    index(k, j + 1),
+                    index(i, j + 1),
+                ]);
+                faces.push((face, T::FP::default()));
+            }
+        }
+        if closed && !full {
+            let cap = |j: usize| dedup_loop((0..n).map(|i| index(i, j)).collect());
+            let mut start = cap(0);
+            start.reverse();
+            faces.push((start, T::FP::default()));
+            faces.push((cap(segments), T::FP::default()));
+        }
+        if angle < T::S::ZERO {
+            // revolving clockwise mirrors the faces
+            faces.iter_mut().for_each(|(face, _)| face.reverse());
+        }
+
+        let (_, fs) = self.insert_indexed_faces(vps, faces)?;
+        Ok(fs)
+    }
+
+    /// Same as `try_insert_revolve` but panics if the profile cannot be revolved.
+    fn insert_revolve(
+        &mut self,
+        profile: impl IntoIterator<Item = T::Vec2>,
+        closed: bool,
+        axis: T::Vec,
+        angle: T::S,
+        segments: usize,
+    ) -> Vec<T::F> {
+        self.try_insert_revolve(profile, closed, axis, angle, segments)
+            .unwrap_or_else(|e| panic!("Failed to revolve the profile: {}", e))
+    }
+
+    /// calls `insert_revolve` on a new mesh
+    fn revolve(
+        profile: impl IntoIterator<Item = T::Vec2>,
+        closed: bool,
+        axis: T::Vec,
+        angle: T::S,
+        segments: usize,
+    ) -> Self {
+        let mut mesh = Self::default();
+        mesh.insert_revolve(profile, closed, axis, angle, segments);
+        mesh
+    }
+
+    /// Revolves all boundaries of the 2d `mesh` like closed profiles in `try_insert_revolve`.
+    /// Curved edges are flattened with the tolerance `tol`.
+    /// If any boundary cannot be revolved, the mesh is not modified.
+    fn try_insert_revolve_boundary<T2: MeshTypeHalfEdge + EuclideanMeshType<2>>(
+        &mut self,
+        mesh: &T2::Mesh,
+        tol: T2::S,
+        axis: T::Vec,
+        angle: T::S,
+        segments: usize,
+    ) -> Result<Vec<T::F>, ModellingError>
+    where
+        T2::Edge: CurvedEdge<2, T2>,
+        T2::EP: DefaultEdgePayload,
+    {
+        let backup = self.clone();
+        let mut res = Vec::new();
+        for profile in boundary_profiles::<T2>(mesh, tol) {
+            let fs = self.try_insert_revolve(
+                profile.iter().map(|p| {
+                    T::Vec2::from_xy(
+                        T::S::from_f64(p.x().to_f64()),
+                        T::S::from_f64(p.y().to_f64()),
+                    )
+                }),
+                true,
+                axis,
+                angle,
+                segments,
+            );
+            match fs {
+                Ok(fs) => res.extend(fs),
+                Err(e) => {
+                    *self = backup;
+                    return Err(e);
+                }
+            }
+        }
+        Ok(res)
+    }
+
+    /// calls `try_insert_revolve_boundary` on a new mesh and panics if it fails
+    fn revolve_boundary<T2: MeshTypeHalfEdge + EuclideanMeshType<2>>(
+        mesh: &T2::Mesh,
+        tol: T2::S,
+        axis: T::Vec,
+        angle: T::S,
+        segments: usize,
+    ) -> Self
+    where
+        T2::Edge: CurvedEdge<2, T2>,
+        T2::EP: DefaultEdgePayload,
+    {
+        let mut res = Self::default();
+        res.try_insert_revolve_boundary::<T2>(mesh, tol, axis, angle, segments)
+            .unwrap_or_else(|e| panic!("Failed to revolve the boundary: {}", e));
+        res
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "nalgebra")]
+mod tests {
+    use crate::test_util::{assert_closed, volume};
+    use crate::{extensions::nalgebra::*, prelude::*};
+
+    const TAU: f64 = std::f64::consts::TAU;
+
+    fn y() -> Vec3<f64> {
+        Vec3::new(0.0, 1.0, 0.0)
+    }
+
+    #[test]
+    fn test_revolve_ring() {
+        // a square revolved around the axis: a ring with a square cross section
+        let profile = [
+            Vec2::new(1.0, 0.0),
+            Vec2::new(2.0, 0.0),
+            Vec2::new(2.0, 1.0),
+            Vec2::new(1.0, 1.0),
+        ];
+        let n = 16;
+        let mesh = Mesh3d64::revolve(profile, true, y(), TAU, n);
+        assert_closed(&mesh);
+        assert_eq!(mesh.num_vertices(), 4 * n);
+        assert_eq!(mesh.num_faces(), 4 * n);
+        let expected = regular_polygon_area(2.0, n) - regular_polygon_area(1.0, n);
+        assert!((volume(&mesh) - expected).abs() < 1e-10);
+
+        // a quarter of the ring is capped
+        let mesh = Mesh3d64::revolve(profile, true, y(), TAU / 4.0, 4);
+        assert_closed(&mesh);
+        assert_eq!(mesh.num_vertices(), 4 * 5);
+        assert_eq!(mesh.num_faces(), 4 * 4 + 2);
+        assert!(volume(&mesh) > 0.0);
+
+        // revolving the other way keeps the faces facing outwards
+        let mirrored = Mesh3d64::revolve(profile, true, y(), -TAU / 4.0, 4);
+        assert_closed(&mirrored);
+        assert!((volume(&mirrored) - volume(&mesh)).abs() < 1e-10);
+        let mesh = Mesh3d64::revolve(profile, true, y(), -TAU, n);
+        assert_closed(&mesh);
+        assert!((volume(&mesh) - expected).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_revolve_poles() {
+        // a half circle from the bottom to the top pole is welded to a sphere
+        let k = 8;
+        let profile = (0..=k).map(|i| {
+            let a = std::f64::consts::PI * (i as f64 / k as f64 - 0.5);
+            Vec2::new(a.cos(), a.sin())
+        });
+        let mesh = Mesh3d64::revolve(profile, false, y(), TAU, 12);
+        assert_closed(&mesh);
+        assert_eq!(mesh.num_vertices(), 2 + (k - 1) * 12);
+        assert_eq!(mesh.num_faces(), k * 12);
+        assert!(volume(&mesh) > 0.0);
+        assert!(mesh
+            .vertices()
+            .all(|v| (v.pos().norm() - 1.0).abs() < 1e-10));
+
+        // a closed profile with a segment on the axis: a cylinder
+        let profile = [
+            Vec2::new(0.0, 0.0),
+            Vec2::new(1.0, 0.0),
+            Vec2::new(1.0, 1.0),
+            Vec2::new(0.0, 1.0),
+        ];
+        let mesh = Mesh3d64::revolve(profile, true, y(), TAU, 8);
+        assert_closed(&mesh);
+        assert_eq!(mesh.num_vertices(), 2 + 2 * 8);
+        assert!((volume(&mesh) - regular_polygon_area(1.0, 8)).abs() < 1e-10);
+
+        // the tolerance for points on the axis scales with the profile
+        let s = 1e6;
+        let profile = profile.map(|p| p * s + Vec2::new(if p.x == 0.0 { -1e-9 } else { 0.0 }, 0.0));
+        let mesh = Mesh3d64::revolve(profile, true, y(), TAU, 8);
+        assert_closed(&mesh);
+        assert_eq!(mesh.num_vertices(), 2 + 2 * 8);
+
+        // an open vase along another axis keeps its rim
+        let profile = [
+            Vec2::new(0.0, 0.0),
+            Vec2::new(1.0, 0.0),
+            Vec2::new(0.5, 1.0),
+            Vec2::new(0.8, 2.0),
+        ];
+        let mesh = Mesh3d64::revolve(profile, false, Vec3::new(0.0, 0.0, 1.0), TAU, 8);
+        assert!(mesh.check().is_ok());
+        assert_eq!(mesh.edges().filter(|e| e.is_boundary_self()).count(), 8);
+        assert!(mesh
+            .vertices()
+            .all(|v| v.pos().z >= -1e-10 && v.pos().z <= 2.0 + 1e-10));
+    }
+
+    #[test]
+    fn test_revolve_errors() {
+        let mut mesh = Mesh3d64::new();
+        // touches the axis in the middle
+        let profile = [
+            Vec2::new(1.0, 0.0),
+            Vec2::new(0.0, 1.0),
+            Vec2::new(1.0, 2.0),
+        ];
+        assert!(matches!(
+            mesh.try_insert_revolve(profile, false, y(), TAU, 8),
+            Err(ModellingError::NonManifold(_))
+        ));
+        assert!(matches!(
+            mesh.try_insert_revolve([Vec2::new(1.0, 0.0)], false, y(), TAU, 8),
+            Err(ModellingError::DegenerateFace(_))
+        ));
+        // crosses the axis
+        let profile = [Vec2::new(1.0, 0.0), Vec2::new(-1.0, 1.0)];
+        assert_eq!(
+            mesh.try_insert_revolve(profile, false, y(), TAU, 8),
+            Err(ModellingError::InvalidTopology(
+                "Profile point 1 is on the negative side of the axis".to_string()
+            ))
+        );
+        // more than a full revolution overlaps itself
+        let profile = [Vec2::new(1.0, 0.0), Vec2::new(1.0, 1.0)];
+        for angle in [TAU * 1.5, -TAU * 1.5] {
+            assert!(matches!(
+                mesh.try_insert_revolve(profile, false, y(), angle, 8),
+                Err(ModellingError::InvalidTopology(_))
+            ));
+        }
+        // non-finite angles and profile points
+        for angle in [f64::NAN, f64::INFINITY] {
+            assert!(matches!(
+                mesh.try_insert_revolve(profile, false, y(), angle, 8),
+                Err(ModellingError::NonFinite(_))
+            ));
+        }
+        let profile = [Vec2::new(1.0, 0.0), Vec2::new(f64::NAN, 1.0)];
+        assert!(matches!(
+            mesh.try_insert_revolve(profile, false, y(), TAU, 8),
+            Err(ModellingError::NonFinite(_))
+        ));
+        // the axis needs a direction
+        let profile = [Vec2::new(1.0, 0.0), Vec2::new(1.0, 1.0)];
+        for axis in [
+            Vec3::new(0.0, f64::NAN, 0.0),
+            Vec3::new(f64::INFINITY, 1.0, 0.0),
+        ] {
+            assert!(matches!(
+                mesh.try_insert_revolve(profile, false, axis, TAU, 8),
+                Err(ModellingError::NonFinite(_))
+            ));
+        }
+        for axis in [Vec3::new(0.0, 0.0, 0.0), Vec3::new(0.0, 1e-200, 0.0)] {
+            assert!(matches!(
+                mesh.try_insert_revolve(profile, false, axis, TAU, 8),
+                Err(ModellingError::DegenerateFace(_))
+            ));
+        }
+        assert_eq!(mesh.num_vertices(), 0);
+
+        // duplicates are detected relative to the size of the profile
+        let square = |offset: f64| {
+            [
+                (1.0, 0.0),
+                (2.0, 0.0),
+                (2.0, 1.0),
+                (1.0, 1.0),
+                (1.0, offset),
+            ]
+            .map(|(x, y)| Vec2::new(x * 1e6, y * 1e6))
+        };
+        let mut mesh = Mesh3d64::new();
+        mesh.try_insert_revolve(square(1e-16), true, y(), TAU, 8)
+            .unwrap();
+        assert!(mesh.check().is_ok());
+        assert_eq!(mesh.num_vertices(), 4 * 8);
+    }
+
+    #[test]
+    fn test_revolve_boundary() {
+        // a square with a curved outer edge
+        let mut mesh2d = Mesh2d64Curved::new();
+        let vp = |x: f64, y: f64| VertexPayloadPNU::<f64, 2>::from_pos(Vec2::new(x, y));
+        let e = mesh2d.insert_polygon([vp(1.0, 0.0), vp(2.0, 0.0), vp(2.0, 1.0), vp(1.0, 1.0)]);
+        let curved = mesh2d
+            .edges()
+            .find(|e| {
+                !e.is_boundary_self()
+                    && e.origin(&mesh2d).pos().x == 2.0
+                    && e.target(&mesh2d).pos().x == 2.0
+            })
+            .unwrap()
+            .id();
+        mesh2d
+            .edge_mut(curved)
+            .set_curve_type(CurvedEdgeType::QuadraticBezier(Vec2::new(3.0, 0.5)));
+        assert!(mesh2d.edge(e).is_boundary_self());
+
+        let profiles = boundary_profiles::<MeshType2d64PNUCurved>(&mesh2d, 0.01);
+        assert_eq!(profiles.len(), 1);
+        assert!(profiles[0].len() > 4);
+
+        let mesh = Mesh3d64::revolve_boundary::<MeshType2d64PNUCurved>(&mesh2d, 0.01, y(), TAU, 16);
+        assert_closed(&mesh);
+        assert_eq!(mesh.num_vertices(), profiles[0].len() * 16);
+        assert!(volume(&mesh) > regular_polygon_area(2.0, 16) - regular_polygon_area(1.0, 16));
+
+        // a second square that crosses the axis fails without inserting the first one
+        mesh2d.insert_polygon([vp(-1.0, 3.0), vp(1.0, 3.0), vp(1.0, 4.0), vp(-1.0, 4.0)]);
+        let mut mesh = Mesh3d64::new();
+        assert!(matches!(
+            mesh.try_insert_revolve_boundary::<MeshType2d64PNUCurved>(&mesh2d, 0.01, y(), TAU, 16),
+            Err(ModellingError::InvalidTopology(_))
+        ));
+        assert_eq!(mesh.num_vertices(), 0);
+        assert_eq!(mesh.num_faces(), 0);
+    }
+}
@@ -1,7 +1,12 @@
-use crate::mesh::{
-    DefaultEdgePayload, EdgeBasics, FaceBasics, HalfEdge, HalfEdgeSemiBuilder, MeshTypeHalfEdge,
-    VertexInterpolator,
+use crate::{
+    math::{HasPosition, Scalar, Transformable, Vector},
+    mesh::{
+        DefaultEdgePayload, EdgeBasics, EuclideanMeshType, FaceBasics, HalfEdge,
+        HalfEdgeSemiBuilder, HasCrease, MeshType, MeshTypeHalfEdge, VertexBasics,
+        VertexInterpolator,
+    },
 };
+use std::collections::HashMap;
 
 /// Describes how to subdivide a mesh.
 #[derive(Debug, Clone, Copy)]
@@ -38,6 +43,22 @@ impl SubdivisionDescription {
     }
 }
 
+/// Returns the payloads of edge midpoints that were computed before the subdivision.
+struct PrecomputedInterpolator<T: MeshType> {
+    edge_points: HashMap<(T::V, T::V), T::VP>,
+}
+
+impl<T: MeshType> VertexInterpolator<3, T> for PrecomputedInterpolator<T> {
+    fn call(&self, _mesh: &T::Mesh, vertices: [(usize, T::V); 3]) -> T::VP {
+        let ends = vertices
+            .iter()
+            .filter(|(w, _)| *w == 1)
+            .map(|(_, v)| *v)
+            .collect::<Vec<_>>();
+        self.edge_points[&(ends[0], ends[1])].clone()
+    }
+}
+
 /// TODO
 
 /// A trait for subdividing meshes.
@@ -51,6 +72,19 @@ where
     ///
     /// based on an algorithm developed by Charles Loop in 1987
     fn loop_subdivision(&mut self, vp_builder: &impl VertexInterpolator<3, T>) -> &mut Self
+    where
+        T::Mesh: HalfEdgeSemiBuilder<T>,
+    {
+        self.loop_subdivision_with(vp_builder, |ep| (ep.clone(), Default::default()))
+    }
+
+    /// Like `loop_subdivision`, but `split` returns the payloads of the first and the second half
+    /// of each subdivided edge given its old payload.
+    fn loop_subdivision_with(
+        &mut self,
+        vp_builder: &impl VertexInterpolator<3, T>,
+        split: impl Fn(&T::EP) -> (T::EP, T::EP),
+    ) -> &mut Self
     where
         T::Mesh: HalfEdgeSemiBuilder<T>,
     {
@@ -64,8 +98,10 @@ where
 
             // insert an additional vertex for each edge
             for i in 0..3 {
+                let (first, ep) = split(edges[i].payload());
+                *self.edge_mut(edges[i].id()).payload_mut() = first;
                 if self
-                    .subdivide_unsafe_try_fixup(edges[i].id(), Default::default())
+                    .subdivide_unsafe_try_fixup(edges[i].id(), ep.clone())
                     .is_some()
                 {
                     // edge is already subdivided
@@ -100,7 +136,7 @@ where
                         ),
                     ],
                 );
-                self.subdivide_unsafe(edges[i].id(), vp, Default::default());
+                self.subdivide_unsafe(edges[i].id(), vp, ep);
             }
 
             // remove the original face
@@ -125,6 +161,102 @@ where
         self
     }
 
+    /// Subdivides the mesh with frequency (2,0) and moves the vertices with Loop's smoothing rules.
+    ///
+    /// Boundary edges and creases (see `HasCrease`) stay sharp: Their new vertices stay on the edge,
+    /// vertices with two sharp edges only move along them, and vertices with more don't move at all.
+    /// Semi-sharp creases blend between the smooth and the sharp rules.
+    /// Both halves of a subdivided edge get the crease weight reduced by one.
+    fn loop_subdivision_smooth(&mut self) -> &mut Self
+    where
+        T: EuclideanMeshType<3>,
+        T::Mesh: HalfEdgeSemiBuilder<T>,
+        T::EP: HasCrease,
+    {
+        let s = |x: f64| T::S::from_f64(x);
+        let sharpness = |e: &T::Edge| -> f64 {
+            if e.is_boundary(self) {
+                f64::INFINITY
+            } else {
+                e.payload().crease().max(e.twin(self).payload().crease()) as f64
+            }
+        };
+
+        // the new vertices on the edges
+        let mut edge_points = HashMap::new();
+        for e in self.edges() {
+            let (a, b) = (e.origin(self), e.target(self));
+            if edge_points.contains_key(&(a.id(), b.id())) {
+                continue;
+            }
+            let sharp = (a.pos() + b.pos()) * s(0.5);
+            let w = sharpness(e).min(1.0);
+            let pos = if w >= 1.0 {
+                sharp
+            } else {
+                let c = e.next(self).target(self).pos();
+                let d = e.twin(self).next(self).target(self).pos();
+                let smooth = (a.pos() + b.pos()) * s(3.0 / 8.0) + (c + d) * s(1.0 / 8.0);
+                smooth * s(1.0 - w) + sharp * s(w)
+            };
+            let mut vp = a.payload().clone();
+            vp.lerp(b.payload(), s(0.5));
+            vp.set_pos(pos);
+            edge_points.insert((a.id(), b.id()), vp.clone());
+            edge_points.insert((b.id(), a.id()), vp);
+        }
+
+        // the new positions of the old vertices
+        let vertex_points = self
+            .vertices()
+            .filter(|v| v.edge(self).is_some())
+            .map(|v| {
+                let p = v.pos();
+                let neighbors = v
+                    .edges_out(self)
+                    .map(|e| (e.target(self).pos(), sharpness(&e)))
+                    .collect::<Vec<_>>();
+                let n = neighbors.len() as f64;
+                let beta = if neighbors.len() == 3 {
+                    3.0 / 16.0
+                } else {
+                    3.0 / (8.0 * n)
+                };
+                let sum = neighbors
+                    .iter()
+                    .fold(T::Vec::zero(), |acc, (q, _)| acc + *q);
+                let smooth = p * s(1.0 - n * beta) + sum * s(beta);
+
+                let creases = neighbors
+                    .iter()
+                    .filter(|(_, w)| *w > 0.0)
+                    .collect::<Vec<_>>();
+                if creases.len() < 2 {
+                    return (v.id(), smooth);
+                }
+                let sharp = if creases.len() == 2 {
+                    p * s(3.0 / 4.0) + (creases[0].0 + creases[1].0) * s(1.0 / 8.0)
+                } else {
+                    p
+                };
+                let w = (creases.iter().map(|(_, w)| w.min(1.0)).sum::<f64>()
+                    / creases.len() as f64)
+                    .min(1.0);
+                (v.id(), smooth * s(1.0 - w) + sharp * s(w))
+            })
+            .collect::<Vec<_>>();
+
+        self.loop_subdivision_with(&PrecomputedInterpolator::<T> { edge_points }, |ep| {
+            let ep = ep.subdivided();
+            (ep.clone(), ep)
+        });
+        for (v, pos) in vertex_points {
+            self.vertex_mut(v).payload_mut().set_pos(pos);
+        }
+
+        self
+    }
+
     /// Subdivides the mesh with frequency (n,m).
     /// Uses the `vp_builder` to create the new vertex payloads.
     /// Returns a new mesh.
@@ -160,6 +292,66 @@ where
     }
 }
 
+#[cfg(test)]
+#[cfg(feature = "nalgebra")]
+mod tests {
+    use crate::{extensions::nalgebra::*, prelude::*};
+
+    fn tetrahedron(crease: f32) -> Mesh3d64Crease {
+        let mut mesh = Mesh3d64Crease::regular_tetrahedron(1.0);
+        let ids: Vec<_> = mesh.edge_ids().collect();
+        for e in ids {
+            mesh.edge_mut(e).payload_mut().set_crease(crease);
+        }
+        mesh
+    }
+
+    fn positions(mesh: &Mesh3d64Crease) -> Vec<Vec3<f64>> {
+        mesh.vertices().map(|v| v.pos()).collect()
+    }
+
+    #[test]
+    fn test_loop_subdivision_creases() {
+        // sharp edges keep the shape
+        let mut mesh = tetrahedron(f32::INFINITY);
+        let old = positions(&mesh);
+        mesh.loop_subdivision_smooth();
+        assert!(mesh.check().is_ok());
+        assert_eq!(mesh.num_faces(), 16);
+        assert_eq!(positions(&mesh)[0..4], old[..]);
+        for e in mesh.edges() {
+            assert!(e.payload().crease().is_infinite() || e.payload().crease() == 0.0);
+        }
+
+        // smooth edges shrink the regular tetrahedron around its center
+        let mut mesh = tetrahedron(0.0);
+        mesh.loop_subdivision_smooth();
+        assert!(mesh.check().is_ok());
+        for (p, q) in positions(&mesh).iter().zip(old.iter()) {
+            assert!(p.is_about(&(*q * 0.25), 1e-10));
+        }
+        for v in mesh.vertices().skip(4) {
+            let corners = v.neighbor_ids(&mesh).filter(|&w| w < 4).collect::<Vec<_>>();
+            assert_eq!(corners.len(), 2);
+            let midpoint = (old[corners[0]] + old[corners[1]]) * 0.5;
+            assert!(v.pos().is_about(&(midpoint * 0.5), 1e-10));
+        }
+
+        // semi-sharp creases get softer with every subdivision step
+        let mut mesh = tetrahedron(2.0);
+        mesh.loop_subdivision_smooth();
+        let creases = |mesh: &Mesh3d64Crease, c: f32| {
+            mesh.edges().filter(|e| e.payload().crease() == c).count()
+        };
+        assert_eq!(creases(&mesh, 1.0), 24);
+        assert_eq!(creases(&mesh, 0.0), 24);
+        assert_eq!(positions(&mesh)[0..4], old[..]);
+        mesh.loop_subdivision_smooth();
+        assert_eq!(creases(&mesh, 0.0), mesh.num_edges());
+        assert!(mesh.check().is_ok());
+    }
+}
+
 /*
 #[cfg(test)]
 mod tests {
@@ -182,4 +374,4 @@ mod tests {
         assert!(mesh.check().is_ok());
     }
 }
-*/
\ No newline at end of file
+*/
@@ -0,0 +1,424 @@
+use crate::{
+    math::{HasPosition, Scalar, Vector, Vector3D},
+    mesh::{
+        DefaultEdgePayload, DefaultFacePayload, HalfEdge, MeshType3D, MeshTypeHalfEdge,
+        ModellingError,
+    },
+    operations::MeshLoft,
+};
+
+/// Samples a Bézier curve of arbitrary degree given by its control points at `n + 1`
+/// uniformly spaced parameters using de Casteljau's algorithm.
+/// Returns an empty path if there are no control points or `n` is zero.
+pub fn bezier_path<S: Scalar, const D: usize, V: Vector<S, D>>(
+    control_points: &[V],
+    n: usize,
+) -> Vec<V> {
+    if control_points.is_empty() || n == 0 {
+        return Vec::new();
+    }
+    (0..=n)
+        .map(|i| {
+            let t = S::from_usize(i) / S::from_usize(n);
+            let mut ps = control_points.to_vec();
+            for k in (1..ps.len()).rev() {
+                for j in 0..k {
+                    ps[j] = ps[j] * (S::ONE - t) + ps[j + 1] * t;
+                }
+            }
+            ps[0]
+        })
+        .collect()
+}
+
+/// The direction of the path at point `i`, i.e., the sum of the normalized adjacent segments.
+/// Its length is close to zero if consecutive points are equal or the path turns back on itself.
+fn tangent<S: Scalar, V: Vector3D<S = S>>(path: &[V], i: usize) -> V {
+    let n = path.len();
+    let before = (path[i] - path[i.saturating_sub(1)]).normalize();
+    let after = (path[(i + 1).min(n - 1)] - path[i]).normalize();
+    if i == 0 {
+        after
+    } else if i + 1 == n {
+        before
+    } else {
+        before + after
+    }
+}
+
+/// Points of the path closer than the rounding errors of its size are the same.
+fn path_tolerance<S: Scalar, V: Vector3D<S = S>>(path: &[V]) -> S {
+    let extent = path.iter().fold(S::ZERO, |acc, p| {
+        acc.max(p.x().abs()).max(p.y().abs()).max(p.z().abs())
+    });
+    S::EPS * S::from_usize(16) * extent
+}
+
+/// Computes rotation-minimizing frames `(normal, binormal, tangent)` along the polyline
+/// using the double reflection method of Wang et al.
+///
+/// The tangent at inner points is the bisector of the adjacent segments.
+/// Panics if a tangent is zero, i.e., if consecutive points are equal or the path turns back on itself.
+pub fn rotation_minimizing_frames<S: Scalar, V: Vector3D<S = S>>(path: &[V]) -> Vec<(V, V, V)> {
+    assert!(path.len() >= 2);
+    let n = path.len();
+    let tol = path_tolerance(path);
+    for i in 1..n {
+        assert!(
+            path[i - 1].distance(&path[i]) > tol,
+            "The points {} and {} of the path are equal",
+            i - 1,
+            i
+        );
+    }
+    // the tangents are sums of unit vectors, so they don't depend on the size of the path
+    let eps = S::EPS * S::from_usize(16);
+    let tangents: Vec<V> = (0..n)
+        .map(|i| {
+            let t = tangent(path, i);
+            assert!(
+                t.length() > eps,
+                "The tangent of the path at point {} is zero",
+                i
+            );
+            t.normalize()
+        })
+        .collect();
+
+    // start with the axis that is least aligned with the tangent
+    let t = tangents[0];
+    let axis = if t.x().abs() <= t.y().abs() && t.x().abs() <= t.z().abs() {
+        V::from_xyz(S::ONE, S::ZERO, S::ZERO)
+    } else if t.y().abs() <= t.z().abs() {
+        V::from_xyz(S::ZERO, S::ONE, S::ZERO)
+    } else {
+        V::from_xyz(S::ZERO, S::ZERO, S::ONE)
+    };
+    let mut r = (axis - t * t.dot(&axis)).normalize();
+
+    let reflect = |v: V, axis: V, c: S| v - axis * (S::TWO * axis.dot(&v) / c);
+    let mut frames = Vec::with_capacity(n);
+    for i in 0..n {
+        frames.push((r, tangents[i].cross(&r), tangents[i]));
+        if i + 1 == n {
+            break;
+        }
+        let v1 = path[i + 1] - path[i];
+        let c1 = v1.dot(&v1);
+        let (r_l, t_l) = if c1 > tol * tol {
+            (reflect(r, v1, c1), reflect(tangents[i], v1, c1))
+        } else {
+            (r, tangents[i])
+        };
+        let v2 = tangents[i + 1] - t_l;
+        let c2 = v2.dot(&v2);
+        r = if c2 > eps { reflect(r_l, v2, c2) } else { r_l };
+    }
+    frames
+}
+
+/// A trait for sweeping profiles along paths.
+pub trait MeshSweep<T: MeshTypeHalfEdge<Mesh = Self> + MeshType3D<Mesh = Self>>:
+    MeshLoft<T>
+where
+    T::EP: DefaultEdgePayload,
+    T::FP: DefaultFacePayload,
+{
+    /// Sweeps the 2d `profile` along the open polyline `path` and connects consecutive
+    /// copies of the profile with quads using `loft_polygon`.
+    ///
+    /// The profile is placed in the plane spanned by the normal (x-axis) and binormal (y-axis)
+    /// of the rotation-minimizing frame at each point of the path. Use `bezier_path` to sweep along curves.
+    /// At each point, the profile is rotated counter-clockwise by `twist(t)` radians and scaled by
+    /// the positive factor `scale(t)` where `t` is the relative arc length in `[0, 1]`.
+    /// If `caps` is true, both ends are closed with faces.
+    ///
+    /// Consecutive duplicates in the path are ignored. The path must not turn back on itself.
+    ///
+    /// The profile should be counter-clockwise to get outward facing normals.
+    /// Returns an edge on the boundary at the end of the sweep.
+    fn try_insert_sweep(
+        &mut self,
+        profile: impl IntoIterator<Item = T::Vec2>,
+        path: impl IntoIterator<Item = T::Vec>,
+        twist: impl Fn(T::S) -> T::S,
+        scale: impl Fn(T::S) -> T::S,
+        caps: bool,
+    ) -> Result<T::E, ModellingError> {
+        let profile: Vec<T::Vec2> = profile.into_iter().collect();
+        let mut path: Vec<T::Vec> = path.into_iter().collect();
+        if profile.len() < 3 {
+            return Err(ModellingError::DegenerateFace(
+                "The profile must have at least 3 vertices".to_string(),
+            ));
+        }
+
+        if let Some(i) = profile
+            .iter()
+            .position(|p| !p.x().is_finite() || !p.y().is_finite())
+        {
+            return Err(ModellingError::NonFinite(format!(
+                "Point {} of the profile is NaN or infinite",
+                i
+            )));
+        }
+
+        let coordinates = |p: &T::Vec| [p.x(), p.y(), p.z()];
+        if let Some(i) = path
+            .iter()
+            .position(|p| !coordinates(p).iter().all(|x| x.is_finite()))
+        {
+            return Err(ModellingError::NonFinite(format!(
+                "Point {} of the path is NaN or infinite",
+                i
+            )));
+        }
+
+        let tol = path_tolerance(&path);
+        path.dedup_by(|a, b| a.is_about(b, tol));
+        if path.len() < 2 {
+            return Err(ModellingError::DegenerateFace(
+                "The path must have at least two distinct points".to_string(),
+            ));
+        }
+        for i in 1..path.len() - 1 {
+            if tangent(&path, i).length() <= T::S::EPS * T::S::from_usize(16) {
+                return Err(ModellingError::DegenerateFace(format!(
+                    "The path turns back on itself at point {}",
+                    i
+                )));
+            }
+        }
+
+        let mut lengths = vec![T::S::ZERO];
+        for (a, b) in path.iter().zip(path.iter().skip(1)) {
+            lengths.push(*lengths.last().unwrap() + a.distance(b));
+        }
+        let total = *lengths.last().unwrap();
+
+        // evaluate the twist and scale first so invalid values leave the mesh untouched
+        let mut transforms = Vec::with_capacity(path.len());
+        for (i, length) in lengths.iter().enumerate() {
+            let t = if total > T::S::ZERO {
+                *length / total
+            } else {
+                T::S::ZERO
+            };
+            let (angle, k) = (twist(t), scale(t));
+            if !angle.is_finite() || !k.is_finite() {
+                return Err(ModellingError::NonFinite(format!(
+                    "The twist or scale at point {} of the path is NaN or infinite",
+                    i
+                )));
+            }
+            if k <= T::S::ZERO {
+                return Err(ModellingError::DegenerateFace(format!(
+                    "The scale {} at point {} of the path must be positive",
+                    k, i
+                )));
+            }
+            transforms.push((angle.sin(), angle.cos(), k));
+        }
+
+        let ring = |i: usize, (r, s, _): (T::Vec, T::Vec, T::Vec)| {
+            let (sin, cos, k) = transforms[i];
+            profile
+                .iter()
+                .map(|p| {
+                    let x = (p.x() * cos - p.y() * sin) * k;
+                    let y = (p.x() * sin + p.y() * cos) * k;
+                    T::VP::from_pos(path[i] + r * x + s * y)
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let frames = rotation_minimizing_frames(&path);
+        let first = self.insert_loop(ring(0, frames[0]));
+        if caps {
+            self.close_hole(first, Default::default(), false);
+        }
+        let mut e = self.edge(first).twin_id();
+        for (i, frame) in frames.iter().enumerate().skip(1) {
+            e = self.loft_polygon(e, 2, 2, ring(i, *frame));
+        }
+        if caps {
+            self.close_hole(e, Default::default(), false);
+        }
+        Ok(e)
+    }
+
+    /// Same as `try_insert_sweep` but panics if the profile cannot be swept along the path.
+    fn insert_sweep(
+        &mut self,
+        profile: impl IntoIterator<Item = T::Vec2>,
+        path: impl IntoIterator<Item = T::Vec>,
+        twist: impl Fn(T::S) -> T::S,
+        scale: impl Fn(T::S) -> T::S,
+        caps: bool,
+    ) -> T::E {
+        self.try_insert_sweep(profile, path, twist, scale, caps)
+            .unwrap_or_else(|e| panic!("Failed to sweep the profile: {}", e))
+    }
+
+    /// calls `insert_sweep` on a new mesh
+    fn sweep(
+        profile: impl IntoIterator<Item = T::Vec2>,
+        path: impl IntoIterator<Item = T::Vec>,
+        twist: impl Fn(T::S) -> T::S,
+        scale: impl Fn(T::S) -> T::S,
+        caps: bool,
+    ) -> Self {
+        let mut mesh = Self::default();
+        mesh.insert_sweep(profile, path, twist, scale, caps);
+        mesh
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "nalgebra")]
+mod tests {
+    use crate::test_util::volume;
+    use crate::{extensions::nalgebra::*, prelude::*};
+
+    fn square() -> Vec<Vec2<f64>> {
+        vec![
+            Vec2::new(-0.5, -0.5),
+            Vec2::new(0.5, -0.5),
+            Vec2::new(0.5, 0.5),
+            Vec2::new(-0.5, 0.5),
+        ]
+    }
+
+    #[test]
+    fn test_sweep_straight() {
+        let path = (0..4).map(|i| Vec3::new(0.0, 0.0, i as f64));
+        let mesh = Mesh3d64::sweep(square(), path, |_| 0.0, |_| 1.0, true);
+        assert!(mesh.check().is_ok());
+        assert!(mesh.edges().all(|e| !e.is_boundary_self()));
+        assert_eq!(mesh.num_vertices(), 4 * 4);
+        assert_eq!(mesh.num_faces(), 3 * 4 + 2);
+        assert!((volume(&mesh) - 3.0).abs() < 1e-10);
+
+        // an eighth twist moves the corners of the last profile onto the axes of the frame
+        let path = (0..=8).map(|i| Vec3::new(0.0, 0.0, i as f64 / 4.0));
+        let mesh = Mesh3d64::sweep(
+            square(),
+            path,
+            |t| t * std::f64::consts::FRAC_PI_4,
+            |_| 1.0,
+            true,
+        );
+        assert!(mesh.check().is_ok());
+        let on_axes = |z: f64| {
+            mesh.vertices()
+                .map(|v| v.pos())
+                .filter(|p| (p.z - z).abs() < 1e-10)
+                .filter(|p| p.x.abs() < 1e-10 || p.y.abs() < 1e-10)
+                .count()
+        };
+        assert_eq!(on_axes(0.0), 0);
+        assert_eq!(on_axes(2.0), 4);
+
+        // linear scale: a frustum
+        let path = [Vec3::new(0.0, 0.0, 0.0), Vec3::new(0.0, 0.0, 1.0)];
+        let mesh = Mesh3d64::sweep(square(), path, |_| 0.0, |t| 1.0 - t * 0.5, true);
+        assert!((volume(&mesh) - (1.0 + 0.5 + 0.25) / 3.0).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_sweep_open() {
+        let path = [Vec3::new(0.0, 0.0, 0.0), Vec3::new(1.0, 1.0, 0.0)];
+        let mut mesh = Mesh3d64::new();
+        let e = mesh.insert_sweep(square(), path, |_| 0.0, |_| 1.0, false);
+        assert!(mesh.check().is_ok());
+        assert!(mesh.edge(e).is_boundary_self());
+        assert_eq!(mesh.num_faces(), 4);
+        assert_eq!(mesh.edges().filter(|e| e.is_boundary_self()).count(), 8);
+    }
+
+    #[test]
+    fn test_sweep_degenerate_path() {
+        // duplicate points are ignored
+        let path = [0.0, 0.0, 1.0, 1.0, 1.0, 2.0].map(|z| Vec3::new(0.0, 0.0, z));
+        let mesh = Mesh3d64::sweep(square(), path, |_| 0.0, |_| 1.0, true);
+        assert!(mesh.check().is_ok());
+        assert_eq!(mesh.num_vertices(), 3 * 4);
+        assert!((volume(&mesh) - 2.0).abs() < 1e-10);
+
+        let mut mesh = Mesh3d64::new();
+        let point = [Vec3::new(1.0, 2.0, 3.0); 3];
+        assert!(matches!(
+            mesh.try_insert_sweep(square(), point, |_| 0.0, |_| 1.0, true),
+            Err(ModellingError::DegenerateFace(_))
+        ));
+        let u_turn = [0.0, 1.0, 0.5].map(|z| Vec3::new(0.0, 0.0, z));
+        assert_eq!(
+            mesh.try_insert_sweep(square(), u_turn, |_| 0.0, |_| 1.0, true),
+            Err(ModellingError::DegenerateFace(
+                "The path turns back on itself at point 1".to_string()
+            ))
+        );
+        let nan = [Vec3::new(0.0, 0.0, 0.0), Vec3::new(0.0, f64::NAN, 1.0)];
+        assert!(matches!(
+            mesh.try_insert_sweep(square(), nan, |_| 0.0, |_| 1.0, true),
+            Err(ModellingError::NonFinite(_))
+        ));
+
+        // the profile, twist, and scale are validated, too
+        let path = [Vec3::new(0.0, 0.0, 0.0), Vec3::new(0.0, 0.0, 1.0)];
+        let mut profile = square();
+        profile[2] = Vec2::new(f64::INFINITY, 0.5);
+        assert!(matches!(
+            mesh.try_insert_sweep(profile, path, |_| 0.0, |_| 1.0, true),
+            Err(ModellingError::NonFinite(_))
+        ));
+        assert!(matches!(
+            mesh.try_insert_sweep(square(), path, |t| t / 0.0, |_| 1.0, true),
+            Err(ModellingError::NonFinite(_))
+        ));
+        assert!(matches!(
+            mesh.try_insert_sweep(square(), path, |_| 0.0, |_| f64::NAN, true),
+            Err(ModellingError::NonFinite(_))
+        ));
+        for scale in [0.0, -1.0] {
+            assert!(matches!(
+                mesh.try_insert_sweep(square(), path, |_| 0.0, |t| 1.0 - t + scale * t, true),
+                Err(ModellingError::DegenerateFace(_))
+            ));
+        }
+        assert_eq!(mesh.num_vertices(), 0);
+        assert!(bezier_path::<f64, 3, Vec3<f64>>(&[], 4).is_empty());
+        assert!(bezier_path(&path, 0).is_empty());
+    }
+
+    #[test]
+    fn test_sweep_bezier() {
+        let path: Vec<Vec3<f64>> = bezier_path(
+            &[
+                Vec3::new(0.0, 0.0, 0.0),
+                Vec3::new(2.0, 0.0, 0.0),
+                Vec3::new(2.0, 2.0, 0.0),
+                Vec3::new(2.0, 2.0, 2.0),
+            ],
+            16,
+        );
+        assert_eq!(path.len(), 17);
+        assert!(path[16].is_about(&Vec3::new(2.0, 2.0, 2.0), 1e-10));
+
+        let frames = rotation_minimizing_frames(&path);
+        for (r, s, t) in &frames {
+            assert!((r.norm() - 1.0).abs() < 1e-10);
+            assert!(r.dot(t).abs() < 1e-10);
+            assert!(s.dot(t).abs() < 1e-10);
+        }
+
+        let profile = (0..8).map(|i| {
+            let a = std::f64::consts::PI * i as f64 / 4.0;
+            Vec2::new(0.1 * a.cos(), 0.1 * a.sin())
+        });
+        let mesh = Mesh3d64::sweep(profile, path, |_| 0.0, |_| 1.0, true);
+        assert!(mesh.check().is_ok());
+        assert_eq!(mesh.num_vertices(), 17 * 8);
+        assert!(volume(&mesh) > 0.0);
+    }
+}
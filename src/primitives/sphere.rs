@@ -1,5 +1,5 @@
 use crate::{
-    math::{HasPosition, IndexType, Scalar, Vector},
+    math::{HasPosition, Scalar, Vector},
     mesh::{
         DefaultEdgePayload, DefaultFacePayload, HalfEdge, HalfEdgeSemiBuilder, MeshType3D,
         MeshTypeHalfEdge, SlerpVertexInterpolator,
@@ -87,7 +87,8 @@ where
         ]);
 
         // TODO: polygon should return something more helpful
-        let start = mesh.shared_edge_id(T::V::new(1), T::V::new(0)).unwrap();
+        let ids: Vec<T::V> = mesh.vertex_ids().take(2).collect();
+        let start = mesh.shared_edge_id(ids[1], ids[0]).unwrap();
         let start_middle = mesh.loft_polygon_back(
             start,
             3,
@@ -1,9 +1,8 @@
 use super::Triangulation;
 use crate::{
     math::{IndexType, Scalar, Vector, Vector2D},
-    mesh::{Face, Face3d, FaceBasics, MeshType3D},
+    mesh::{Face, Face3d, FaceBasics, MeshType3D, ModellingError},
 };
-use itertools::Itertools;
 use spade::{ConstrainedDelaunayTriangulation, Point2, Triangulation as _};
 use std::collections::HashMap;
 
@@ -16,28 +15,69 @@ pub fn delaunay_triangulation<T: MeshType3D>(
     debug_assert!(face.may_be_curved() || face.is_planar2(mesh));
     debug_assert!(!face.has_self_intersections(mesh));
 
+    if let Err(e) = try_delaunay_triangulation::<T>(face, mesh, tri) {
+        panic!("{}", e);
+    }
+}
+
+/// Like `delaunay_triangulation`, but fails instead of panicking if two vertices
+/// of the face have the same position or the face intersects itself.
+/// Doesn't insert any triangles in that case.
+pub fn try_delaunay_triangulation<T: MeshType3D>(
+    face: &T::Face,
+    mesh: &T::Mesh,
+    tri: &mut Triangulation<T::V>,
+) -> Result<(), ModellingError> {
     let mut cdt = ConstrainedDelaunayTriangulation::<Point2<_>>::default();
     // PERF: faster: ConstrainedDelaunayTriangulation::bulk_load()
     // PERF: allow Delaunay refinements!
-    let mut last = None;
-    let mut first = None;
+    let (vec2s, hole_starts) = face.vec2s_with_holes(mesh);
+    let n = vec2s.len();
+
+    // the neighbors of each vertex within its loop
+    let mut prev = vec![0; n];
+    let mut next = vec![0; n];
+    let mut start = 0;
+    for end in hole_starts.iter().copied().chain(std::iter::once(n)) {
+        for i in start..end {
+            prev[i] = if i == start { end - 1 } else { i - 1 };
+            next[i] = if i + 1 == end { start } else { i + 1 };
+        }
+        start = end;
+    }
+
     let mut i2v: Vec<T::V> = Vec::new();
-    let vec2s = face.vertices_2d(mesh).collect_vec();
-    for (i2, (vec2, global_index)) in vec2s.iter().enumerate() {
-        i2v.push(*global_index);
+    let mut spade_vertices = Vec::with_capacity(n);
+    for (i2, v) in vec2s.iter().enumerate() {
+        i2v.push(v.index);
         let spade_vertex = cdt
-            .insert(Point2::new(vec2.x().to_f64(), vec2.y().to_f64()))
-            .unwrap();
-        // TODO: Handle meshes with vertices with the same position
-        assert!(spade_vertex.index() == i2);
-        if let Some(j) = last {
-            assert!(cdt.add_constraint(j, spade_vertex));
-        } else {
-            first = Some(spade_vertex);
+            .insert(Point2::new(v.vec.x().to_f64(), v.vec.y().to_f64()))
+            .map_err(|e| {
+                ModellingError::DegenerateFace(format!(
+                    "Vertex {} has an invalid position: {:?}",
+                    v.index, e
+                ))
+            })?;
+        if spade_vertex.index() != i2 {
+            // spade returns the existing vertex when inserting the same position twice
+            return Err(ModellingError::DuplicatePositions {
+                first: i2v[spade_vertex.index()].index(),
+                second: v.index.index(),
+            });
         }
-        last = Some(spade_vertex);
+        spade_vertices.push(spade_vertex);
+    }
+    for i in 0..n {
+        if !cdt.can_add_constraint(spade_vertices[i], spade_vertices[next[i]]) {
+            return Err(ModellingError::DegenerateFace(format!(
+                "Face {} intersects itself at the edge from vertex {} to {}",
+                face.id(),
+                i2v[i],
+                i2v[next[i]]
+            )));
+        }
+        cdt.add_constraint(spade_vertices[i], spade_vertices[next[i]]);
     }
-    assert!(cdt.add_constraint(last.unwrap(), first.unwrap()));
 
     //let v2d = face.vertices_2d(mesh).collect::<Vec<_>>();
     //let poly = T::Poly::from_iter(v2d.iter().map(|(v, _)| v.clone()));
@@ -78,35 +118,24 @@ pub fn delaunay_triangulation<T: MeshType3D>(
                 }
             }
 
-            fn is_edge_inside<V: IndexType, V2: Vector2D>(
-                vec2s: &[(V2, V)],
-                v: usize,
-                other_v: usize,
-            ) -> bool {
-                let n = vec2s.len();
-                let prev = vec2s[(v + n - 1) % n].0;
-                let next = vec2s[(v + 1) % n].0;
-                let triangle_angle = wrap_angle(vec2s[v].0.angle_tri(vec2s[other_v].0, prev));
-                let boundary_angle = wrap_angle(vec2s[v].0.angle_tri(next, prev));
+            let is_edge_inside = |v: usize, other_v: usize| -> bool {
+                let prev = vec2s[prev[v]].vec;
+                let next = vec2s[next[v]].vec;
+                let triangle_angle = wrap_angle(vec2s[v].vec.angle_tri(vec2s[other_v].vec, prev));
+                let boundary_angle = wrap_angle(vec2s[v].vec.angle_tri(next, prev));
                 triangle_angle <= boundary_angle
-            }
+            };
 
             {
-                let is_inside = is_edge_inside(&vec2s, p0.index(), p1.index());
+                let is_inside = is_edge_inside(p0.index(), p1.index());
                 if is_inside {
                     tri.insert_triangle(v0, v1, v2);
                 }
 
                 // we expect the same result for all 6 edge orientations
                 for (v, other_v) in &[(p0, p1), (p1, p2), (p2, p0)] {
-                    debug_assert_eq!(
-                        is_edge_inside(&vec2s, v.index(), other_v.index()),
-                        is_inside
-                    );
-                    debug_assert_eq!(
-                        is_edge_inside(&vec2s, other_v.index(), v.index()),
-                        is_inside
-                    );
+                    debug_assert_eq!(is_edge_inside(v.index(), other_v.index()), is_inside);
+                    debug_assert_eq!(is_edge_inside(other_v.index(), v.index()), is_inside);
                 }
             }
         }
@@ -114,14 +143,20 @@ pub fn delaunay_triangulation<T: MeshType3D>(
 
     // TODO: make tests to perform these tests. This is too slow, even for debug builds!
     debug_assert!({
-        let vec2s = face.vec2s(mesh);
         let vec_hm: HashMap<T::V, T::Vec2> = vec2s.iter().map(|v| (v.index, v.vec)).collect();
-        tri.verify_indices(&vec_hm);
+        if hole_starts.is_empty() {
+            tri.verify_indices(&vec_hm);
+        } else {
+            // every hole adds two triangles
+            assert_eq!(tri.len(), n + 2 * hole_starts.len() - 2);
+        }
         tri.verify_all_indices_used(&vec2s);
         tri.verify_no_intersections(&vec_hm);
         tri.verify_non_degenerate_triangle(&vec_hm);
         true
     });
+
+    Ok(())
 }
 
 #[cfg(test)]
@@ -67,6 +67,9 @@ pub struct TesselationMeta<V: IndexType> {
 }
 
 /// Triangulate a face using the specified algorithm.
+///
+/// Faces with holes are only supported by the sweep and Delaunay algorithms.
+/// All other algorithms fall back to `Delaunay` for such faces.
 pub fn triangulate_face<T: MeshType3D>(
     face: &T::Face,
     mesh: &T::Mesh,
@@ -82,6 +85,26 @@ pub fn triangulate_face<T: MeshType3D>(
         n
     );
 
+    if face.has_holes() {
+        match algorithm {
+            TriangulationAlgorithm::Sweep => {
+                sweep_line::<T, LinearMonoTriangulator<T::V, T::Vec2>>(face, mesh, tri, meta);
+            }
+            TriangulationAlgorithm::SweepDynamic => {
+                sweep_line::<T, DynamicMonoTriangulator<T::V, T::Vec2, T::Poly>>(
+                    face, mesh, tri, meta,
+                );
+            }
+            TriangulationAlgorithm::SweepDelaunay => {
+                sweep_line::<T, DelaunayMonoTriangulator<T::V, T::Vec2>>(face, mesh, tri, meta);
+            }
+            _ => {
+                delaunay_triangulation::<T>(face, mesh, tri);
+            }
+        }
+        return;
+    }
+
     if try_min_weight_small::<T>(face, mesh, tri) {
         return;
     }
@@ -172,3 +195,72 @@ mod tests {
     }
 }
 */
+
+#[cfg(test)]
+#[cfg(feature = "nalgebra")]
+mod hole_tests {
+    use crate::{extensions::nalgebra::*, prelude::*, test_util::square_with_hole};
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_face_with_hole() {
+        let (mesh, f, _) = square_with_hole();
+        assert!(mesh.check().is_ok(), "{:?}", mesh.check());
+        let face = mesh.face(f);
+        assert!(face.has_holes());
+        assert_eq!(face.num_holes(), 1);
+        assert_eq!(face.num_vertices(&mesh), 4);
+        assert_eq!(face.hole_vertices(&mesh, 0).count(), 4);
+        assert_eq!(face.num_triangles(&mesh), 8 * 3);
+        assert!(face.hole_edges(&mesh, 0).all(|e| e.face_id() == f));
+
+        let mut broken = mesh.clone();
+        broken.face_mut(f).take_holes();
+        assert!(broken.check().is_err());
+
+        let mut removed = mesh.clone();
+        removed.remove_face(f);
+        assert!(removed.edges().all(|e| e.is_boundary_self()));
+    }
+
+    #[test]
+    fn test_triangulate_face_with_hole() {
+        let (mesh, f, _) = square_with_hole();
+        let face = mesh.face(f);
+        let (vec2s, _) = face.vec2s_with_holes(&mesh);
+        let vec_hm: HashMap<usize, Vec2<f64>> = vec2s.iter().map(|v| (v.index, v.vec)).collect();
+        for algorithm in [
+            TriangulationAlgorithm::Auto,
+            TriangulationAlgorithm::Delaunay,
+            TriangulationAlgorithm::Sweep,
+            TriangulationAlgorithm::SweepDynamic,
+            TriangulationAlgorithm::SweepDelaunay,
+            TriangulationAlgorithm::EarClipping,
+        ] {
+            let mut indices = Vec::new();
+            let mut tri = Triangulation::new(&mut indices);
+            triangulate_face::<MeshType3d64PNU>(
+                face,
+                &mesh,
+                &mut tri,
+                algorithm,
+                &mut TesselationMeta::default(),
+            );
+            assert_eq!(tri.len(), 8, "{:?}", algorithm);
+            tri.verify_no_intersections(&vec_hm);
+            // the area of the outer square minus the hole (twice the area due to the determinant)
+            let area: f64 = (0..tri.len())
+                .map(|i| tri.get_triangle_area(i, &vec_hm))
+                .sum();
+            assert!(area.is_about(2.0 * 12.0, 1e-8), "{:?}: {}", algorithm, area);
+        }
+
+        // the hole vertices are part of the exported vertex buffer
+        let (indices, vertices) = mesh.triangulate_and_generate_flat_normals_post(
+            Default::default(),
+            &mut Default::default(),
+        );
+        assert_eq!(indices.len(), 8 * 3);
+        assert_eq!(vertices.len(), 8);
+    }
+}
@@ -7,7 +7,7 @@ mod sweep;
 mod vertex_type;
 
 pub use monotone::*;
-pub use sweep::sweep_line_triangulation;
+pub use sweep::{sweep_line_triangulation, sweep_line_triangulation_with_holes};
 pub use vertex_type::VertexType;
 
 use super::TesselationMeta;
@@ -59,6 +59,12 @@ pub fn sweep_line<T: MeshType3D, Tri: MonotoneTriangulator<V = T::V, Vec2 = T::V
     debug_assert!(face.may_be_curved() || face.is_planar2(mesh));
 
     // TODO: Improve performance by directly using the nd-vertices instead of converting to 2d
+    if face.has_holes() {
+        let (vec2s, hole_starts) = face.vec2s_with_holes(mesh);
+        sweep_line_triangulation_with_holes::<Tri>(indices, &vec2s, &hole_starts, &mut meta.sweep);
+        return;
+    }
+
     let vec2s: Vec<_> = face
         .vertices_2d(mesh)
         .map(|(p, i)| IndexedVertex2D::<T::V, T::Vec2>::new(p, i))
@@ -440,7 +440,9 @@ impl<V: IndexType, Vec2: Vector2D> MonotoneTriangulator for DelaunayMonoTriangul
         } else if self.d == ChainDirection::Right {
             self.last_left.unwrap()
         } else {
-            self.last_right.unwrap()
+            // the start vertex is part of both chains, so the right chain might
+            // not have any other vertex yet, e.g., after a horizontal edge
+            self.last_right.unwrap_or(self.first())
         };
 
         assert!(res == self.first());
@@ -102,8 +102,10 @@ impl<V: IndexType, Vec2: Vector2D, Poly: Polygon<Vec2>> MonotoneTriangulator
             assert!(self.left.len() > 0);
             self.left[self.left.len() - 1]
         } else {
-            assert!(self.right.len() > 0);
-            self.right[self.right.len() - 1]
+            // the start vertex is part of both chains, so the right chain might
+            // be empty, e.g., after a horizontal edge
+            assert!(!self.left.is_empty());
+            *self.right.last().unwrap_or(&self.left[0])
         }
     }
 
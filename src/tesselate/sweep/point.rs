@@ -22,8 +22,19 @@ where
 
 impl<Vec2: Vector2D> EventPoint<Vec2> {
     pub fn classify<V: IndexType>(here: usize, vec2s: &Vec<IndexedVertex2D<V, Vec2>>) -> Self {
-        let prev = (here + vec2s.len() - 1) % vec2s.len();
-        let next = (here + 1) % vec2s.len();
+        Self::classify_in_loop(here, 0, vec2s.len(), vec2s)
+    }
+
+    /// Classifies the vertex `here` of the loop stored in `vec2s[start..end]`.
+    pub fn classify_in_loop<V: IndexType>(
+        here: usize,
+        start: usize,
+        end: usize,
+        vec2s: &[IndexedVertex2D<V, Vec2>],
+    ) -> Self {
+        let n = end - start;
+        let prev = start + (here - start + n - 1) % n;
+        let next = start + (here - start + 1) % n;
 
         EventPoint {
             here,
@@ -22,13 +22,29 @@ pub fn sweep_line_triangulation<MT: MonotoneTriangulator>(
     indices: &mut Triangulation<MT::V>,
     vec2s: &Vec<IndexedVertex2D<MT::V, MT::Vec2>>,
     meta: &mut SweepMeta<MT::V>,
+) {
+    sweep_line_triangulation_with_holes::<MT>(indices, vec2s, &[], meta);
+}
+
+/// Like `sweep_line_triangulation`, but `vec2s` contains the counter-clockwise outer boundary
+/// followed by the clockwise holes. `hole_starts` is the index of the first vertex of each hole.
+pub fn sweep_line_triangulation_with_holes<MT: MonotoneTriangulator>(
+    indices: &mut Triangulation<MT::V>,
+    vec2s: &Vec<IndexedVertex2D<MT::V, MT::Vec2>>,
+    hole_starts: &[usize],
+    meta: &mut SweepMeta<MT::V>,
 ) {
     let n = vec2s.len();
     assert!(n >= 3, "At least 3 vertices are required");
 
     let mut event_queue: Vec<EventPoint<MT::Vec2>> = Vec::with_capacity(n);
-    for i in 0..n {
-        event_queue.push(EventPoint::classify(i, &vec2s));
+    let loop_ends = hole_starts.iter().copied().chain(std::iter::once(n));
+    let mut start = 0;
+    for end in loop_ends {
+        for i in start..end {
+            event_queue.push(EventPoint::classify_in_loop(i, start, end, vec2s));
+        }
+        start = end;
     }
     event_queue.sort_unstable();
 
@@ -516,6 +532,52 @@ mod tests {
         ]));
     }
 
+    #[test]
+    fn sweep_horizontal_notch() {
+        verify_triangulations(&liv_from_array(&[
+            [-2.0, -2.0],
+            [-1.0, -2.0],
+            [-1.0, 1.0],
+            [1.0, 1.0],
+            [1.0, -2.0],
+            [2.0, -2.0],
+            [2.0, 2.0],
+            [-2.0, 2.0],
+        ]));
+    }
+
+    #[test]
+    fn sweep_square_with_hole() {
+        let vec2s = liv_from_array(&[
+            // outer boundary
+            [-2.0, -2.0],
+            [2.0, -2.0],
+            [2.0, 2.0],
+            [-2.0, 2.0],
+            // hole
+            [-1.0, -1.0],
+            [-1.0, 1.0],
+            [1.0, 1.0],
+            [1.0, -1.0],
+        ]);
+        let vec_hm: HashMap<usize, Vec2<f64>> = vec2s.iter().map(|v| (v.index, v.vec)).collect();
+
+        let mut indices = Vec::new();
+        let mut tri = Triangulation::new(&mut indices);
+        sweep_line_triangulation_with_holes::<LinearMonoTriangulator<usize, Vec2<f64>>>(
+            &mut tri,
+            &vec2s,
+            &[4],
+            &mut SweepMeta::default(),
+        );
+        assert_eq!(tri.len(), 8);
+        tri.verify_no_intersections(&vec_hm);
+        let area: f64 = (0..tri.len())
+            .map(|i| tri.get_triangle_area(i, &vec_hm))
+            .sum();
+        assert!((area - 24.0).abs() < 1e-10);
+    }
+
     #[test]
     fn sweep_tricky_shape() {
         verify_triangulations(&liv_from_array(&[
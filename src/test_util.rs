@@ -0,0 +1,60 @@
+//! Fixtures shared by the tests of the operations, the tesselation, and the file formats.
+
+use crate::{extensions::nalgebra::*, prelude::*};
+
+/// Returns the signed volume enclosed by the faces of the mesh.
+pub(crate) fn volume(mesh: &Mesh3d64) -> f64 {
+    mesh.faces()
+        .map(|f| {
+            let ps: Vec<Vec3<f64>> = f.vertices(mesh).map(|v| v.pos()).collect();
+            (1..ps.len() - 1)
+                .map(|i| ps[0].dot(&ps[i].cross(&ps[i + 1])) / 6.0)
+                .sum::<f64>()
+        })
+        .sum()
+}
+
+/// Returns the area of the outer boundary of a convex face.
+pub(crate) fn face_area(mesh: &Mesh3d64, f: usize) -> f64 {
+    let ps: Vec<Vec3<f64>> = mesh.face(f).vertices(mesh).map(|v| v.pos()).collect();
+    (1..ps.len() - 1)
+        .map(|i| (ps[i] - ps[0]).cross(&(ps[i + 1] - ps[0])).norm() / 2.0)
+        .sum()
+}
+
+/// Returns the total area of the convex faces of the mesh.
+pub(crate) fn area(mesh: &Mesh3d64) -> f64 {
+    mesh.face_ids().map(|f| face_area(mesh, f)).sum()
+}
+
+/// A flat square with a square hole, i.e., an "O". Returns the mesh, its face,
+/// and a halfedge of the outer boundary that doesn't belong to the face.
+pub(crate) fn square_with_hole() -> (Mesh3d64, usize, usize) {
+    square_with_hole_in::<3, MeshType3d64PNU>()
+}
+
+/// Like `square_with_hole`, but for any half-edge mesh type, e.g., 2d meshes.
+pub(crate) fn square_with_hole_in<const D: usize, T: EuclideanMeshType<D> + MeshTypeHalfEdge>(
+) -> (T::Mesh, T::F, T::E)
+where
+    T::EP: DefaultEdgePayload,
+    T::FP: DefaultFacePayload,
+    T::Mesh: Make2dShape<T>,
+{
+    let square = |r: f64| {
+        [(-r, -r), (r, -r), (r, r), (-r, r)]
+            .map(|(x, y)| T::VP::from_pos(T::Vec::from_xy(T::S::from_f64(x), T::S::from_f64(y))))
+    };
+    let mut mesh = T::Mesh::default();
+    let outer = mesh.insert_polygon(square(2.0));
+    let f = mesh.edge(outer).twin(&mesh).face_id();
+    let inner = mesh.insert_loop(square(1.0));
+    mesh.insert_hole(f, mesh.edge(inner).twin_id());
+    (mesh, f, outer)
+}
+
+/// Asserts that the mesh is consistent and has no boundary edges.
+pub(crate) fn assert_closed(mesh: &Mesh3d64) {
+    assert!(mesh.check().is_ok());
+    assert!(mesh.edges().all(|e| !e.is_boundary_self()));
+}
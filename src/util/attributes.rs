@@ -0,0 +1,405 @@
+//! A module for named attribute layers that can be attached to mesh elements at runtime.
+
+use crate::math::IndexType;
+use std::{any::Any, collections::HashMap, marker::PhantomData};
+
+/// A value that can be stored in an attribute layer.
+pub trait Attribute: Clone + Default + 'static {
+    /// Blends `self` (at `t = 0`) with `other` (at `t = 1`).
+    /// Used to derive the attributes of new elements, e.g., when subdividing an edge.
+    fn interpolate(&self, other: &Self, t: f64) -> Self;
+}
+
+macro_rules! impl_attribute_lerp {
+    ($($t:ty),*) => {
+        $(
+            impl Attribute for $t {
+                fn interpolate(&self, other: &Self, t: f64) -> Self {
+                    self + (other - self) * (t as $t)
+                }
+            }
+
+            impl<const N: usize> Attribute for [$t; N]
+            where
+                [$t; N]: Default,
+            {
+                fn interpolate(&self, other: &Self, t: f64) -> Self {
+                    std::array::from_fn(|i| self[i].interpolate(&other[i], t))
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! impl_attribute_nearest {
+    ($($t:ty),*) => {
+        $(
+            impl Attribute for $t {
+                fn interpolate(&self, other: &Self, t: f64) -> Self {
+                    if t < 0.5 {
+                        self.clone()
+                    } else {
+                        other.clone()
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_attribute_lerp!(f32, f64);
+impl_attribute_nearest!(bool, u8, u16, u32, u64, usize, i8, i16, i32, i64, isize, String);
+
+/// Type-erased storage of a single attribute layer.
+trait AttributeLayer: Any {
+    fn as_any(&self) -> &dyn Any;
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+    fn clone_box(&self) -> Box<dyn AttributeLayer>;
+    fn resize(&mut self, len: usize);
+    fn reset(&mut self, i: usize);
+    fn interpolate(&mut self, target: usize, a: usize, b: usize, t: f64);
+    fn remap(&mut self, map: &[(usize, usize)], len: usize);
+    fn empty(&self, len: usize) -> Box<dyn AttributeLayer>;
+    fn copy_from(&mut self, other: &dyn AttributeLayer, map: &[(usize, usize)]);
+}
+
+impl<A: Attribute> AttributeLayer for Vec<A> {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn clone_box(&self) -> Box<dyn AttributeLayer> {
+        Box::new(self.clone())
+    }
+
+    fn resize(&mut self, len: usize) {
+        if self.len() < len {
+            Vec::resize(self, len, A::default());
+        }
+    }
+
+    fn reset(&mut self, i: usize) {
+        self[i] = A::default();
+    }
+
+    fn interpolate(&mut self, target: usize, a: usize, b: usize, t: f64) {
+        self[target] = self[a].interpolate(&self[b], t);
+    }
+
+    fn remap(&mut self, map: &[(usize, usize)], len: usize) {
+        let mut values = vec![A::default(); len];
+        for &(old, new) in map {
+            values[new] = std::mem::take(&mut self[old]);
+        }
+        *self = values;
+    }
+
+    fn empty(&self, len: usize) -> Box<dyn AttributeLayer> {
+        Box::new(vec![A::default(); len])
+    }
+
+    fn copy_from(&mut self, other: &dyn AttributeLayer, map: &[(usize, usize)]) {
+        if let Some(other) = other.as_any().downcast_ref::<Vec<A>>() {
+            for &(old, new) in map {
+                self[new] = other[old].clone();
+            }
+        }
+    }
+}
+
+/// Named, typed attribute layers for one kind of mesh element (vertices, halfedges, or faces).
+///
+/// Every layer stores one value per element slot. The mesh keeps the layers in sync:
+/// new elements start with the default value, deleted elements are reset,
+/// and subdivisions interpolate the values of the neighbors.
+pub struct AttributeStore<I: IndexType> {
+    layers: HashMap<String, Box<dyn AttributeLayer>>,
+    len: usize,
+    phantom: PhantomData<I>,
+}
+
+impl<I: IndexType> AttributeStore<I> {
+    /// Creates a new store without layers.
+    pub fn new() -> Self {
+        Self {
+            layers: HashMap::new(),
+            len: 0,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Adds a new layer with the given name where every element has the default value.
+    /// Returns `false` (and keeps the old layer) if a layer with that name already exists.
+    pub fn add<A: Attribute>(&mut self, name: &str) -> bool {
+        if self.layers.contains_key(name) {
+            return false;
+        }
+        self.layers
+            .insert(name.to_string(), Box::new(vec![A::default(); self.len]));
+        true
+    }
+
+    /// Removes the layer with the given name. Returns whether it existed.
+    pub fn remove(&mut self, name: &str) -> bool {
+        self.layers.remove(name).is_some()
+    }
+
+    /// Returns whether there is a layer with the given name.
+    pub fn contains(&self, name: &str) -> bool {
+        self.layers.contains_key(name)
+    }
+
+    /// Returns whether there is a layer with the given name and value type.
+    pub fn contains_typed<A: Attribute>(&self, name: &str) -> bool {
+        self.values::<A>(name).is_some()
+    }
+
+    /// Returns an iterator over the names of all layers.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.layers.keys().map(|name| name.as_str())
+    }
+
+    /// Returns the values of the layer indexed by slot.
+    /// Slots of deleted elements have the default value.
+    /// Returns `None` if there is no such layer with that value type.
+    pub fn values<A: Attribute>(&self, name: &str) -> Option<&[A]> {
+        self.layers
+            .get(name)
+            .and_then(|layer| layer.as_any().downcast_ref::<Vec<A>>())
+            .map(|values| values.as_slice())
+    }
+
+    fn values_mut<A: Attribute>(&mut self, name: &str) -> Option<&mut Vec<A>> {
+        self.layers
+            .get_mut(name)
+            .and_then(|layer| layer.as_any_mut().downcast_mut::<Vec<A>>())
+    }
+
+    /// Returns the value of the element in the given layer.
+    /// Returns `None` if there is no such layer with that value type.
+    pub fn get<A: Attribute>(&self, name: &str, i: I) -> Option<&A> {
+        self.values::<A>(name)
+            .and_then(|values| values.get(i.index()))
+    }
+
+    /// Returns a mutable reference to the value of the element in the given layer.
+    /// Returns `None` if there is no such layer with that value type.
+    pub fn get_mut<A: Attribute>(&mut self, name: &str, i: I) -> Option<&mut A> {
+        self.values_mut::<A>(name)
+            .and_then(|values| values.get_mut(i.index()))
+    }
+
+    /// Sets the value of the element in the given layer.
+    /// Panics if there is no such layer with that value type.
+    pub fn set<A: Attribute>(&mut self, name: &str, i: I, value: A) {
+        *self.get_mut(name, i).unwrap_or_else(|| {
+            panic!(
+                "There is no attribute layer '{}' of type {} with element {}",
+                name,
+                std::any::type_name::<A>(),
+                i
+            )
+        }) = value;
+    }
+
+    /// Makes sure all layers have at least `len` slots.
+    pub(crate) fn reserve(&mut self, len: usize) {
+        if len > self.len {
+            self.len = len;
+            for layer in self.layers.values_mut() {
+                layer.resize(len);
+            }
+        }
+    }
+
+    /// Called when an element is inserted (possibly into the slot of a deleted one).
+    pub(crate) fn insert(&mut self, i: I) {
+        self.reserve(i.index() + 1);
+        self.delete(i);
+    }
+
+    /// Called when an element is deleted.
+    pub(crate) fn delete(&mut self, i: I) {
+        for layer in self.layers.values_mut() {
+            layer.reset(i.index());
+        }
+    }
+
+    /// Sets the values of `target` to the interpolation between `a` (at `t = 0`) and `b` (at `t = 1`).
+    pub(crate) fn interpolate(&mut self, target: I, a: I, b: I, t: f64) {
+        for layer in self.layers.values_mut() {
+            layer.interpolate(target.index(), a.index(), b.index(), t);
+        }
+    }
+
+    /// Resets all values but keeps the layers.
+    pub(crate) fn clear(&mut self) {
+        self.len = 0;
+        for layer in self.layers.values_mut() {
+            layer.remap(&[], 0);
+        }
+    }
+
+    /// Moves the values according to the old-to-new index map, see `DeletableVector::compact`.
+    pub(crate) fn compact(&mut self, map: &HashMap<I, I>) {
+        let map = map
+            .iter()
+            .map(|(old, new)| (old.index(), new.index()))
+            .collect::<Vec<_>>();
+        self.len = map.len();
+        for layer in self.layers.values_mut() {
+            layer.remap(&map, self.len);
+        }
+    }
+
+    /// Copies the values of `other` according to the other-to-self index map.
+    /// Layers that only exist in `other` are added, layers with the same name but a different type are skipped.
+    pub(crate) fn append(&mut self, other: &Self, map: &HashMap<I, I>) {
+        let map = map
+            .iter()
+            .map(|(old, new)| (old.index(), new.index()))
+            .collect::<Vec<_>>();
+        let len = self.len;
+        for (name, layer) in other.layers.iter() {
+            self.layers
+                .entry(name.clone())
+                .or_insert_with(|| layer.empty(len))
+                .copy_from(layer.as_ref(), &map);
+        }
+    }
+}
+
+impl<I: IndexType> Default for AttributeStore<I> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<I: IndexType> Clone for AttributeStore<I> {
+    fn clone(&self) -> Self {
+        Self {
+            layers: self
+                .layers
+                .iter()
+                .map(|(name, layer)| (name.clone(), layer.clone_box()))
+                .collect(),
+            len: self.len,
+            phantom: PhantomData,
+        }
+    }
+}
+
+/// The serialized values of a layer. Only the built-in attribute types can be serialized.
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+enum SerializedLayer {
+    F32(Vec<f32>),
+    F64(Vec<f64>),
+    Bool(Vec<bool>),
+    U8(Vec<u8>),
+    U16(Vec<u16>),
+    U32(Vec<u32>),
+    U64(Vec<u64>),
+    Usize(Vec<usize>),
+    I8(Vec<i8>),
+    I16(Vec<i16>),
+    I32(Vec<i32>),
+    I64(Vec<i64>),
+    Isize(Vec<isize>),
+    String(Vec<String>),
+    F32x2(Vec<[f32; 2]>),
+    F32x3(Vec<[f32; 3]>),
+    F32x4(Vec<[f32; 4]>),
+    F64x2(Vec<[f64; 2]>),
+    F64x3(Vec<[f64; 3]>),
+    F64x4(Vec<[f64; 4]>),
+}
+
+#[cfg(feature = "serde")]
+macro_rules! serialized_layer {
+    ($($variant:ident: $t:ty),*) => {
+        impl SerializedLayer {
+            fn from_layer(layer: &dyn AttributeLayer) -> Option<Self> {
+                $(
+                    if let Some(values) = layer.as_any().downcast_ref::<Vec<$t>>() {
+                        return Some(SerializedLayer::$variant(values.clone()));
+                    }
+                )*
+                None
+            }
+
+            fn into_layer(self) -> (Box<dyn AttributeLayer>, usize) {
+                match self {
+                    $(SerializedLayer::$variant(values) => {
+                        let len = values.len();
+                        (Box::new(values), len)
+                    })*
+                }
+            }
+        }
+    };
+}
+
+#[cfg(feature = "serde")]
+serialized_layer!(
+    F32: f32, F64: f64, Bool: bool, U8: u8, U16: u16, U32: u32, U64: u64, Usize: usize,
+    I8: i8, I16: i16, I32: i32, I64: i64, Isize: isize, String: String,
+    F32x2: [f32; 2], F32x3: [f32; 3], F32x4: [f32; 4],
+    F64x2: [f64; 2], F64x3: [f64; 3], F64x4: [f64; 4]
+);
+
+/// Layers of the built-in attribute types (numbers, `bool`, `String`, and arrays of 2 to 4 floats)
+/// are serialized. Serialization fails for layers of any other type.
+#[cfg(feature = "serde")]
+impl<I: IndexType> serde::Serialize for AttributeStore<I> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::Error;
+        let mut layers = Vec::with_capacity(self.layers.len());
+        for (name, layer) in self.layers.iter() {
+            let layer = SerializedLayer::from_layer(layer.as_ref()).ok_or_else(|| {
+                S::Error::custom(format!(
+                    "The attribute layer '{}' doesn't have a serializable type",
+                    name
+                ))
+            })?;
+            layers.push((name, layer));
+        }
+        // keep the output deterministic
+        layers.sort_by(|a, b| a.0.cmp(b.0));
+        (self.len, layers).serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, I: IndexType> serde::Deserialize<'de> for AttributeStore<I> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use serde::de::Error;
+        let (len, layers) = <(usize, Vec<(String, SerializedLayer)>)>::deserialize(deserializer)?;
+        let mut res = Self::new();
+        res.len = len;
+        for (name, layer) in layers {
+            let (layer, n) = layer.into_layer();
+            if n != len {
+                return Err(D::Error::custom(format!(
+                    "The attribute layer '{}' has {} values but expected {}",
+                    name, n, len
+                )));
+            }
+            res.layers.insert(name, layer);
+        }
+        Ok(res)
+    }
+}
+
+impl<I: IndexType> std::fmt::Debug for AttributeStore<I> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AttributeStore")
+            .field("layers", &self.layers.keys().collect::<Vec<_>>())
+            .field("len", &self.len)
+            .finish()
+    }
+}
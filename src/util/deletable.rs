@@ -1,6 +1,7 @@
 //! A module for soft-deletable elements.
 
 use crate::math::IndexType;
+use std::collections::HashMap;
 
 /// A trait for soft-deletable elements.
 pub trait Deletable<I> {
@@ -19,9 +20,16 @@ pub trait Deletable<I> {
 
 /// A vector that also keeps track of deleted elements to reallocate them.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct DeletableVector<T: Deletable<I>, I: IndexType> {
     data: Vec<T>,
     deleted: Vec<I>,
+
+    /// The generation of each slot. Empty unless the index type is generational.
+    generations: Vec<u32>,
+
+    /// The generation of newly allocated slots.
+    base_generation: u32,
 }
 
 impl<T: Deletable<I>, I: IndexType> DeletableVector<T, I> {
@@ -30,13 +38,45 @@ impl<T: Deletable<I>, I: IndexType> DeletableVector<T, I> {
         Self {
             data: Vec::new(),
             deleted: Vec::new(),
+            generations: Vec::new(),
+            base_generation: 0,
         }
     }
 
     /// Deletes all elements.
+    /// Handles of generational index types that are still around become stale.
     pub fn clear(&mut self) {
+        self.base_generation = self.next_generation();
         self.data.clear();
         self.deleted.clear();
+        self.generations.clear();
+    }
+
+    /// Returns a generation that is larger than any generation used so far.
+    fn next_generation(&self) -> u32 {
+        let max = self.generations.iter().copied().max().unwrap_or(0);
+        max.max(self.base_generation).wrapping_add(1)
+    }
+
+    /// Returns the current handle of the slot at the given position.
+    fn handle(&self, i: usize) -> I {
+        let generation = self.generations.get(i).copied();
+        I::new(i).with_generation(generation.unwrap_or(self.base_generation))
+    }
+
+    /// Adds a new slot at the end.
+    fn push_slot(&mut self, v: T) -> I {
+        if I::GENERATIONAL {
+            self.generations.push(self.base_generation);
+        }
+        self.data.push(v);
+        self.handle(self.data.len() - 1)
+    }
+
+    /// Returns whether the index refers to the current element in its slot.
+    /// Always true for plain integer indices.
+    fn is_current(&self, index: I) -> bool {
+        index == self.handle(index.index())
     }
 
     /// Returns an iterator over the non-deleted elements.
@@ -49,7 +89,7 @@ impl<T: Deletable<I>, I: IndexType> DeletableVector<T, I> {
         self.data.iter_mut().filter(|f| !f.is_deleted())
     }
 
-    /// Returns the requested element. Panics if it doesn't exist or is deleted.
+    /// Returns the requested element. Panics if it doesn't exist, is deleted, or the handle is stale.
     pub fn get(&self, index: I) -> &T {
         let v = &self.data[index.index()];
         assert!(
@@ -57,17 +97,38 @@ impl<T: Deletable<I>, I: IndexType> DeletableVector<T, I> {
             "Tried to access deleted element at {}",
             index
         );
+        assert!(
+            self.is_current(index),
+            "Tried to access element at {} via the stale handle {}",
+            self.handle(index.index()),
+            index
+        );
         v
     }
 
-    /// Returns whether the element exists and is not deleted.
+    /// Returns the requested element or `None` if it doesn't exist, is deleted, or the handle is stale.
+    pub fn try_get(&self, index: I) -> Option<&T> {
+        if self.has(index) {
+            Some(&self.data[index.index()])
+        } else {
+            None
+        }
+    }
+
+    /// Returns whether the element exists, is not deleted, and the handle is not stale.
     pub fn has(&self, index: I) -> bool {
         let i = index.index();
-        i < self.data.len() && !self.data[i].is_deleted()
+        i < self.data.len() && !self.data[i].is_deleted() && self.is_current(index)
     }
 
-    /// Returns the requested element mutably. Panics if it doesn't exist or is deleted.
+    /// Returns the requested element mutably. Panics if it doesn't exist, is deleted, or the handle is stale.
     pub fn get_mut(&mut self, index: I) -> &mut T {
+        assert!(
+            self.is_current(index),
+            "Tried to mutably access element at {} via the stale handle {}",
+            self.handle(index.index()),
+            index
+        );
         let v = &mut self.data[index.index()];
         assert!(
             !v.is_deleted(),
@@ -98,10 +159,9 @@ impl<T: Deletable<I>, I: IndexType> DeletableVector<T, I> {
             self.data[index.index()] = v;
             index
         } else {
-            let index = I::new(self.data.len());
+            let index = self.handle(self.data.len());
             v.set_id(index);
-            self.data.push(v);
-            index
+            self.push_slot(v)
         }
     }
 
@@ -121,9 +181,58 @@ impl<T: Deletable<I>, I: IndexType> DeletableVector<T, I> {
     }
 
     /// Marks the element as deleted and remembers it for reallocation.
+    /// Increments the generation of the slot, so existing handles become stale.
     pub fn delete_internal(&mut self, f: I) {
-        self.data[f.index()].delete();
-        self.deleted.push(f);
+        assert!(
+            self.is_current(f),
+            "Tried to delete element at {} via the stale handle {}",
+            self.handle(f.index()),
+            f
+        );
+        let i = f.index();
+        self.data[i].delete();
+        if I::GENERATIONAL {
+            self.generations[i] = self.generations[i].wrapping_add(1);
+        }
+        self.deleted.push(self.handle(i));
+    }
+
+    /// Returns the index each non-deleted element will have after calling `compact`.
+    pub fn compaction_map(&self) -> HashMap<I, I> {
+        let generation = self.next_generation();
+        self.data
+            .iter()
+            .enumerate()
+            .filter(|(_, v)| !v.is_deleted())
+            .enumerate()
+            .map(|(new, (old, _))| (self.handle(old), I::new(new).with_generation(generation)))
+            .collect()
+    }
+
+    /// Removes the deleted elements by moving the remaining elements to the front
+    /// and updates their ids. References to other elements are not updated.
+    /// Returns the old-to-new index map, see `compaction_map`.
+    pub fn compact(&mut self) -> HashMap<I, I> {
+        let map = self.compaction_map();
+        // all handles from before the compaction become stale
+        let generation = self.next_generation();
+        let data = std::mem::take(&mut self.data);
+        self.data = data
+            .into_iter()
+            .filter(|v| !v.is_deleted())
+            .enumerate()
+            .map(|(i, mut v)| {
+                v.delete();
+                v.set_id(I::new(i).with_generation(generation));
+                v
+            })
+            .collect();
+        self.deleted.clear();
+        if I::GENERATIONAL {
+            self.generations = vec![generation; self.data.len()];
+        }
+        self.base_generation = generation;
+        map
     }
 
     /// Returns the next free index or allocates a new one.
@@ -135,8 +244,7 @@ impl<T: Deletable<I>, I: IndexType> DeletableVector<T, I> {
         } else {
             let t = T::allocate();
             debug_assert!(t.is_deleted());
-            self.data.push(t);
-            I::new(self.data.len() - 1)
+            self.push_slot(t)
         }
     }
 }
@@ -1,5 +1,7 @@
 //! utility functions
 
+mod attributes;
 mod deletable;
 
+pub use attributes::*;
 pub use deletable::*;